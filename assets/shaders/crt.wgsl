@@ -0,0 +1,39 @@
+// CRT overlay shader
+//
+// Drawn as a fullscreen quad on top of the game. It only darkens what is
+// underneath, producing scanlines, a vignette and rounded, curved screen
+// edges that mimic the barrel distortion of an old tube.
+//
+// params.x - effect intensity (0.0 to 1.0)
+// params.y - number of scanlines across the screen height
+
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+@group(2) @binding(0) var<uniform> params: vec4<f32>;
+
+const PI: f32 = 3.14159265;
+
+@fragment
+fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+    let intensity = clamp(params.x, 0.0, 1.0);
+    let scanlines = params.y;
+
+    // Position relative to the screen center, from -1.0 to 1.0
+    let centered = mesh.uv * 2.0 - 1.0;
+
+    // Push positions outward the further they are from the center, so the
+    // corners of the screen fall outside the curved tube
+    let curved = centered * (1.0 + 0.12 * intensity * dot(centered, centered));
+    let edge = max(abs(curved.x), abs(curved.y));
+    let bezel = smoothstep(1.0 - 0.01, 1.0, edge);
+
+    // Dark bands between scanlines
+    let band = 0.5 - 0.5 * cos(mesh.uv.y * scanlines * 2.0 * PI);
+    let scan = band * 0.35 * intensity;
+
+    // Darken toward the corners
+    let vignette = smoothstep(0.5, 1.5, length(centered)) * 0.8 * intensity;
+
+    let alpha = clamp(max(scan + vignette, bezel), 0.0, 1.0);
+    return vec4<f32>(0.0, 0.0, 0.0, alpha);
+}
@@ -0,0 +1,161 @@
+//! Aim Assist Module
+//!
+//! This module provides the optional aim assist, which marks where the ball
+//! will cross each human player's paddle line, including:
+//! - A faint tick on the line at the predicted height, updated every frame
+//! - Only showing while the ball is heading toward that player
+//! - Fading in and out rather than popping on and off
+//!
+//! The prediction is the same one the AI plans with, from the trajectory
+//! module, so bounces off the top and bottom walls are included. The assist
+//! is off by default and is turned on from the menu.
+
+use crate::ball::{Ball, BallConfig};
+use crate::board::BoardConfig;
+use crate::player::{Controller, PaddleConfig, Player, PlayerRoles, PlayerSide};
+use crate::theme::Theme;
+use crate::trajectory::{ball_height_limit, predict_intersection};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Aim assist settings
+#[derive(Debug, Resource)]
+pub struct AssistSettings {
+    /// Whether human players get the marker
+    pub aim_assist: bool,
+    /// Size of the marker in world units
+    pub marker_size: Vec2,
+    /// Opacity of the marker once fully faded in
+    pub opacity: f32,
+    /// How quickly the marker fades in or out, in opacity per second
+    pub fade_rate: f32,
+}
+
+impl Default for AssistSettings {
+    fn default() -> Self {
+        Self {
+            aim_assist: false,                 // Opt in from the menu
+            marker_size: Vec2::new(0.5, 0.06), // A short, thin tick
+            opacity: 0.35,                     // Faint enough not to distract
+            fade_rate: 3.0,                    // A third of a second to fade
+        }
+    }
+}
+
+impl AssistSettings {
+    /// Label for the menu's aim assist line
+    pub fn label(&self) -> &'static str {
+        if self.aim_assist {
+            "On"
+        } else {
+            "Off"
+        }
+    }
+}
+
+/// Component for a human player's aim assist marker
+#[derive(Component)]
+struct AssistMarker {
+    /// Player whose paddle line the marker sits on
+    player: Player,
+    /// Current opacity, as a fraction of [`AssistSettings::opacity`]
+    fade: f32,
+}
+
+/// Plugin that draws the aim assist markers.
+pub struct AssistPlugin;
+
+impl Plugin for AssistPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AssistSettings>()
+            .add_systems(OnEnter(GameState::Playing), spawn_assist_markers)
+            .add_systems(
+                Update,
+                update_assist_markers.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), despawn_assist_markers);
+    }
+}
+
+/// Spawns a hidden marker for each human player.
+fn spawn_assist_markers(
+    mut commands: Commands,
+    settings: Res<AssistSettings>,
+    roles: Res<PlayerRoles>,
+) {
+    for player in [Player::P1, Player::P2] {
+        if roles.controller(player) != Controller::Human {
+            continue;
+        }
+        commands.spawn((
+            AssistMarker { player, fade: 0.0 },
+            Sprite {
+                color: Color::NONE,
+                custom_size: Some(settings.marker_size),
+                ..default()
+            },
+            // Drawn in front of the paddle it sits on
+            Transform::from_xyz(0.0, 0.0, 1.0),
+        ));
+    }
+}
+
+/// Moves each marker to where the soonest incoming ball will cross its
+/// player's paddle line, fading it in while there is one and out otherwise.
+#[allow(clippy::too_many_arguments)]
+fn update_assist_markers(
+    time: Res<Time>,
+    settings: Res<AssistSettings>,
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    ball_config: Res<BallConfig>,
+    side: Res<PlayerSide>,
+    theme: Res<Theme>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    mut marker_query: Query<(&mut AssistMarker, &mut Transform, &mut Sprite), Without<Ball>>,
+) {
+    let ball_limit = ball_height_limit(&board, ball_config.size);
+
+    for (mut marker, mut transform, mut sprite) in marker_query.iter_mut() {
+        let paddle_x = side.paddle_x(marker.player, &paddle_config, &board);
+        let crossing = ball_query
+            .iter()
+            .filter_map(|(ball_transform, ball_velocity)| {
+                predict_intersection(
+                    ball_transform.translation.truncate(),
+                    ball_velocity.linvel,
+                    paddle_x,
+                    ball_limit,
+                )
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        // The marker stays where it was while it fades out
+        if let Some((y, _)) = crossing {
+            transform.translation.x = paddle_x;
+            transform.translation.y = y;
+        }
+
+        let shown = settings.aim_assist && crossing.is_some();
+        let step = settings.fade_rate * time.delta_secs();
+        marker.fade = if shown {
+            (marker.fade + step).min(1.0)
+        } else {
+            (marker.fade - step).max(0.0)
+        };
+
+        sprite.color = theme
+            .colors()
+            .text
+            .with_alpha(settings.opacity * marker.fade);
+        sprite.custom_size = Some(settings.marker_size);
+    }
+}
+
+/// Removes the markers when play stops.
+fn despawn_assist_markers(mut commands: Commands, query: Query<Entity, With<AssistMarker>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,233 @@
+//! Attract Mode Module
+//!
+//! This module plays an AI-vs-AI demo behind the splash screen and the main
+//! menu, like the attract mode of an arcade cabinet, including:
+//! - Two AI paddles, one on each side
+//! - Serving the ball to each side in turn
+//! - Serving again shortly after a ball reaches a scoring wall
+//! - Clearing the board when the menu is left, or the splash screen for
+//!   a match
+//! - Starting over when the court size changes
+//!
+//! The demo is driven by the same paddle systems as a real match, which
+//! also run while [`attract_running`] holds. Nothing is scored, and the
+//! player's settings, statistics and recordings are left untouched.
+
+use crate::ball::{create_ball, Ball, BallAssets, BallConfig, DespawnBall};
+use crate::board::{BoardConfig, Wall};
+use crate::player::{
+    spawn_paddle, AiPaddle, Handicap, PaddleConfig, PaddleShapes, Player, PlayerSide,
+};
+use crate::theme::Theme;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Largest angle off horizontal a demo serve can take, in radians
+const MAX_SERVE_ANGLE: f32 = 0.35;
+
+/// Configuration for the attract demo
+#[derive(Debug, Resource)]
+pub struct AttractConfig {
+    /// Whether the demo plays behind the splash screen and the menu
+    pub enabled: bool,
+    /// Pause between a ball leaving play and the next serve, in seconds
+    pub serve_delay: f32,
+}
+
+impl Default for AttractConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,    // Something to watch while choosing settings
+            serve_delay: 1.0, // Long enough to see where the ball went
+        }
+    }
+}
+
+/// Resource tracking the attract demo
+#[derive(Debug, Resource)]
+pub struct AttractDemo {
+    /// Whether the demo's paddles are on the board
+    running: bool,
+    /// Whether the left paddle serves next
+    left_serves: bool,
+    /// Time until the next serve, while no ball is in play
+    serve_timer: Timer,
+}
+
+impl Default for AttractDemo {
+    fn default() -> Self {
+        Self {
+            running: false,
+            left_serves: true,
+            serve_timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+}
+
+/// Marker component for entities that belong to the attract demo
+#[derive(Component)]
+struct AttractEntity;
+
+/// Run condition for systems shared with a real match that also drive the
+/// attract demo
+pub fn attract_running(demo: Res<AttractDemo>) -> bool {
+    demo.running
+}
+
+/// Plugin that plays the attract demo behind the splash screen and the
+/// menu.
+pub struct AttractPlugin;
+
+impl Plugin for AttractPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AttractConfig>()
+            .init_resource::<AttractDemo>()
+            .add_systems(
+                Update,
+                (
+                    // Paddles stand at the old court's edges until replaced
+                    stop_attract_demo.run_if(resource_changed::<BoardConfig>),
+                    start_attract_demo,
+                    end_rally,
+                    serve_attract_ball,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Splash).or(in_state(GameState::Menu))),
+            )
+            // The real match starts from an empty board. The demo carries
+            // on from the splash screen into the menu.
+            .add_systems(OnExit(GameState::Menu), stop_attract_demo)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                stop_attract_demo,
+            );
+    }
+}
+
+/// Puts an AI paddle on each side once the splash screen or menu is up.
+#[allow(clippy::too_many_arguments)]
+fn start_attract_demo(
+    mut commands: Commands,
+    config: Res<AttractConfig>,
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    side: Res<PlayerSide>,
+    theme: Res<Theme>,
+    mut demo: ResMut<AttractDemo>,
+    mut shapes: ResMut<PaddleShapes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if demo.running || !config.enabled {
+        return;
+    }
+
+    let material_handle = materials.add(ColorMaterial::from(theme.colors().paddle));
+    for player in [Player::P1, Player::P2] {
+        let entity = spawn_paddle(
+            &mut commands,
+            &mut shapes,
+            &mut meshes,
+            &board,
+            &paddle_config,
+            material_handle.clone(),
+            player,
+            *side,
+            Handicap::default(),
+        );
+        commands
+            .entity(entity)
+            .insert((AiPaddle::default(), AttractEntity));
+    }
+
+    *demo = AttractDemo {
+        running: true,
+        left_serves: true,
+        serve_timer: Timer::from_seconds(config.serve_delay, TimerMode::Once),
+    };
+}
+
+/// Takes a ball out of play when it reaches a scoring wall, and starts the
+/// wait for the next serve.
+fn end_rally(
+    config: Res<AttractConfig>,
+    mut demo: ResMut<AttractDemo>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut despawns: EventWriter<DespawnBall>,
+    ball_query: Query<Entity, With<Ball>>,
+    wall_query: Query<(Entity, &Wall)>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = collision_event else {
+            continue;
+        };
+        let ball = ball_query.iter().find(|e| e == e1 || e == e2);
+        let scoring_wall = wall_query
+            .iter()
+            .any(|(e, wall)| (e == *e1 || e == *e2) && matches!(wall, Wall::Left | Wall::Right));
+
+        if let (Some(ball), true) = (ball, scoring_wall) {
+            despawns.send(DespawnBall::One(ball));
+            demo.serve_timer = Timer::from_seconds(config.serve_delay, TimerMode::Once);
+        }
+    }
+}
+
+/// Serves a new ball, from each side in turn, once the board is clear and
+/// the serve delay has passed.
+fn serve_attract_ball(
+    mut commands: Commands,
+    time: Res<Time>,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    mut demo: ResMut<AttractDemo>,
+    ball_query: Query<(), With<Ball>>,
+) {
+    if !demo.running || !ball_query.is_empty() {
+        return;
+    }
+    if !demo.serve_timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    let ball = create_ball(
+        &mut commands,
+        &ball_assets,
+        &ball_config,
+        demo.left_serves,
+        Vec2::ZERO,
+    );
+    let angle = (rand::random::<f32>() * 2.0 - 1.0) * MAX_SERVE_ANGLE;
+    let velocity = Vec2::from_angle(angle).rotate(Vec2::new(
+        ball_config.min_velocity * if demo.left_serves { 1.0 } else { -1.0 },
+        0.0,
+    ));
+    commands
+        .entity(ball)
+        .insert((Velocity::linear(velocity), AttractEntity));
+    demo.left_serves = !demo.left_serves;
+}
+
+/// Clears the demo's paddles and ball off the board.
+///
+/// The ball is removed through [`DespawnBall`], like every other ball.
+fn stop_attract_demo(
+    mut commands: Commands,
+    mut demo: ResMut<AttractDemo>,
+    mut despawn: EventWriter<DespawnBall>,
+    query: Query<Entity, (With<AttractEntity>, Without<Ball>)>,
+    ball_query: Query<Entity, (With<AttractEntity>, With<Ball>)>,
+) {
+    for entity in query.iter() {
+        // Along with any stun flash over a paddle
+        commands.entity(entity).despawn_recursive();
+    }
+    for ball in ball_query.iter() {
+        despawn.send(DespawnBall::One(ball));
+    }
+    demo.running = false;
+}
@@ -1,9 +1,15 @@
+use crate::mode::GameMode;
+use crate::player::PlayerRoles;
+use crate::score::GameWon;
 use crate::GameState;
-use bevy::app::{App, Plugin, Update};
+use bevy::app::{App, Plugin, Startup, Update};
 use bevy::asset::{AssetServer, Assets, Handle};
 use bevy::input::ButtonInput;
-use bevy::prelude::{KeyCode, OnEnter, OnExit, ParamSet, Res, ResMut, Resource};
-use bevy_kira_audio::{Audio, AudioControl, AudioInstance, AudioPlugin, AudioTween};
+use bevy::prelude::{
+    resource_changed, Commands, EventReader, IntoSystemConfigs, KeyCode, OnEnter, OnExit, ParamSet,
+    Res, ResMut, Resource,
+};
+use bevy_kira_audio::{Audio, AudioControl, AudioInstance, AudioPlugin, AudioSource, AudioTween};
 
 /// The MusicPlugin manages all background music functionality for the game.
 ///
@@ -11,41 +17,125 @@ use bevy_kira_audio::{Audio, AudioControl, AudioInstance, AudioPlugin, AudioTwee
 /// - Playing background music during gameplay
 /// - Pausing/resuming music based on game state
 /// - Toggling music on/off with the 'M' key
+/// - Toggling sound effects on/off with the 'N' key
+/// - Playing a victory, defeat or match over jingle when a match ends
 /// - Managing the music state across game state transitions
 pub struct MusicPlugin;
 
-/// Tracks the current state of the background music system.
+/// The player's audio preferences.
+///
+/// Music and sound effects are muted independently. Any system that plays a
+/// sound should check the matching flag before calling `audio.play`.
 ///
-/// This resource maintains information about:
-/// - Whether music is currently enabled
-/// - The handle to the current audio instance (if one exists)
+/// Like the music itself, these settings persist across game state changes.
+#[derive(Debug, Resource)]
+pub struct AudioSettings {
+    /// Whether background music should be playing
+    pub music_enabled: bool,
+    /// Whether sound effects should be played
+    pub sfx_enabled: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            // Music starts off until the player asks for it
+            music_enabled: false,
+            sfx_enabled: true,
+        }
+    }
+}
+
+/// Tracks the current state of the background music system.
 ///
-/// The state persists across game state changes to maintain user preferences
-/// for music playback.
+/// This resource holds the handle to the current audio instance (if one
+/// exists), which persists across game state changes so playback can be
+/// paused and resumed.
 #[derive(Resource, Default)]
 struct MusicState {
-    /// Indicates if music should be playing (true) or muted (false)
-    playing: bool,
     /// Optional handle to the current audio instance
     /// None if no music has been started or if music was explicitly stopped
     handle: Option<Handle<AudioInstance>>,
 }
 
+/// The jingles played when a match ends, loaded at startup so they are
+/// ready the moment the endgame screen appears.
+#[derive(Resource)]
+struct EndgameSounds {
+    /// Played when the human beats the AI
+    victory: Handle<AudioSource>,
+    /// Played when the AI beats the human
+    defeat: Handle<AudioSource>,
+    /// Played when there is no single human to cheer or console
+    match_over: Handle<AudioSource>,
+}
+
 impl Plugin for MusicPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(AudioPlugin)
+            .init_resource::<AudioSettings>()
             .init_resource::<MusicState>()
-            // System to handle manual music toggling via 'M' key
-            .add_systems(Update, handle_music_toggle)
+            .add_systems(Startup, load_endgame_sounds)
+            // Systems to handle manual music and sound effect toggling
+            .add_systems(
+                Update,
+                (
+                    handle_music_toggle,
+                    apply_music_setting.run_if(resource_changed::<AudioSettings>),
+                )
+                    .chain(),
+            )
+            .add_systems(Update, handle_sfx_toggle)
             // We want to pause it for the pause menu and game over screen
             .add_systems(OnEnter(GameState::Paused), pause_background_music)
-            .add_systems(OnEnter(GameState::GameOver), pause_background_music)
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                (pause_background_music, play_endgame_sound),
+            )
             // And resume it when the player resumes playing
             .add_systems(OnExit(GameState::Paused), resume_background_music)
             .add_systems(OnExit(GameState::GameOver), resume_background_music);
     }
 }
 
+/// Loads the endgame jingles.
+fn load_endgame_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(EndgameSounds {
+        victory: asset_server.load("victory.wav"),
+        defeat: asset_server.load("defeat.wav"),
+        match_over: asset_server.load("match_over.wav"),
+    });
+}
+
+/// Plays the jingle for the finished match, if sound effects are enabled.
+///
+/// With a single human the jingle follows their result: victory when they
+/// won and defeat when they lost, which includes every survival run. AI
+/// versus AI and two player matches get the neutral match over jingle.
+fn play_endgame_sound(
+    audio: Res<Audio>,
+    settings: Res<AudioSettings>,
+    sounds: Res<EndgameSounds>,
+    mode: Res<GameMode>,
+    roles: Res<PlayerRoles>,
+    mut wins: EventReader<GameWon>,
+) {
+    // Read regardless, so a muted match's result isn't left for the next one
+    let winner = wins.read().last().map(|won| won.winner);
+    if !settings.sfx_enabled {
+        return;
+    }
+
+    let sound = match (roles.sole_human(), winner) {
+        (Some(human), Some(winner)) if human == winner && *mode != GameMode::Survival => {
+            &sounds.victory
+        }
+        (Some(_), _) => &sounds.defeat,
+        (None, _) => &sounds.match_over,
+    };
+    audio.play(sound.clone());
+}
+
 /// Temporarily pauses the background music without changing the enabled state.
 ///
 /// Used when:
@@ -65,13 +155,14 @@ fn pause_background_music(
 /// Resumes background music playback if it was previously enabled.
 ///
 /// This system:
-/// 1. Checks if music should be playing based on the stored state
+/// 1. Checks if music should be playing based on the audio settings
 /// 2. If enabled, resumes playback of the existing audio instance
 fn resume_background_music(
+    settings: Res<AudioSettings>,
     music_state: ResMut<MusicState>,
     mut audio_instances: ResMut<Assets<AudioInstance>>,
 ) {
-    if music_state.playing {
+    if settings.music_enabled {
         if let Some(handle) = &music_state.handle {
             if let Some(instance) = audio_instances.get_mut(handle) {
                 instance.resume(AudioTween::default());
@@ -80,44 +171,51 @@ fn resume_background_music(
     }
 }
 
-/// Manages toggling the background music on/off via the 'M' key.
+/// Toggles the background music on/off via the 'M' key.
+fn handle_music_toggle(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<AudioSettings>) {
+    if keys.just_pressed(KeyCode::KeyM) {
+        settings.music_enabled = !settings.music_enabled;
+    }
+}
+
+/// Starts or stops the background music to match the audio settings.
 ///
 /// This system:
-/// 1. Detects 'M' key presses
-/// 2. Toggles the music state
-/// 3. Either starts new music playback or stops the current playback
-/// 4. Updates the MusicState resource accordingly
+/// 1. Compares `music_enabled` with whether music is currently playing
+/// 2. Either starts new music playback or stops the current playback
+/// 3. Updates the MusicState resource accordingly
+///
+/// Runs whenever the settings change, however they were changed, and once
+/// at startup so music saved as on starts straight away.
 ///
 /// Uses ParamSet to safely handle multiple mutable resources:
 /// - p0: MusicState for tracking playback state
 /// - p1: AudioInstances for controlling actual playback
-fn handle_music_toggle(
+fn apply_music_setting(
     audio: Res<Audio>,
     asset_server: Res<AssetServer>,
-    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<AudioSettings>,
     mut param_set: ParamSet<(ResMut<MusicState>, ResMut<Assets<AudioInstance>>)>,
 ) {
-    if keys.just_pressed(KeyCode::KeyM) {
-        // Toggle the playing state in a separate scope to release the borrow
-        let playing = {
-            let mut music_state = param_set.p0();
-            music_state.playing = !music_state.playing;
-            music_state.playing
-        };
-
-        if playing {
-            // Start new background music
-            let handle = audio.play(asset_server.load("pong.flac")).looped().handle();
-            param_set.p0().handle = Some(handle);
-        } else {
-            // Stop current background music
-            let handle = param_set.p0().handle.clone();
-            if let Some(handle) = handle {
-                if let Some(instance) = param_set.p1().get_mut(&handle) {
-                    instance.stop(AudioTween::default());
-                }
-                param_set.p0().handle = None;
+    let playing = param_set.p0().handle.is_some();
+    if settings.music_enabled && !playing {
+        // Start new background music
+        let handle = audio.play(asset_server.load("pong.flac")).looped().handle();
+        param_set.p0().handle = Some(handle);
+    } else if !settings.music_enabled && playing {
+        // Stop current background music
+        let handle = param_set.p0().handle.take();
+        if let Some(handle) = handle {
+            if let Some(instance) = param_set.p1().get_mut(&handle) {
+                instance.stop(AudioTween::default());
             }
         }
     }
 }
+
+/// Toggles sound effects on/off via the 'N' key.
+fn handle_sfx_toggle(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<AudioSettings>) {
+    if keys.just_pressed(KeyCode::KeyN) {
+        settings.sfx_enabled = !settings.sfx_enabled;
+    }
+}
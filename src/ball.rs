@@ -6,27 +6,128 @@
 //! - Ball creation and initialization
 //! - Physics properties and collision response
 //! - Velocity management and speed constraints
+//! - The optional speed boost from bouncing off the top and bottom walls
+//! - Speed-based color tinting
 //! - Cleanup and state management
 //! - Collision detection and event handling
 //!
 //! The ball uses Rapier2D's rigid body physics system for realistic movement and collisions,
 //! with carefully tuned parameters to ensure engaging gameplay while maintaining physical plausibility.
+//!
+//! Balls are only ever despawned here. Other modules send a [`DespawnBall`]
+//! event instead, so several of them can remove the same ball in one frame
+//! without despawning it twice.
 
+use crate::attract::attract_running;
+use crate::board::{ball_collision_groups, Wall};
+use crate::player::Player;
+use crate::theme::{Theme, Themed};
 use crate::GameState;
 use bevy::app::{App, Plugin, Update};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use std::collections::HashSet;
+
+/// Physical properties and speed limits of the ball.
+///
+/// The defaults are carefully tuned to provide satisfying gameplay mechanics
+/// while maintaining physical plausibility. Size and mass apply to balls
+/// served after a change, and the speed limits apply immediately.
+#[derive(Debug, Resource)]
+pub struct BallConfig {
+    /// Ball diameter in world units
+    pub size: f32,
+    /// Ball mass for collision responses
+    pub mass: f32,
+    /// Bounce elasticity of the ball itself. Multiplied with the restitution
+    /// of whatever it hits, see [`BoardConfig`](crate::board::BoardConfig),
+    /// so it scales wall bounces and paddle returns alike.
+    pub restitution: f32,
+    /// Minimum ball speed in world units per second
+    pub min_velocity: f32,
+    /// Maximum ball speed in world units per second
+    pub max_velocity: f32,
+    /// Extra speed from each bounce off the top or bottom wall, as a
+    /// fraction of the ball's speed
+    pub wall_boost: f32,
+    /// Fastest the ball can be sped up to by wall bounces, in world units
+    /// per second, so only paddle play takes it to its top speed
+    pub wall_boost_limit: f32,
+}
+
+impl Default for BallConfig {
+    fn default() -> Self {
+        Self {
+            // Small enough for precise gameplay
+            size: 0.3,
+            // Tuned for realistic collision responses
+            mass: 0.0027,
+            // Leaves the walls and paddles to set their own bounce
+            restitution: 1.0,
+            // Ensures the game keeps moving
+            min_velocity: 7.0,
+            // Prevents the ball from becoming too fast
+            max_velocity: 20.0,
+            // Off, so the walls never change the ball's speed. Around 0.05
+            // makes long rallies quicken on their own.
+            wall_boost: 0.0,
+            // Well short of the maximum speed
+            wall_boost_limit: 12.0,
+        }
+    }
+}
+
+/// Configuration for the ball's visual feedback
+#[derive(Debug, Resource)]
+pub struct BallVisualConfig {
+    /// Whether the ball is tinted toward red as it speeds up.
+    /// Disable to keep the classic white ball.
+    pub speed_tint: bool,
+}
 
-/// Physical properties and gameplay constants for the ball
+impl Default for BallVisualConfig {
+    fn default() -> Self {
+        Self { speed_tint: true }
+    }
+}
+
+/// Render assets shared by every ball.
 ///
-/// These constants define both the visual and physical characteristics of the ball,
-/// carefully tuned to provide satisfying gameplay mechanics while maintaining
-/// physical plausibility.
-const BALL_SIZE: f32 = 0.3; // Ball diameter in world units (small enough for precise gameplay)
-const MIN_VELOCITY: f32 = 7.0; // Minimum ball speed (ensures game keeps moving)
-const MAX_VELOCITY: f32 = 20.0; // Maximum ball speed (prevents ball from becoming too fast)
-const RESTITUTION: f32 = 0.9; // Bounce elasticity (slightly inelastic for better control)
-const BALL_MASS: f32 = 0.0027; // Ball mass (tuned for realistic collision responses)
+/// Created once at startup so serving a ball never adds new meshes or
+/// materials. The mesh is rebuilt in place when [`BallConfig::size`]
+/// changes. All balls share the material, so recoloring it (for the theme
+/// or the speed tint) recolors every ball; a ball that needs its own color
+/// should be given an explicit clone of the material instead.
+#[derive(Debug, Resource)]
+pub struct BallAssets {
+    /// Circle mesh matching the ball's collider
+    pub mesh: Handle<Mesh>,
+    /// Shared ball material
+    pub material: Handle<ColorMaterial>,
+}
+
+/// Creates the shared ball mesh and material.
+fn setup_ball_assets(
+    mut commands: Commands,
+    config: Res<BallConfig>,
+    theme: Res<Theme>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(BallAssets {
+        mesh: meshes.add(Circle::new(config.size / 2.0)),
+        material: materials.add(ColorMaterial::from(theme.colors().ball)),
+    });
+}
+
+/// Rebuilds the shared ball mesh to match the configured size.
+fn resize_ball_mesh(
+    config: Res<BallConfig>,
+    assets: Res<BallAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    meshes.insert(&assets.mesh, Circle::new(config.size / 2.0).into());
+}
 
 /// Marker component for identifying ball entities in the game world.
 ///
@@ -36,7 +137,7 @@ const BALL_MASS: f32 = 0.0027; // Ball mass (tuned for realistic collision respo
 /// - Manage ball-specific behavior and cleanup
 ///
 /// # Example Usage
-/// ```rust
+/// ```rust,ignore
 /// // Query for ball entities
 /// fn ball_system(query: Query<&Transform, With<Ball>>) {
 ///     for transform in query.iter() {
@@ -47,10 +148,74 @@ const BALL_MASS: f32 = 0.0027; // Ball mass (tuned for realistic collision respo
 #[derive(Component)]
 pub struct Ball;
 
+/// Tracks which player last touched the ball.
+///
+/// The owner is updated whenever the ball collides with a paddle, and is
+/// `None` for a freshly served ball that nobody has returned yet. Gameplay
+/// features such as power-ups use this to decide who benefits from an event.
+#[derive(Component, Debug, Default)]
+pub struct BallOwner(pub Option<Player>);
+
+/// Temporarily raises the ball's maximum speed above [`BallConfig::max_velocity`].
+///
+/// Inserted on the ball by a charged paddle hit. While present,
+/// `maintain_ball_velocity` caps the ball at `max_velocity * factor`
+/// instead of the normal limit. The boost is removed when the timer expires,
+/// after which the ball is clamped back to the normal maximum.
+#[derive(Component, Debug)]
+pub struct SpeedBoost {
+    /// Multiplier applied to the normal maximum speed
+    pub factor: f32,
+    /// Remaining time before the normal cap applies again
+    pub timer: Timer,
+}
+
+impl SpeedBoost {
+    /// Creates a boost raising the speed cap by `factor` for `duration` seconds
+    pub fn new(factor: f32, duration: f32) -> Self {
+        Self {
+            factor,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
+/// Permanently replaces the ball's maximum speed.
+///
+/// Used by modes that deliberately push the ball past [`BallConfig::max_velocity`],
+/// such as survival mode's accelerating ball. A [`SpeedBoost`] still
+/// multiplies this cap when both are present.
+#[derive(Component, Debug)]
+pub struct SpeedCap(pub f32);
+
+/// Slows the ball down for a moment, such as for a dramatic save.
+///
+/// Whoever inserts it also scales the ball's velocity down by `factor`.
+/// While present, `maintain_ball_velocity` scales the ball's speed limits
+/// by the same factor, so the clamp doesn't undo the slowdown. When the
+/// timer expires the velocity is scaled back up and the component removed.
+#[derive(Component, Debug)]
+pub struct SlowMotion {
+    /// Fraction of its speed the ball keeps
+    pub factor: f32,
+    /// Remaining time before normal speed returns
+    pub timer: Timer,
+}
+
+impl SlowMotion {
+    /// Creates a slowdown to `factor` of normal speed for `duration` seconds
+    pub fn new(factor: f32, duration: f32) -> Self {
+        Self {
+            factor,
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+        }
+    }
+}
+
 /// Creates a new ball entity with complete physics and rendering setup.
 ///
 /// This function creates a ball entity configured with:
-/// - Visual representation (white circle mesh)
+/// - Visual representation (the shared [`BallAssets`] circle)
 /// - Physics body and collider
 /// - Initial velocity based on serving direction
 /// - Collision properties and response settings
@@ -58,9 +223,12 @@ pub struct Ball;
 ///
 /// # Arguments
 /// * `commands` - Command buffer for entity creation and component insertion
-/// * `meshes` - Asset storage for managing the ball's visual mesh
-/// * `materials` - Asset storage for managing the ball's material/color
+/// * `assets` - Shared mesh and material for the ball
+/// * `config` - Size, mass and serve speed of the ball
 /// * `served_by_p1` - Boolean flag indicating serve direction (true = right, false = left)
+/// * `position` - Where the ball is put into play
+///
+/// Returns the new ball's entity.
 ///
 /// # Physics Configuration
 /// The ball is configured with:
@@ -71,42 +239,43 @@ pub struct Ball;
 /// - Custom mass and restitution for desired bounce behavior
 ///
 /// # Example
-/// ```rust
-/// create_ball(&mut commands, &mut meshes, &mut materials, true); // Serve to the right
+/// ```rust,ignore
+/// create_ball(&mut commands, &ball_assets, &ball_config, true, Vec2::ZERO); // Serve to the right
 /// ```
 pub fn create_ball(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<ColorMaterial>>,
+    assets: &BallAssets,
+    config: &BallConfig,
     served_by_p1: bool,
-) {
+    position: Vec2,
+) -> Entity {
     // Calculate initial direction and velocity
     let direction = if served_by_p1 { 1 } else { -1 };
-    let initial_velocity = Vec2::new(MIN_VELOCITY * direction as f32, 0.0);
+    let initial_velocity = Vec2::new(config.min_velocity * direction as f32, 0.0);
 
     commands
         .spawn(Ball)
+        // Nobody owns a freshly served ball
+        .insert(BallOwner::default())
         // Visual Components
-        // Creates a circular mesh for rendering with appropriate size
-        .insert(Mesh2d(meshes.add(Circle::new(BALL_SIZE / 2.0))))
-        // Applies white color material to the ball
-        .insert(MeshMaterial2d(
-            materials.add(ColorMaterial::from(Color::WHITE)),
-        ))
-        // Positions ball at center of screen initially
-        .insert(Transform::from_xyz(0.0, 0.0, 0.0))
+        // Reuses the shared circle mesh and material
+        .insert(Mesh2d(assets.mesh.clone()))
+        .insert(MeshMaterial2d(assets.material.clone()))
+        .insert(Themed::Ball)
+        // Positions ball where it is put into play
+        .insert(Transform::from_translation(position.extend(0.0)))
         // Physics Body Configuration
         // Sets up dynamic rigid body for physics simulation
         .insert(RigidBody::Dynamic)
         // Creates circular collider matching visual size
-        .insert(Collider::ball(BALL_SIZE / 2.0))
+        .insert(Collider::ball(config.size / 2.0))
         // Sets initial movement velocity
         .insert(Velocity::linear(initial_velocity))
         // Collision Properties
         // Configures bounce behavior
         .insert(Restitution {
-            coefficient: RESTITUTION,
-            combine_rule: CoefficientCombineRule::Max,
+            coefficient: config.restitution,
+            combine_rule: CoefficientCombineRule::Multiply,
         })
         // Removes friction for consistent movement
         .insert(Friction {
@@ -128,13 +297,54 @@ pub fn create_ball(
         .insert(Sleeping::disabled())
         // Enables all collision types for comprehensive detection
         .insert(ActiveCollisionTypes::all())
+        // Meets the board, the paddles, power-ups and other balls
+        .insert(ball_collision_groups())
         // Enables collision event generation
         .insert(ActiveEvents::COLLISION_EVENTS)
         // Sets mass for collision response calculations
-        .insert(AdditionalMassProperties::Mass(BALL_MASS));
+        .insert(AdditionalMassProperties::Mass(config.mass))
+        .id()
 }
 
-/// System that removes the ball entity when exiting the Playing state.
+/// Event asking for balls to be removed from play
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DespawnBall {
+    /// Remove this ball
+    One(Entity),
+    /// Remove every ball
+    All,
+}
+
+/// Removes the balls asked for by [`DespawnBall`] events.
+///
+/// Runs every frame. Systems that need a scored ball gone before they run,
+/// like the next serve, are ordered after it. Each ball is despawned at
+/// most once however many events name it, and balls that are already gone,
+/// such as those cleaned up on leaving gameplay, are skipped.
+pub fn despawn_balls(
+    mut commands: Commands,
+    mut events: EventReader<DespawnBall>,
+    ball_query: Query<Entity, With<Ball>>,
+) {
+    let mut despawned = HashSet::new();
+    for event in events.read() {
+        let targets: Vec<Entity> = match *event {
+            DespawnBall::One(entity) => ball_query.get(entity).into_iter().collect(),
+            DespawnBall::All => ball_query.iter().collect(),
+        };
+        for entity in targets {
+            if despawned.insert(entity) {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+/// System that removes the ball entity when leaving gameplay.
+///
+/// Pausing keeps the balls, halted with the physics, so the pause menu's
+/// grace period can carry them on. They are only removed when the game
+/// moves on from the Playing or Paused states to any other.
 ///
 /// This cleanup system ensures that:
 /// - Ball is properly despawned when leaving gameplay
@@ -151,6 +361,14 @@ fn cleanup_ball(mut commands: Commands, ball_query: Query<Entity, With<Ball>>) {
     }
 }
 
+/// Query data for a ball's velocity and any cap overrides
+type BallSpeedLimits<'a> = (
+    &'a mut Velocity,
+    Option<&'a SpeedCap>,
+    Option<&'a SpeedBoost>,
+    Option<&'a SlowMotion>,
+);
+
 /// System that maintains the ball's velocity within gameplay constraints.
 ///
 /// This system ensures that:
@@ -169,18 +387,28 @@ fn cleanup_ball(mut commands: Commands, ball_query: Query<Entity, With<Ball>>) {
 /// - Uses vector normalization to preserve direction
 /// - Handles potential division by zero
 /// - Maintains speed constraints for consistent gameplay
-fn maintain_ball_velocity(mut query: Query<&mut Velocity, With<Ball>>) {
-    for mut velocity in query.iter_mut() {
+/// - Respects [`SpeedCap`] and temporary [`SpeedBoost`] cap overrides
+/// - Scales both limits down while the ball is in [`SlowMotion`]
+pub(crate) fn maintain_ball_velocity(
+    config: Res<BallConfig>,
+    mut query: Query<BallSpeedLimits, With<Ball>>,
+) {
+    for (mut velocity, cap, boost, slow) in query.iter_mut() {
         let current_velocity = velocity.linvel;
         let current_speed = current_velocity.length();
+        let slowdown = slow.map_or(1.0, |slow| slow.factor);
+        let min_velocity = config.min_velocity * slowdown;
+        let max_velocity = cap.map_or(config.max_velocity, |cap| cap.0)
+            * boost.map_or(1.0, |boost| boost.factor)
+            * slowdown;
 
         // Only adjust non-zero velocities to prevent normalization issues
         if current_speed != 0.0 {
             // Determine new speed based on constraints
-            let new_speed = if current_speed.abs() < MIN_VELOCITY {
-                MIN_VELOCITY // Enforce minimum speed
-            } else if current_speed.abs() > MAX_VELOCITY {
-                MAX_VELOCITY // Cap maximum speed
+            let new_speed = if current_speed.abs() < min_velocity {
+                min_velocity // Enforce minimum speed
+            } else if current_speed.abs() > max_velocity {
+                max_velocity // Cap maximum speed
             } else {
                 current_speed // Maintain current speed if within bounds
             };
@@ -191,11 +419,122 @@ fn maintain_ball_velocity(mut query: Query<&mut Velocity, With<Ball>>) {
     }
 }
 
+/// Speeds up each ball that has just bounced off the top or bottom wall by
+/// [`BallConfig::wall_boost`], without taking it past
+/// [`BallConfig::wall_boost_limit`].
+///
+/// A ball already at or above the limit keeps its speed.
+fn boost_wall_bounces(
+    config: Res<BallConfig>,
+    mut collision_events: EventReader<CollisionEvent>,
+    wall_query: Query<&Wall>,
+    mut ball_query: Query<&mut Velocity, With<Ball>>,
+) {
+    if config.wall_boost <= 0.0 {
+        collision_events.clear();
+        return;
+    }
+
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = collision_event else {
+            continue;
+        };
+        let (ball, other) = if ball_query.contains(*e1) {
+            (*e1, *e2)
+        } else {
+            (*e2, *e1)
+        };
+        if !matches!(wall_query.get(other), Ok(Wall::Top | Wall::Bottom)) {
+            continue;
+        }
+        let Ok(mut velocity) = ball_query.get_mut(ball) else {
+            continue;
+        };
+
+        let speed = velocity.linvel.length();
+        if speed == 0.0 || speed >= config.wall_boost_limit {
+            continue;
+        }
+        let boosted = (speed * (1.0 + config.wall_boost)).min(config.wall_boost_limit);
+        velocity.linvel *= boosted / speed;
+    }
+}
+
+/// Returns how fast a ball is going as a factor from 0.0 at the minimum
+/// speed to 1.0 at the maximum speed or above.
+fn speed_factor(speed: f32, config: &BallConfig) -> f32 {
+    ((speed - config.min_velocity) / (config.max_velocity - config.min_velocity)).clamp(0.0, 1.0)
+}
+
+/// System that tints the ball from its theme color toward red as it speeds up.
+///
+/// Balls share the [`BallAssets`] material, so with several balls in play
+/// the tint follows the fastest one. With the tint disabled the ball is kept
+/// at its theme color.
+fn tint_ball_by_speed(
+    config: Res<BallVisualConfig>,
+    ball_config: Res<BallConfig>,
+    theme: Res<Theme>,
+    assets: Res<BallAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<&Velocity, With<Ball>>,
+) {
+    let factor = if config.speed_tint {
+        query
+            .iter()
+            .map(|velocity| speed_factor(velocity.linvel.length(), &ball_config))
+            .fold(0.0, f32::max)
+    } else {
+        0.0
+    };
+    let color = theme.colors().ball.mix(&Color::srgb(1.0, 0.0, 0.0), factor);
+
+    if let Some(material) = materials.get_mut(&assets.material) {
+        if material.color != color {
+            material.color = color;
+        }
+    }
+}
+
+/// System that counts down speed boosts and removes expired ones.
+fn expire_speed_boosts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SpeedBoost)>,
+) {
+    for (entity, mut boost) in query.iter_mut() {
+        if boost.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<SpeedBoost>();
+        }
+    }
+}
+
+/// System that counts down slowdowns, restoring the ball's speed when
+/// they end.
+fn expire_slow_motion(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut SlowMotion, &mut Velocity)>,
+) {
+    for (entity, mut slow, mut velocity) in query.iter_mut() {
+        if slow.timer.tick(time.delta()).finished() {
+            if slow.factor > 0.0 {
+                velocity.linvel /= slow.factor;
+            }
+            commands.entity(entity).remove::<SlowMotion>();
+        }
+    }
+}
+
 /// Plugin that manages all ball-related systems and behavior.
 ///
 /// This plugin integrates the ball systems into the game by:
+/// - Creating the shared ball assets
+/// - Despawning balls other modules are done with
 /// - Adding cleanup system for state transitions
-/// - Adding velocity maintenance system for gameplay
+/// - Adding velocity maintenance system, in lockstep with physics
+/// - Expiring temporary speed boosts and slowdowns
+/// - Tinting the ball by speed during gameplay
 /// - Organizing ball-related functionality
 ///
 /// The plugin ensures proper initialization and cleanup of ball
@@ -207,8 +546,45 @@ impl Plugin for BallPlugin {
     fn build(&self, app: &mut App) {
         app
             // Add cleanup system for state transitions
-            .add_systems(OnExit(GameState::Playing), cleanup_ball)
-            // Add velocity maintenance system during gameplay updates
-            .add_systems(Update, maintain_ball_velocity);
+            .init_resource::<BallConfig>()
+            .init_resource::<BallVisualConfig>()
+            .add_event::<DespawnBall>()
+            .add_systems(Startup, setup_ball_assets)
+            .add_systems(Update, despawn_balls)
+            // Keep the shared mesh in step with the configured size
+            .add_systems(
+                Update,
+                resize_ball_mesh.run_if(resource_changed::<BallConfig>),
+            )
+            // The new state is already in place when the exit schedules
+            // run, so moving between Playing and Paused keeps the balls
+            .add_systems(
+                OnExit(GameState::Playing),
+                cleanup_ball.run_if(not(in_state(GameState::Paused))),
+            )
+            .add_systems(
+                OnExit(GameState::Paused),
+                cleanup_ball.run_if(not(in_state(GameState::Playing))),
+            )
+            // Clamp the ball's speed after every physics step, so it can't
+            // run past its limits between corrections at low frame rates.
+            // Boosts and slowdowns only wear off while the ball is moving.
+            .add_systems(
+                FixedUpdate,
+                (
+                    boost_wall_bounces,
+                    expire_speed_boosts,
+                    expire_slow_motion,
+                    maintain_ball_velocity,
+                )
+                    .chain()
+                    .after(PhysicsSet::Writeback)
+                    .run_if(in_state(GameState::Playing).or(attract_running)),
+            )
+            // Update the ball's color from its settled speed
+            .add_systems(
+                Update,
+                tint_ball_by_speed.run_if(in_state(GameState::Playing)),
+            );
     }
 }
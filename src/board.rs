@@ -1,18 +1,30 @@
 //! Game Board Module
 //!
 //! This module handles the game board setup and configuration, including:
-//! - Board dimensions and layout
+//! - Board dimensions and layout, with long and tight court presets
 //! - Wall creation and physics properties
-//! - Visual elements like the center line
-//! - Background color
+//! - Rounded corners, so the ball can't wedge between two walls
+//! - Rebuilding the board when its size changes
+//! - Visual elements like the center line, with its gentle pulse, and the
+//!   optional faint background grid
+//! - The optional net, which makes the middle of the center line solid
 //!
 //! The game board uses Rapier2D physics for wall collisions and boundaries.
+//! The physics is halted while the game is paused or over, so nothing moves
+//! behind the pause menu or the end screen.
+//! It also defines the collision groups every collider joins, so the board,
+//! the paddles and the power-up tokens only ever touch the ball.
 
+use crate::camera::GraphicsSettings;
+use crate::replay::InstantReplay;
+use crate::theme::{Theme, Themed};
+use crate::GameState;
 use bevy::app::Plugin;
-use bevy::color::Color;
 use bevy::prelude::*;
 use bevy::sprite::Sprite;
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
 
 /// Component that identifies which wall this entity represents.
 /// Used for collision detection and scoring logic.
@@ -24,176 +36,686 @@ pub enum Wall {
     Right,  // Player 1's scoring wall
 }
 
-/// Physical dimensions of the game board and its elements.
-/// These constants define the overall size and scale of the game.
-const WALL_THICKNESS: f32 = 0.1; // Wall thickness in world units
-const BOARD_WIDTH: f32 = 16.0; // Total width of game board
-const BOARD_HEIGHT: f32 = 10.0; // Total height of game board
+/// Size and physics of the game board.
+///
+/// Every position on the board is worked out from this rather than fixed:
+/// the walls and center line, where the paddles stand, how far the ball
+/// and paddles can move up and down, and the camera's view. Whenever it
+/// changes the board is rebuilt, so change it between matches; paddles
+/// already on the board keep their places.
+///
+/// Every surface the ball meets combines its restitution with the ball's
+/// own using `CoefficientCombineRule::Multiply`, so a wall bounce has the
+/// wall's restitution times the ball's, and a return the paddle's times the
+/// ball's. With the ball's restitution at 1.0, walls and paddles are tuned
+/// independently. Rapier uses the highest priority rule of the two
+/// colliders (`Max` over `Multiply`), so anything added to the board must
+/// use `Multiply` too, or its restitution would win outright on contact.
+/// Values above 1.0 add energy on every bounce, 1.0 is lossless and values
+/// below 1.0 dampen the ball.
+///
+/// The walls default to 1.0, so a bounce off them never changes the ball's
+/// speed. Walls that speed the ball up on purpose are better set with the
+/// ball's bounded `wall_boost` than with a restitution above 1.0: the speed
+/// clamp in `maintain_ball_velocity` caps whatever bouncy walls add at the
+/// ball's maximum speed, so after a few bounces every rally would run at
+/// top speed whatever the paddles did.
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct BoardConfig {
+    /// Total width of the board, from the outside of one scoring wall to
+    /// the other
+    pub width: f32,
+    /// Total height of the board, from the outside of the top wall to the
+    /// bottom one
+    pub height: f32,
+    /// Thickness of every wall
+    pub wall_thickness: f32,
+    /// Wall bounciness (>1 means adding energy), multiplied with the ball's
+    pub wall_restitution: f32,
+    /// Radius of the rounded inside of each corner, see [`Corner`]
+    pub corner_radius: f32,
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        Self {
+            width: 16.0,           // The standard court
+            height: 10.0,          // Wide enough for a 16:10 screen
+            wall_thickness: 0.1,   // Thin lines at the board's edge
+            wall_restitution: 1.0, // Lossless, see `BallConfig::wall_boost`
+            corner_radius: 0.2,    // Just over the ball's radius
+        }
+    }
+}
+
+impl BoardConfig {
+    /// Distance from the center to the middle of each scoring wall
+    pub fn half_width(&self) -> f32 {
+        self.width / 2.0
+    }
+
+    /// Distance from the center to the middle of the top and bottom walls
+    pub fn half_height(&self) -> f32 {
+        self.height / 2.0
+    }
+
+    /// Distance from the center to the inside faces of the top and bottom
+    /// walls
+    pub fn inner_half_height(&self) -> f32 {
+        ((self.height - self.wall_thickness) / 2.0).max(0.0)
+    }
+
+    /// Distance from the center to the inside faces of the scoring walls
+    pub fn inner_half_width(&self) -> f32 {
+        ((self.width - self.wall_thickness) / 2.0).max(0.0)
+    }
+}
+
+/// Court sizes that can be picked in the menu.
+///
+/// A preset only sets the board's width and height, leaving the walls as
+/// they are configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CourtPreset {
+    /// The standard 16 by 10 court
+    #[default]
+    Standard,
+    /// A 20 by 10 court, with more time to read each shot
+    Long,
+    /// A 12 by 8 court, for quick reactions
+    Tight,
+}
+
+impl CourtPreset {
+    /// All presets in the order they are cycled through
+    const ALL: [CourtPreset; 3] = [CourtPreset::Standard, CourtPreset::Long, CourtPreset::Tight];
+
+    /// Width and height of the court
+    pub fn size(&self) -> Vec2 {
+        match self {
+            CourtPreset::Standard => Vec2::new(16.0, 10.0),
+            CourtPreset::Long => Vec2::new(20.0, 10.0),
+            CourtPreset::Tight => Vec2::new(12.0, 8.0),
+        }
+    }
+
+    /// Name shown in the menu
+    pub fn name(&self) -> &'static str {
+        match self {
+            CourtPreset::Standard => "Standard",
+            CourtPreset::Long => "Long",
+            CourtPreset::Tight => "Tight",
+        }
+    }
+
+    /// The preset matching the board's size, or `None` for a custom size
+    pub fn of(board: &BoardConfig) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|preset| preset.size() == Vec2::new(board.width, board.height))
+    }
+
+    /// Returns the next preset, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Returns the previous preset, wrapping around
+    pub fn previous(&self) -> Self {
+        let index = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Resizes the board to this preset
+    pub fn apply(&self, board: &mut BoardConfig) {
+        let size = self.size();
+        board.width = size.x;
+        board.height = size.y;
+    }
+}
+
+/// World scale of the physics engine.
+///
+/// Every size and speed in the game (board, paddles, ball, velocities) is
+/// expressed in world units. Rapier is told how many of its "pixels" (our
+/// world units) make up one physics meter, which sets the length scale of
+/// its internal tolerances such as contact distances and sleep thresholds.
+///
+/// The scale is fixed when the app is built, so it follows the standard
+/// court's height rather than the configured board. Court presets are close
+/// enough in size to share it. If the game is rescaled as a whole, scale the
+/// world-unit sizes in `ball.rs` and `player.rs` with the board, and this
+/// by the same factor, or colliders behave as if they were a different size
+/// than they are drawn.
+pub const PIXELS_PER_METER: f32 = 100.0; // World units per physics meter
+
+/// Physics timing.
+///
+/// Physics runs on a fixed timestep. Rapier steps in `FixedUpdate`, and
+/// the gameplay systems that push the ball and paddles around run there too,
+/// so speed limits, boosts and paddle movement behave the same at any frame
+/// rate. The timestep is applied once when the board plugin is built.
+#[derive(Debug, Resource)]
+pub struct PhysicsConfig {
+    /// Length of one physics step in seconds
+    pub timestep: f32,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            // 60 steps per second, matching the most common display rate
+            timestep: 1.0 / 60.0,
+        }
+    }
+}
+
+/// Collision group of the balls
+pub const BALL_GROUP: Group = Group::GROUP_1;
+/// Collision group of the paddles
+pub const PADDLE_GROUP: Group = Group::GROUP_2;
+/// Collision group of the walls, the net and the obstacles
+pub const BOARD_GROUP: Group = Group::GROUP_3;
+/// Collision group of the power-up tokens
+pub const POWER_UP_GROUP: Group = Group::GROUP_4;
+
+/// Collision groups for a ball, which meets everything, other balls included
+pub fn ball_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(BALL_GROUP, Group::ALL)
+}
 
-/// Center line visual settings.
-/// These constants control the appearance of the dashed center line.
-const DASH_LENGTH: f32 = 0.8; // Length of each dash
-const DASH_WIDTH: f32 = 0.1; // Width of each dash
-const DASH_GAP: f32 = 0.4; // Gap between dashes
+/// Collision groups for a paddle, which only meets balls.
+///
+/// Paddles are kept on the board by their movement code rather than by
+/// touching the walls, so paddle-wall contacts would only be noise.
+pub fn paddle_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(PADDLE_GROUP, BALL_GROUP)
+}
 
-/// Physics settings for the walls.
-/// Walls are bouncy to create more interesting gameplay.
-const WALL_RESTITUTION: f32 = 2.0; // Wall bounciness (>1 means adding energy)
+/// Collision groups for walls, net dashes and obstacles, which only meet balls
+pub fn board_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(BOARD_GROUP, BALL_GROUP)
+}
 
-/// Creates the black background color resource.
-/// This sets the clear color for the game's rendering.
-pub fn black_background() -> ClearColor {
-    ClearColor(Color::srgb(0.0, 0.0, 0.0))
+/// Collision groups for a power-up token, which only meets balls
+pub fn power_up_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(POWER_UP_GROUP, BALL_GROUP)
 }
 
+/// Configuration for the optional net.
+///
+/// When enabled, the center line dashes near the middle of the board get
+/// thin colliders with the same physics as the walls, so shots through the
+/// middle bounce back and the ball has to be played around the net. Dashes
+/// outside the net's height stay purely visual, and the small gaps between
+/// dashes are just wide enough for a perfectly aimed ball to slip through.
+///
+/// Net dashes are not [`Wall`]s, so hitting the net never scores.
+#[derive(Debug, Resource)]
+pub struct NetConfig {
+    /// Whether the net has colliders
+    pub enabled: bool,
+    /// Height of the solid part of the net, centered on the board
+    pub height: f32,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            // The middle four dashes, leaving open lanes above and below
+            height: 4.5,
+        }
+    }
+}
+
+/// Appearance of the dashed center line.
+///
+/// As many whole dashes as fit are spread along the board's height and
+/// centered on it. A gap of 0.0 draws a solid line. The color comes from the
+/// [`Theme`]. The line is drawn along with the rest of the board, so changes
+/// to the dashes show the next time the board is rebuilt.
+///
+/// Unless turned off in [`GraphicsSettings`], the dashes fade a little in a
+/// wave that travels down the line. The pulse settings apply straight away.
+#[derive(Debug, Resource)]
+pub struct CenterLineConfig {
+    /// Length of each dash
+    pub dash_length: f32,
+    /// Width of each dash
+    pub dash_width: f32,
+    /// Gap between dashes
+    pub dash_gap: f32,
+    /// Fraction of the dashes' opacity the pulse takes away at its faintest
+    pub pulse_depth: f32,
+    /// How fast the pulse travels down the line, in world units per second
+    pub pulse_speed: f32,
+    /// Distance between the pulse's peaks along the line
+    pub pulse_wavelength: f32,
+}
+
+impl Default for CenterLineConfig {
+    fn default() -> Self {
+        Self {
+            dash_length: 0.8,      // Long enough to read as a line at a glance
+            dash_width: 0.1,       // Thinner than the walls
+            dash_gap: 0.4,         // Half a dash
+            pulse_depth: 0.35,     // Noticeable without pulling the eye
+            pulse_speed: 2.0,      // An unhurried drift
+            pulse_wavelength: 6.0, // About one peak on the board at a time
+        }
+    }
+}
+
+/// Marker component for center line dashes that are part of the net
+#[derive(Component)]
+pub struct Net;
+
+/// Component for a center line dash's place in the pulse, in radians
+#[derive(Component)]
+struct DashPulse(f32);
+
+/// Marker component for the lines of the background grid
+#[derive(Component)]
+struct GridLine;
+
+/// Distance between the background grid's lines
+const GRID_SPACING: f32 = 1.0;
+
+/// Width of the background grid's lines
+const GRID_LINE_WIDTH: f32 = 0.02;
+
+/// Component for the rounded inside of a corner of the board.
+///
+/// Where two walls meet, the ball could otherwise touch both at once and
+/// jitter between the two contacts, or squeeze through the seam. Each corner
+/// is filled with a quarter-circle arc joining the inside faces of its two
+/// walls, so the ball always meets one smooth surface there and is turned
+/// back toward the middle of the board.
+///
+/// Corners are not [`Wall`]s, so a ball turned back by one never scores.
+/// Keeping the radius just over the ball's leaves the arc too small to
+/// reach a ball that isn't already wedged in the corner, so one heading
+/// for the very end of a goal line still meets the scoring wall first.
+/// They are only physics; the walls' sprites already draw the corners.
+#[derive(Component)]
+pub struct Corner;
+
+/// Number of straight edges approximating each corner's arc. Odd, so a ball
+/// sent straight into the corner meets the middle of an edge, not a vertex.
+const CORNER_SEGMENTS: usize = 9;
+
+/// Marker component for the walls, corners, center line dashes and grid
+/// lines, which are replaced when the board is rebuilt
+#[derive(Component)]
+struct BoardPiece;
+
 /// Creates a common physics bundle for walls to ensure consistent behavior.
 ///
 /// # Arguments
-/// * `width` - Wall width in world units
-/// * `height` - Wall height in world units
+/// * `collider` - The wall's shape
+/// * `restitution` - Wall bounciness, see [`BoardConfig`]
 ///
 /// # Returns
 /// A tuple of components that define the wall's physics properties:
 /// - RigidBody: Fixed position
-/// - Collider: Rectangular shape
-/// - Restitution: Bouncy surface
+/// - Collider: The given shape
+/// - Restitution: The given bounciness
 /// - Friction: Frictionless surface
-/// - Collision types and events
+/// - Collision types, groups and events
 fn wall_physics_bundle(
-    width: f32,
-    height: f32,
+    collider: Collider,
+    restitution: f32,
 ) -> (
     RigidBody,
     Collider,
     Restitution,
     Friction,
     ActiveCollisionTypes,
+    CollisionGroups,
     ActiveEvents,
 ) {
     (
-        RigidBody::Fixed,                            // Walls don't move
-        Collider::cuboid(width / 2.0, height / 2.0), // Rectangular collision shape
+        RigidBody::Fixed, // Walls don't move
+        collider,
         Restitution {
-            coefficient: WALL_RESTITUTION,
-            combine_rule: CoefficientCombineRule::Max, // Use highest restitution in collisions
+            coefficient: restitution,
+            // Scaled by the ball's, see `BoardConfig`
+            combine_rule: CoefficientCombineRule::Multiply,
         },
         Friction {
             coefficient: 0.0,
             combine_rule: CoefficientCombineRule::Min, // No friction to maintain energy
         },
         ActiveCollisionTypes::all(),    // Detect all collision types
+        board_collision_groups(),       // Only meet the ball
         ActiveEvents::COLLISION_EVENTS, // Generate collision events
     )
 }
 
 /// Spawns the center line made up of dashed sprites.
-/// This is purely visual and has no collision components.
+/// The line is purely visual unless the net is enabled in [`NetConfig`],
+/// in which case the dashes within the net's height also get colliders.
 ///
 /// The center line is created by spawning multiple dash sprites
-/// evenly spaced along the vertical center of the board.
-fn spawn_center_line(mut commands: Commands) {
+/// evenly spaced along the vertical center of the board. Each dash's place
+/// in the pulse follows from its height, so the pulse runs down the line.
+fn spawn_center_line(
+    mut commands: Commands,
+    board: Res<BoardConfig>,
+    config: Res<CenterLineConfig>,
+    net: Res<NetConfig>,
+    theme: Res<Theme>,
+) {
+    let dash_length = config.dash_length.min(board.height);
+    let dash_gap = config.dash_gap.max(0.0);
+    if dash_length <= 0.0 {
+        return;
+    }
+
     // Calculate space for one complete dash cycle
-    let dash_cycle = DASH_LENGTH + DASH_GAP;
+    let dash_cycle = dash_length + dash_gap;
 
-    // Calculate number of complete cycles that fit
-    let num_cycles = (BOARD_HEIGHT / dash_cycle).floor();
+    // Calculate number of complete cycles that fit, the last dash needing
+    // no gap after it
+    let num_cycles = ((board.height + dash_gap) / dash_cycle).floor();
 
     // Center the pattern vertically
-    let total_pattern_height = num_cycles * dash_cycle - DASH_GAP;
+    let total_pattern_height = num_cycles * dash_cycle - dash_gap;
     let start_y = -(total_pattern_height / 2.0);
 
     // Spawn visual dashes
     for i in 0..num_cycles as i32 {
-        let y_position = start_y + (i as f32 * dash_cycle) + (DASH_LENGTH / 2.0);
+        let y_position = start_y + (i as f32 * dash_cycle) + (dash_length / 2.0);
 
         // Spawn a single dash sprite
-        commands.spawn((
+        let mut dash = commands.spawn((
             Sprite {
-                color: Color::WHITE,
-                custom_size: Some(Vec2::new(DASH_WIDTH, DASH_LENGTH)),
+                color: theme.colors().center_line,
+                custom_size: Some(Vec2::new(config.dash_width, dash_length)),
                 ..default()
             },
+            Themed::CenterLine,
+            BoardPiece,
+            DashPulse(y_position / config.pulse_wavelength.max(f32::EPSILON) * TAU),
             Transform::from_xyz(0.0, y_position, 0.0),
             GlobalTransform::default(),
             Visibility::default(),
         ));
+
+        // Dashes that lie entirely within the net's height become solid
+        if net.enabled && y_position.abs() + dash_length / 2.0 <= net.height / 2.0 {
+            dash.insert((
+                wall_physics_bundle(
+                    Collider::cuboid(config.dash_width / 2.0, dash_length / 2.0),
+                    board.wall_restitution,
+                ),
+                Net,
+            ));
+        }
     }
 }
 
 /// Spawns the four walls that make up the game board boundaries.
-/// Each wall is given bouncy physics properties to create more
-/// interesting ball trajectories.
+/// Each wall bounces the ball back with the board's restitution.
 ///
 /// The walls are positioned relative to the board dimensions:
 /// - Top/Bottom: Horizontal walls at +/- half board height
 /// - Left/Right: Vertical walls at +/- half board width
-fn spawn_walls(mut commands: Commands) {
-    let half_width = BOARD_WIDTH / 2.0;
-    let half_height = BOARD_HEIGHT / 2.0;
-
-    // Top wall
-    commands.spawn((
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(BOARD_WIDTH, WALL_THICKNESS)),
-            ..default()
-        },
-        Transform::from_xyz(0.0, half_height, 0.0),
-        wall_physics_bundle(BOARD_WIDTH, WALL_THICKNESS),
-        Wall::Top,
-    ));
-
-    // Bottom wall
-    commands.spawn((
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(BOARD_WIDTH, WALL_THICKNESS)),
-            ..default()
-        },
-        Transform::from_xyz(0.0, -half_height, 0.0),
-        wall_physics_bundle(BOARD_WIDTH, WALL_THICKNESS),
-        Wall::Bottom,
-    ));
-
-    // Left wall (scoring wall for P2)
-    commands.spawn((
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(WALL_THICKNESS, BOARD_HEIGHT)),
-            ..default()
-        },
-        Transform::from_xyz(-half_width, 0.0, 0.0),
-        wall_physics_bundle(WALL_THICKNESS, BOARD_HEIGHT),
-        Wall::Left,
-    ));
-
-    // Right wall (scoring wall for P1)
-    commands.spawn((
-        Sprite {
-            color: Color::WHITE,
-            custom_size: Some(Vec2::new(WALL_THICKNESS, BOARD_HEIGHT)),
-            ..default()
-        },
-        Transform::from_xyz(half_width, 0.0, 0.0),
-        wall_physics_bundle(WALL_THICKNESS, BOARD_HEIGHT),
-        Wall::Right,
-    ));
+fn spawn_walls(mut commands: Commands, board: Res<BoardConfig>, theme: Res<Theme>) {
+    let color = theme.colors().wall;
+    let horizontal = Vec2::new(board.width, board.wall_thickness);
+    let vertical = Vec2::new(board.wall_thickness, board.height);
+
+    let walls = [
+        (Wall::Top, Vec2::new(0.0, board.half_height()), horizontal),
+        (
+            Wall::Bottom,
+            Vec2::new(0.0, -board.half_height()),
+            horizontal,
+        ),
+        // Scoring wall for P2
+        (Wall::Left, Vec2::new(-board.half_width(), 0.0), vertical),
+        // Scoring wall for P1
+        (Wall::Right, Vec2::new(board.half_width(), 0.0), vertical),
+    ];
+
+    for (wall, position, size) in walls {
+        commands.spawn((
+            Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            Transform::from_translation(position.extend(0.0)),
+            wall_physics_bundle(
+                Collider::cuboid(size.x / 2.0, size.y / 2.0),
+                board.wall_restitution,
+            ),
+            wall,
+            Themed::Wall,
+            BoardPiece,
+        ));
+    }
+}
+
+/// Spawns the faint background grid, behind the center line and walls.
+///
+/// Lines are spaced evenly out from the middle of the board, stopping at
+/// the walls' inside faces. The grid is hidden unless it is turned on in
+/// [`GraphicsSettings`].
+fn spawn_grid(
+    mut commands: Commands,
+    board: Res<BoardConfig>,
+    settings: Res<GraphicsSettings>,
+    theme: Res<Theme>,
+) {
+    let inner = Vec2::new(board.inner_half_width(), board.inner_half_height());
+    let visibility = grid_visibility(&settings);
+    let color = theme.colors().get(Themed::Grid);
+
+    // Vertical lines across the width, then horizontal ones up the height
+    let columns = (inner.x / GRID_SPACING).ceil() as i32 - 1;
+    let rows = (inner.y / GRID_SPACING).ceil() as i32 - 1;
+    let vertical = (-columns..=columns).map(|i| {
+        (
+            Vec2::new(i as f32 * GRID_SPACING, 0.0),
+            Vec2::new(GRID_LINE_WIDTH, inner.y * 2.0),
+        )
+    });
+    let horizontal = (-rows..=rows).map(|i| {
+        (
+            Vec2::new(0.0, i as f32 * GRID_SPACING),
+            Vec2::new(inner.x * 2.0, GRID_LINE_WIDTH),
+        )
+    });
+
+    for (position, size) in vertical.chain(horizontal) {
+        commands.spawn((
+            Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            Themed::Grid,
+            GridLine,
+            BoardPiece,
+            // Behind everything else on the board
+            Transform::from_translation(position.extend(-1.0)),
+            visibility,
+        ));
+    }
+}
+
+/// Whether the background grid is drawn
+fn grid_visibility(settings: &GraphicsSettings) -> Visibility {
+    if settings.background_grid {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+/// Shows or hides the background grid to match the graphics settings.
+fn show_background_grid(
+    settings: Res<GraphicsSettings>,
+    mut query: Query<&mut Visibility, With<GridLine>>,
+) {
+    let visibility = grid_visibility(&settings);
+    for mut line in query.iter_mut() {
+        line.set_if_neq(visibility);
+    }
+}
+
+/// Fades the center line dashes in a wave travelling down the line.
+///
+/// The pulse keeps its own clock, so it freezes while the game is paused
+/// and carries on from the same place. With the pulse turned off, the
+/// dashes are left at the theme's plain color.
+fn pulse_center_line(
+    time: Res<Time>,
+    settings: Res<GraphicsSettings>,
+    config: Res<CenterLineConfig>,
+    theme: Res<Theme>,
+    mut clock: Local<f32>,
+    mut query: Query<(&DashPulse, &mut Sprite)>,
+) {
+    let color = theme.colors().center_line;
+    if !settings.center_line_pulse {
+        for (_, mut sprite) in query.iter_mut() {
+            if sprite.color != color {
+                sprite.color = color;
+            }
+        }
+        return;
+    }
+
+    *clock += time.delta_secs();
+    let travel = *clock * config.pulse_speed / config.pulse_wavelength.max(f32::EPSILON) * TAU;
+    let depth = config.pulse_depth.clamp(0.0, 1.0);
+    for (pulse, mut sprite) in query.iter_mut() {
+        // 1.0 at the wave's peak, 0.0 at its faintest
+        let wave = 0.5 + 0.5 * (pulse.0 + travel).sin();
+        sprite.color = color.with_alpha(color.alpha() * (1.0 - depth * (1.0 - wave)));
+    }
+}
+
+/// Spawns the rounded inside of each corner, see [`Corner`].
+///
+/// Each arc is centered `corner_radius` in from both walls' inside faces,
+/// and runs from the scoring wall's face to the top or bottom wall's.
+fn spawn_corners(mut commands: Commands, board: Res<BoardConfig>) {
+    let inner = Vec2::new(board.inner_half_width(), board.inner_half_height());
+    let radius = board.corner_radius.min(inner.x).min(inner.y);
+    if radius <= 0.0 {
+        return;
+    }
+
+    for sign in [
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(-1.0, -1.0),
+    ] {
+        let center = (inner - Vec2::splat(radius)) * sign;
+        let arc: Vec<Vec2> = (0..=CORNER_SEGMENTS)
+            .map(|i| {
+                let angle = i as f32 / CORNER_SEGMENTS as f32 * std::f32::consts::FRAC_PI_2;
+                Vec2::from_angle(angle) * radius * sign
+            })
+            .collect();
+
+        commands.spawn((
+            Transform::from_translation(center.extend(0.0)),
+            wall_physics_bundle(Collider::polyline(arc, None), board.wall_restitution),
+            Corner,
+            BoardPiece,
+        ));
+    }
+}
+
+/// Halts the physics when the game is paused or over.
+///
+/// Rapier's steps are skipped rather than saved up, so nothing catches up
+/// in a burst when the physics resumes.
+fn halt_physics(mut rapier_query: Query<&mut RapierConfiguration>) {
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = false;
+    }
+}
+
+/// Resumes the physics when leaving the pause menu or end screen, unless an
+/// instant replay is still showing, which keeps it halted until it ends.
+fn resume_physics(replay: Res<InstantReplay>, mut rapier_query: Query<&mut RapierConfiguration>) {
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = !replay.is_playing();
+    }
+}
+
+/// Removes the walls, corners, center line and grid, so the board can be
+/// rebuilt.
+fn despawn_board(mut commands: Commands, query: Query<Entity, With<BoardPiece>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
 }
 
 /// Plugin that manages the game board setup.
 ///
 /// This plugin is responsible for:
-/// - Creating the black background
-/// - Spawning the bouncy walls
-/// - Drawing the center line
+/// - Initializing the board configuration and fixed timestep
+/// - Spawning the walls and their rounded corners
+/// - Drawing the center line, and its net colliders when enabled
+/// - Drawing the background grid
+/// - Rebuilding all of them whenever the board configuration changes
+/// - Pulsing the center line, except while paused
+/// - Halting the physics while paused and on the end screen
 pub struct BoardPlugin;
 
 impl Plugin for BoardPlugin {
     fn build(&self, app: &mut bevy::app::App) {
+        // Kept if the app already set its own timestep
+        app.init_resource::<PhysicsConfig>();
+        let timestep = app.world().resource::<PhysicsConfig>().timestep;
+
         app
-            // Set background color
-            .insert_resource(black_background())
-            // Add startup systems for board creation
-            .add_systems(Startup, (spawn_walls, spawn_center_line));
+            // Step both Bevy's fixed schedule and Rapier by the same amount
+            .insert_resource(Time::<Fixed>::from_seconds(timestep as f64))
+            .insert_resource(TimestepMode::Fixed {
+                dt: timestep,
+                substeps: 1,
+            })
+            .add_systems(OnEnter(GameState::Paused), halt_physics)
+            .add_systems(OnEnter(GameState::GameOver), halt_physics)
+            .add_systems(OnExit(GameState::Paused), resume_physics)
+            .add_systems(OnExit(GameState::GameOver), resume_physics)
+            // Initialize board configuration
+            .init_resource::<BoardConfig>()
+            .init_resource::<CenterLineConfig>()
+            .init_resource::<NetConfig>()
+            // Shared with the camera plugin, which owns the other settings
+            .init_resource::<GraphicsSettings>()
+            // Build the board when the game starts, and again whenever its
+            // size or walls change
+            .add_systems(
+                Update,
+                (
+                    despawn_board,
+                    spawn_walls,
+                    spawn_corners,
+                    spawn_center_line,
+                    spawn_grid,
+                )
+                    .chain()
+                    .run_if(resource_changed::<BoardConfig>),
+            )
+            .add_systems(
+                Update,
+                (
+                    show_background_grid.run_if(resource_changed::<GraphicsSettings>),
+                    pulse_center_line.run_if(not(in_state(GameState::Paused))),
+                ),
+            );
     }
 }
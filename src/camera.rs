@@ -1,54 +1,159 @@
 //! Camera Module
 //!
 //! This module handles the game's camera system, providing a 2D orthographic view
-//! of the game world. It maintains consistent scaling regardless of window size
-//! by using a fixed vertical height strategy.
+//! of the game world. It keeps the whole board visible regardless of window size
+//! by guaranteeing a minimum view width and height.
 //!
 //! The camera system ensures that:
-//! - Game objects appear the same size regardless of screen dimensions
+//! - The full board, including both scoring walls, is always on screen,
+//!   whichever court size is picked
 //! - The game viewport adjusts properly to different aspect ratios
 //! - World coordinates map consistently to screen space
+//!
+//! It also provides an optional CRT effect. A second camera renders a
+//! fullscreen overlay on its own render layer, after the game and UI have
+//! been drawn, darkening the image with scanlines, a vignette and curved
+//! screen edges. If the overlay's shader can't be compiled on the target,
+//! the overlay is simply not drawn and the game renders without the effect.
+//!
+//! The optional action zoom (toggled with F9) slowly tightens the view as
+//! the ball nears a scoring wall, panning just enough to keep the ball in
+//! the default view, and eases back out between points. The view is reset
+//! exactly whenever play stops.
 
-use bevy::app::{App, Plugin, Startup};
-use bevy::prelude::{Camera2d, Commands, OrthographicProjection};
+use crate::ball::Ball;
+use crate::board::BoardConfig;
+use crate::GameState;
+use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
+use bevy::render::render_resource::{AsBindGroup, ShaderRef};
+use bevy::render::view::RenderLayers;
+use bevy::sprite::{AlphaMode2d, Material2d, Material2dPlugin};
+
+/// Render layer used by the CRT overlay and its camera
+const CRT_LAYER: usize = 1;
+
+/// Number of scanlines drawn across the screen height
+const CRT_SCANLINES: f32 = 240.0;
+
+/// Graphics settings
+#[derive(Debug, Resource)]
+pub struct GraphicsSettings {
+    /// Whether the CRT effect is drawn (toggled with F10)
+    pub crt_enabled: bool,
+    /// Strength of the CRT effect from 0.0 (none) to 1.0 (full)
+    pub crt_intensity: f32,
+    /// Whether the camera zooms toward the action (toggled with F9)
+    pub zoom_enabled: bool,
+    /// Fraction of the view trimmed away when the ball reaches a scoring wall
+    pub zoom_amount: f32,
+    /// How far from the center toward either scoring wall the zoom begins,
+    /// as a fraction of the way there, so it suits every court size
+    pub zoom_start: f32,
+    /// How quickly the zoom eases toward its target, per second
+    pub zoom_rate: f32,
+    /// Whether a gentle pulse runs down the center line
+    pub center_line_pulse: bool,
+    /// Whether a faint grid is drawn behind the board
+    pub background_grid: bool,
+    /// Whether sparks fly off the paddle when it hits the ball
+    pub hit_sparks: bool,
+}
+
+impl Default for GraphicsSettings {
+    fn default() -> Self {
+        Self {
+            crt_enabled: false,
+            crt_intensity: 0.6,
+            zoom_enabled: false,     // Off unless asked for
+            zoom_amount: 0.05,       // Subtle: 5% tighter at the wall
+            zoom_start: 0.5,         // Halfway to either wall
+            zoom_rate: 1.5,          // Slow enough not to distract
+            center_line_pulse: true, // A gentle pulse unless turned off
+            background_grid: false,  // A bare board unless asked for
+            hit_sparks: true,        // Makes every hit feel solid
+        }
+    }
+}
+
+/// Material for the CRT overlay
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub struct CrtMaterial {
+    /// x: intensity, y: scanline count, z and w unused.
+    /// Packed into one vector to keep the uniform WebGL2 friendly.
+    #[uniform(0)]
+    params: Vec4,
+}
+
+impl Material2d for CrtMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/crt.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> AlphaMode2d {
+        AlphaMode2d::Blend
+    }
+}
+
+/// Marker component for the camera showing the board
+#[derive(Component)]
+pub(crate) struct MainCamera;
+
+/// Marker component for the camera drawing the CRT overlay
+#[derive(Component)]
+struct CrtCamera;
+
+/// The smallest view that shows the whole of `board`
+fn board_view(board: &BoardConfig) -> ScalingMode {
+    ScalingMode::AutoMin {
+        min_width: board.width,   // Minimum width in world units
+        min_height: board.height, // Minimum height in world units
+    }
+}
 
-/// Spawns a 2D camera with a fixed vertical viewport height.
+/// Spawns a 2D camera that always shows the whole board.
 ///
 /// # Camera Properties
 /// - Uses orthographic projection for 2D rendering
-/// - Maintains fixed vertical height of 10 world units
-/// - Automatically adjusts width based on window aspect ratio
+/// - Shows at least the board's width and height in world units
+/// - Extends the view in one direction when the window's aspect ratio
+///   doesn't match the board's, which letterboxes the board with the
+///   background color
+/// - Rescales automatically when the window is resized
 /// - Centers coordinate system at (0,0)
 ///
 /// # Coordinate System
-/// The viewport coordinates are mapped as follows:
+/// On the standard 16 x 10 court, the viewport coordinates are mapped as
+/// follows:
 /// - Center: (0, 0)
-/// - Vertical range: -5 to +5 units
-/// - Horizontal range: varies with aspect ratio
+/// - Wider than 16:10: vertical range -5 to +5, horizontal range wider
 ///   - 16:9 aspect: approximately -8.89 to +8.89 units
-///   - 16:10 aspect: approximately -8 to +8 units
-///   - 4:3 aspect: approximately -6.67 to +6.67 units
+/// - 16:10 aspect: exactly -8 to +8 by -5 to +5 units
+/// - Narrower than 16:10: horizontal range -8 to +8, vertical range taller
+///   - 4:3 aspect: approximately -6 to +6 units vertically
 ///
 /// # Example
 /// ```
 /// // Object at (0,0) appears at screen center
-/// // Object at (0,5) appears at top of screen
-/// // Object at (4,0) appears halfway to right edge in 16:10 window
+/// // Object at (8,0) is on screen at any aspect ratio
+/// // Object at (0,5) appears at top of screen in a 16:10 or wider window
 /// ```
-fn spawn_camera(mut commands: Commands) {
+fn spawn_camera(mut commands: Commands, board: Res<BoardConfig>) {
     commands.spawn((
         // Camera2d component marks this as a 2D camera
         // This sets up appropriate defaults for 2D rendering
         Camera2d,
+        // Tells it apart from the CRT overlay's camera
+        MainCamera,
+        // Draw the UI with the game camera, so the CRT overlay covers it too
+        IsDefaultUiCamera,
         // Configure the orthographic projection settings
         OrthographicProjection {
-            // Use fixed vertical scaling mode to maintain consistent height
-            // This ensures the game view is always exactly 10 units tall,
-            // with width adjusting to maintain the window's aspect ratio
-            scaling_mode: ScalingMode::FixedVertical {
-                viewport_height: 10.0, // Fixed height in world units
-            },
+            // Guarantee a minimum view in both directions
+            // The board always fits, and the remaining space on the long
+            // axis is filled with the background. Bevy's camera system
+            // recomputes the projection on every window resize.
+            scaling_mode: board_view(&board),
 
             // Use default settings for remaining properties:
             // - Near/far clipping planes
@@ -59,19 +164,175 @@ fn spawn_camera(mut commands: Commands) {
     ));
 }
 
+/// Spawns the CRT overlay camera and its fullscreen quad.
+///
+/// The overlay camera renders after the main camera without clearing, and
+/// only sees the overlay's render layer. Its projection is fixed at 2x2
+/// units, so a 2x2 quad always covers the whole window.
+fn spawn_crt_overlay(
+    mut commands: Commands,
+    settings: Res<GraphicsSettings>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CrtMaterial>>,
+) {
+    commands.spawn((
+        CrtCamera,
+        Camera2d,
+        Camera {
+            order: 1,
+            clear_color: ClearColorConfig::None,
+            is_active: settings.crt_enabled,
+            ..default()
+        },
+        OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: 2.0,
+                height: 2.0,
+            },
+            ..OrthographicProjection::default_2d()
+        },
+        RenderLayers::layer(CRT_LAYER),
+    ));
+
+    commands.spawn((
+        Mesh2d(meshes.add(Rectangle::new(2.0, 2.0))),
+        MeshMaterial2d(materials.add(CrtMaterial {
+            params: Vec4::new(settings.crt_intensity, CRT_SCANLINES, 0.0, 0.0),
+        })),
+        RenderLayers::layer(CRT_LAYER),
+    ));
+}
+
+/// Toggles the CRT effect with F10.
+fn toggle_crt(keyboard: Res<ButtonInput<KeyCode>>, mut settings: ResMut<GraphicsSettings>) {
+    if keyboard.just_pressed(KeyCode::F10) {
+        settings.crt_enabled = !settings.crt_enabled;
+    }
+}
+
+/// Applies changed graphics settings to the CRT overlay.
+fn apply_crt_settings(
+    settings: Res<GraphicsSettings>,
+    mut materials: ResMut<Assets<CrtMaterial>>,
+    mut camera_query: Query<&mut Camera, With<CrtCamera>>,
+) {
+    for mut camera in camera_query.iter_mut() {
+        camera.is_active = settings.crt_enabled;
+    }
+    for (_, material) in materials.iter_mut() {
+        material.params.x = settings.crt_intensity.clamp(0.0, 1.0);
+    }
+}
+
+/// Toggles the action zoom with F9.
+fn toggle_zoom(keyboard: Res<ButtonInput<KeyCode>>, mut settings: ResMut<GraphicsSettings>) {
+    if keyboard.just_pressed(KeyCode::F9) {
+        settings.zoom_enabled = !settings.zoom_enabled;
+    }
+}
+
+/// Eases the main camera's zoom toward the ball nearest a scoring wall.
+///
+/// The camera pans by no more than the zoom trims away, so everything
+/// inside the default view around the ball stays on screen. With no ball
+/// in play, or the zoom turned off, it eases back to the default view.
+fn zoom_to_action(
+    time: Res<Time>,
+    settings: Res<GraphicsSettings>,
+    board: Res<BoardConfig>,
+    ball_query: Query<&Transform, (With<Ball>, Without<MainCamera>)>,
+    mut camera_query: Query<(&mut OrthographicProjection, &mut Transform), With<MainCamera>>,
+) {
+    let ball = ball_query
+        .iter()
+        .map(|transform| transform.translation.truncate())
+        .max_by(|a, b| a.x.abs().total_cmp(&b.x.abs()))
+        .filter(|_| settings.zoom_enabled);
+
+    let (target_scale, focus) = match ball {
+        Some(position) => {
+            let start_x = board.half_width() * settings.zoom_start.clamp(0.0, 1.0);
+            let span = (board.half_width() - start_x).max(f32::EPSILON);
+            let closeness = ((position.x.abs() - start_x) / span).clamp(0.0, 1.0);
+            (
+                1.0 - settings.zoom_amount.clamp(0.0, 0.5) * closeness,
+                position,
+            )
+        }
+        None => (1.0, Vec2::ZERO),
+    };
+
+    let blend = 1.0 - (-settings.zoom_rate * time.delta_secs()).exp();
+    for (mut projection, mut transform) in camera_query.iter_mut() {
+        projection.scale += (target_scale - projection.scale) * blend;
+
+        let slack = Vec2::new(board.width, board.height) / 2.0 * (1.0 - projection.scale);
+        let center = focus.clamp(-slack, slack);
+        let current = transform.translation.truncate();
+        let next = (current + (center - current) * blend).clamp(-slack, slack);
+        transform.translation.x = next.x;
+        transform.translation.y = next.y;
+    }
+}
+
+/// Refits the main camera's view when the board changes size.
+fn fit_view_to_board(
+    board: Res<BoardConfig>,
+    mut camera_query: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    for mut projection in camera_query.iter_mut() {
+        projection.scaling_mode = board_view(&board);
+    }
+}
+
+/// Restores the main camera's default view.
+fn reset_zoom(
+    mut camera_query: Query<(&mut OrthographicProjection, &mut Transform), With<MainCamera>>,
+) {
+    for (mut projection, mut transform) in camera_query.iter_mut() {
+        projection.scale = 1.0;
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+    }
+}
+
 /// Plugin responsible for camera setup and management.
 ///
 /// # Features
 /// - Spawns and configures the main 2D camera
 /// - Sets up orthographic projection
 /// - Ensures consistent scaling across different screen sizes
+/// - Refits the view when the board changes size
+/// - Provides the optional CRT overlay
+/// - Provides the optional action zoom
 pub(crate) struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        // Add camera spawn system to startup schedule
-        // This ensures the camera is created when the game begins
-        // and before any other systems that might need it
-        app.add_systems(Startup, spawn_camera);
+        app.add_plugins(Material2dPlugin::<CrtMaterial>::default())
+            .init_resource::<GraphicsSettings>()
+            // Add camera spawn systems to startup schedule
+            // This ensures the cameras are created when the game begins
+            // and before any other systems that might need them
+            .add_systems(Startup, (spawn_camera, spawn_crt_overlay))
+            .add_systems(
+                Update,
+                (
+                    toggle_crt,
+                    apply_crt_settings.run_if(resource_changed::<GraphicsSettings>),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                Update,
+                fit_view_to_board.run_if(resource_changed::<BoardConfig>),
+            )
+            .add_systems(
+                Update,
+                (toggle_zoom, zoom_to_action)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), reset_zoom);
     }
 }
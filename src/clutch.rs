@@ -0,0 +1,168 @@
+//! Dramatic Saves Module
+//!
+//! This module provides the optional "dramatic saves" rule, a brief
+//! slow-motion moment when the ball is about to score, including:
+//! - Spotting a fast ball closing in on a scoring wall
+//! - Slowing that ball down for a fraction of a second
+//! - Letting each side have it at most once per rally
+//! - Sparing the AI's defense unless asked to be fair to it
+//!
+//! The slowdown itself is a [`SlowMotion`] on the ball, which the ball
+//! module's speed clamp honors and later undoes. The rule is off by default
+//! and is turned on from the menu.
+
+use crate::ball::{maintain_ball_velocity, Ball, SlowMotion};
+use crate::board::{BoardConfig, Wall};
+use crate::player::{Controller, Player, PlayerRoles, PlayerSide};
+use crate::replay::not_replaying;
+use crate::score::ServeStarted;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Configuration for dramatic saves
+#[derive(Debug, Resource)]
+pub struct ClutchConfig {
+    /// Whether dramatic saves happen at all
+    pub enabled: bool,
+    /// Whether the AI's defense gets them too
+    pub include_ai: bool,
+    /// Distance from a scoring wall, in world units, where they can start
+    pub wall_distance: f32,
+    /// Slowest ball, in world units per second, that gets one
+    pub min_speed: f32,
+    /// Fraction of its speed the ball keeps while slowed
+    pub factor: f32,
+    /// How long the slowdown lasts in seconds
+    pub duration: f32,
+}
+
+impl Default for ClutchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,     // Opt in from the menu
+            include_ai: false,  // Only humans get a second chance
+            wall_distance: 1.5, // About the paddle's own height away
+            min_speed: 12.0,    // Only balls too fast to comfortably read
+            factor: 0.6,        // Noticeable, but the ball keeps moving
+            duration: 0.3,      // Over before the rally loses its pace
+        }
+    }
+}
+
+impl ClutchConfig {
+    /// Label for the menu's dramatic saves line
+    pub fn label(&self) -> &'static str {
+        if self.enabled {
+            "On"
+        } else {
+            "Off"
+        }
+    }
+}
+
+/// Resource tracking which players have had their dramatic save this rally
+#[derive(Debug, Resource, Default)]
+struct ClutchUsed {
+    /// Whether `Player::P1` has had it
+    p1: bool,
+    /// Whether `Player::P2` has had it
+    p2: bool,
+}
+
+impl ClutchUsed {
+    /// Mutable access to the given player's flag
+    fn get_mut(&mut self, player: Player) -> &mut bool {
+        match player {
+            Player::P1 => &mut self.p1,
+            Player::P2 => &mut self.p2,
+        }
+    }
+}
+
+/// Query data for a ball that can be slowed down
+type SlowableBall<'a> = (Entity, &'a Transform, &'a mut Velocity);
+
+/// Plugin that slows the ball for dramatic saves.
+pub struct ClutchPlugin;
+
+impl Plugin for ClutchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClutchConfig>()
+            .init_resource::<ClutchUsed>()
+            .add_systems(
+                Update,
+                reset_clutch_on_serve.run_if(in_state(GameState::Playing)),
+            )
+            // Between the physics step and the speed clamp, so the clamp
+            // already sees the slowdown
+            .add_systems(
+                FixedUpdate,
+                trigger_dramatic_saves
+                    .after(PhysicsSet::Writeback)
+                    .before(maintain_ball_velocity)
+                    .run_if(in_state(GameState::Playing).and(not_replaying)),
+            );
+    }
+}
+
+/// Gives both players their dramatic save back when a new rally is served.
+fn reset_clutch_on_serve(mut used: ResMut<ClutchUsed>, mut serves: EventReader<ServeStarted>) {
+    if serves.read().count() > 0 {
+        *used = ClutchUsed::default();
+    }
+}
+
+/// Slows down a fast ball closing in on a scoring wall, if its defender
+/// hasn't had a dramatic save yet this rally.
+fn trigger_dramatic_saves(
+    mut commands: Commands,
+    config: Res<ClutchConfig>,
+    board: Res<BoardConfig>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
+    mut used: ResMut<ClutchUsed>,
+    mut ball_query: Query<SlowableBall, (With<Ball>, Without<SlowMotion>)>,
+) {
+    if !config.enabled {
+        return;
+    }
+
+    for (entity, transform, mut velocity) in ball_query.iter_mut() {
+        let linvel = velocity.linvel;
+        if linvel.x == 0.0 || linvel.length() < config.min_speed {
+            continue;
+        }
+
+        let wall = if linvel.x > 0.0 {
+            Wall::Right
+        } else {
+            Wall::Left
+        };
+        let distance = board.half_width() - transform.translation.x * linvel.x.signum();
+        if distance > config.wall_distance {
+            continue;
+        }
+
+        let Some(defender) = side.defender(&wall) else {
+            continue;
+        };
+        let ai_defends = matches!(
+            roles.controller(defender),
+            Controller::Ai | Controller::Ghost
+        );
+        if ai_defends && !config.include_ai {
+            continue;
+        }
+        let had_save = used.get_mut(defender);
+        if *had_save {
+            continue;
+        }
+        *had_save = true;
+
+        velocity.linvel *= config.factor;
+        commands
+            .entity(entity)
+            .insert(SlowMotion::new(config.factor, config.duration));
+    }
+}
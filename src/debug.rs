@@ -0,0 +1,252 @@
+//! Debug Overlay Module
+//!
+//! This module implements a developer overlay for tuning the AI and physics,
+//! toggled with F3. While enabled it shows:
+//! - Frames per second
+//! - The ball's position and speed
+//! - The AI paddle's last prediction, movement state and aim (F8 cycles it)
+//! - The AI's current error and miss chances and decision rate, which
+//!   adaptive difficulty changes with the score
+//! - Where the AI believes the human's returns go
+//! - The player who served, the number of paddle hits in the current rally
+//!   and the ball's speed off the last hit
+//! - A gizmo line along the ball's velocity
+//! - A marker where the AI expects the ball to reach its paddle
+//!
+//! Separately, F4 toggles Rapier's debug render, which draws the physics
+//! colliders and contacts over the game. It works in every state, including
+//! while paused, and is independent of the F3 overlay.
+//!
+//! The module is only compiled into debug builds, or into release builds
+//! with the `debug-overlay` feature enabled.
+
+use crate::ball::Ball;
+use crate::player::{AiAdaptation, AiConfig, AiDebugInfo, PaddleHit, Player};
+use crate::score::ServeStarted;
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Color of the overlay text and gizmos
+const DEBUG_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+
+/// How far ahead the velocity line reaches, in seconds of ball travel
+const VELOCITY_LINE_SECONDS: f32 = 0.25;
+
+/// Radius of the predicted intersection marker in world units
+const PREDICTION_MARKER_RADIUS: f32 = 0.2;
+
+/// Resource controlling whether the debug overlay is shown
+#[derive(Debug, Resource, Default)]
+pub struct DebugOverlay {
+    /// Whether the overlay is visible
+    pub enabled: bool,
+}
+
+/// Resource tracking the rally since the ball was last served
+#[derive(Debug, Resource, Default)]
+struct Rally {
+    /// Player who served the ball
+    server: Option<Player>,
+    /// Paddle hits since the serve
+    hits: u32,
+    /// Ball speed leaving the paddle on the last hit
+    last_hit_speed: Option<f32>,
+}
+
+/// Marker component for the overlay's text block
+#[derive(Component)]
+struct DebugText;
+
+/// Run condition that passes while the overlay is enabled
+fn overlay_enabled(overlay: Res<DebugOverlay>) -> bool {
+    overlay.enabled
+}
+
+/// Toggles the overlay when F3 is pressed
+fn toggle_debug_overlay(keyboard: Res<ButtonInput<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keyboard.just_pressed(KeyCode::F3) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+/// Spawns or despawns the overlay text to match the setting
+fn sync_debug_text(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    query: Query<Entity, With<DebugText>>,
+) {
+    if !overlay.enabled {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    if query.is_empty() {
+        // The default font is Fira Mono, so the block lines up in columns
+        commands.spawn((
+            DebugText,
+            Text::default(),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(DEBUG_COLOR),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                ..default()
+            },
+        ));
+    }
+}
+
+/// Counts paddle hits, starting over whenever a new ball is served
+fn count_rally(
+    mut rally: ResMut<Rally>,
+    mut serves: EventReader<ServeStarted>,
+    mut hits: EventReader<PaddleHit>,
+) {
+    for serve in serves.read() {
+        *rally = Rally {
+            server: Some(serve.server),
+            ..default()
+        };
+    }
+    for hit in hits.read() {
+        rally.hits += 1;
+        rally.last_hit_speed = Some(hit.ball_speed);
+    }
+}
+
+/// Toggles Rapier's collider rendering when F4 is pressed
+fn toggle_collider_render(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut render_context: ResMut<DebugRenderContext>,
+) {
+    if keyboard.just_pressed(KeyCode::F4) {
+        render_context.enabled = !render_context.enabled;
+    }
+}
+
+/// Refreshes the overlay text with the latest measurements
+fn update_debug_text(
+    diagnostics: Res<DiagnosticsStore>,
+    rally: Res<Rally>,
+    ai_config: Res<AiConfig>,
+    adaptation: Res<AiAdaptation>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    ai_query: Query<&AiDebugInfo>,
+    mut text_query: Query<&mut Text, With<DebugText>>,
+) {
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.0);
+
+    let mut lines = vec![format!("FPS:        {:>6.1}", fps)];
+
+    if ball_query.is_empty() {
+        lines.push("Ball:       none".to_string());
+    }
+    for (transform, velocity) in ball_query.iter() {
+        let position = transform.translation.truncate();
+        lines.push(format!(
+            "Ball:       ({:>6.2}, {:>6.2})",
+            position.x, position.y
+        ));
+        lines.push(format!("Speed:      {:>6.2}", velocity.linvel.length()));
+    }
+
+    for ai in ai_query.iter() {
+        let prediction = ai
+            .last_prediction
+            .map_or("none".to_string(), |y| format!("{:>6.2}", y));
+        lines.push(format!("Prediction: {}", prediction));
+        lines.push(format!("AI state:   {:?}", ai.movement_state));
+        lines.push(format!("AI aim:     {:?}", ai_config.aim));
+        lines.push(format!(
+            "AI odds:    error {:.3} / miss {:.3} / every {:.2}s",
+            ai_config.error_chance, ai_config.miss_chance, ai_config.update_rate
+        ));
+        let [top, middle, bottom] = adaptation.shares().map(|share| share * 100.0);
+        lines.push(format!(
+            "AI reads:   top {:.0}% / mid {:.0}% / bottom {:.0}%",
+            top, middle, bottom
+        ));
+    }
+
+    let server = rally
+        .server
+        .map_or("none".to_string(), |server| format!("{:?}", server));
+    lines.push(format!("Server:     {:>6}", server));
+    lines.push(format!("Rally:      {:>6}", rally.hits));
+    let last_hit = rally
+        .last_hit_speed
+        .map_or("none".to_string(), |speed| format!("{:>6.2}", speed));
+    lines.push(format!("Last hit:   {}", last_hit));
+
+    for mut text in text_query.iter_mut() {
+        **text = lines.join("\n");
+    }
+}
+
+/// Draws the ball's velocity and the AI's predicted intersection point
+fn draw_debug_gizmos(
+    mut gizmos: Gizmos,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    ai_query: Query<(&Transform, &AiDebugInfo)>,
+) {
+    for (transform, velocity) in ball_query.iter() {
+        let start = transform.translation.truncate();
+        let end = start + velocity.linvel * VELOCITY_LINE_SECONDS;
+        gizmos.line_2d(start, end, DEBUG_COLOR);
+    }
+
+    for (transform, ai) in ai_query.iter() {
+        if let Some(y) = ai.last_prediction {
+            let marker = Vec2::new(transform.translation.x, y);
+            gizmos.circle_2d(
+                Isometry2d::from_translation(marker),
+                PREDICTION_MARKER_RADIUS,
+                DEBUG_COLOR,
+            );
+        }
+    }
+}
+
+/// Plugin that provides the F3 debug overlay and F4 collider rendering.
+///
+/// Adds frame time diagnostics if they aren't already registered, and only
+/// runs the measurement and drawing systems while the overlay is enabled.
+/// Rapier's debug render starts disabled.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin);
+        }
+
+        app.add_plugins(RapierDebugRenderPlugin::default().disabled())
+            .init_resource::<DebugOverlay>()
+            .init_resource::<Rally>()
+            .add_systems(
+                Update,
+                (
+                    toggle_debug_overlay,
+                    sync_debug_text.run_if(resource_changed::<DebugOverlay>),
+                )
+                    .chain(),
+            )
+            .add_systems(Update, toggle_collider_render)
+            // Rallies are counted even while hidden so the count is right when shown
+            .add_systems(Update, count_rally)
+            .add_systems(
+                Update,
+                (update_debug_text, draw_debug_gizmos).run_if(overlay_enabled),
+            );
+    }
+}
@@ -1,12 +1,21 @@
 //! Endgame Screen Module
 //!
 //! This module handles the game's victory screen, including:
-//! - Victory/Defeat message display
-//! - Final score display
-//! - Prompt for starting a new game
+//! - Victory/Defeat, winning player or draw message display
+//! - Final score and match duration display
+//! - Survival time and session best in survival mode
+//! - Callouts for lifetime records broken during the match
+//! - A quiet note of any handicaps the match was played with
+//! - "Play Again" / "Back to Menu" selection
 //! - Game state reset functionality
 
-use crate::score::Score;
+use crate::mode::GameMode;
+use crate::palette::{overlay, ENDGAME_OVERLAY_ALPHA, FOREGROUND, RUST_ORANGE, VICTORY_GREEN};
+use crate::player::{Handicaps, Player, PlayerRoles};
+use crate::profile::NewRecords;
+use crate::score::{format_duration, GameResult, GameWon, MatchTimer, Score, ScoreRules};
+use crate::survival::SurvivalStats;
+use crate::theme::{Theme, Themed};
 use crate::GameState;
 use bevy::prelude::*;
 
@@ -17,15 +26,91 @@ pub struct EndgamePlugin;
 #[derive(Component)]
 struct EndgameScreen;
 
+/// The options offered on the endgame screen.
+/// Also used as a resource tracking the highlighted option.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum EndgameOption {
+    #[default]
+    PlayAgain, // Reset the score and start a rematch
+    BackToMenu, // Reset the score and return to the menu
+}
+
+impl EndgameOption {
+    /// Text shown for the option
+    fn label(&self) -> &'static str {
+        match self {
+            EndgameOption::PlayAgain => "Play Again",
+            EndgameOption::BackToMenu => "Back to Menu",
+        }
+    }
+
+    /// Returns the other option, used for arrow key navigation
+    fn toggle(&self) -> Self {
+        match self {
+            EndgameOption::PlayAgain => EndgameOption::BackToMenu,
+            EndgameOption::BackToMenu => EndgameOption::PlayAgain,
+        }
+    }
+}
+
+/// Component attached to each selectable option's text
+#[derive(Component)]
+struct EndgameOptionText(EndgameOption);
+
+/// Color used to highlight the selected option
+const SELECTED_COLOR: Color = RUST_ORANGE;
+
+/// Color for winning messages
+const WIN_COLOR: Color = VICTORY_GREEN;
+
+/// Color for losing messages
+const LOSS_COLOR: Color = RUST_ORANGE;
+
+/// Color for draw messages
+const DRAW_COLOR: Color = FOREGROUND;
+
+/// Color for new record callouts
+const RECORD_COLOR: Color = VICTORY_GREEN;
+
+/// Chooses the headline message and its color for a finished game.
+///
+/// - Survival mode always ends in "Game Over"
+/// - Drawn games show "Draw"
+/// - Human-vs-AI games show "Victory!" or "Defeat!" from the human's view
+/// - Otherwise the winning player is named
+fn result_message(
+    mode: GameMode,
+    roles: &PlayerRoles,
+    result: GameResult,
+) -> (&'static str, Color) {
+    if mode == GameMode::Survival {
+        return ("Game Over", LOSS_COLOR);
+    }
+    let Some(winner) = result.winner() else {
+        return ("Draw", DRAW_COLOR);
+    };
+
+    match roles.sole_human() {
+        Some(human) if human == winner => ("Victory!", WIN_COLOR),
+        Some(_) => ("Defeat!", LOSS_COLOR),
+        None => match winner {
+            Player::P1 => ("Player 1 Wins!", WIN_COLOR),
+            Player::P2 => ("Player 2 Wins!", WIN_COLOR),
+        },
+    }
+}
+
 impl Plugin for EndgamePlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<EndgameOption>()
             // Create victory screen when entering GameOver state
             .add_systems(OnEnter(GameState::GameOver), spawn_endgame_screen)
-            // Handle space bar input while in GameOver state
+            // Handle menu input while in GameOver state
             .add_systems(
                 Update,
-                handle_endgame_input.run_if(in_state(GameState::GameOver)),
+                (handle_endgame_input, update_option_highlight)
+                    .chain()
+                    .run_if(in_state(GameState::GameOver)),
             )
             // Clean up victory screen when leaving GameOver state
             .add_systems(OnExit(GameState::GameOver), despawn_endgame_screen);
@@ -33,11 +118,45 @@ impl Plugin for EndgamePlugin {
 }
 
 /// Spawns the victory screen UI elements
-fn spawn_endgame_screen(mut commands: Commands, score: Res<Score>) {
-    let (message, color) = if score.p1 > score.p2 {
-        ("Victory!", Color::srgba(0.1, 0.89, 0.24, 1.0)) // Complementary green (26/255, 228/255, 61/255)
+#[allow(clippy::too_many_arguments)]
+fn spawn_endgame_screen(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    theme: Res<Theme>,
+    roles: Res<PlayerRoles>,
+    score: Res<Score>,
+    timer: Res<MatchTimer>,
+    survival: Res<SurvivalStats>,
+    records: Res<NewRecords>,
+    handicaps: Res<Handicaps>,
+    mut selected: ResMut<EndgameOption>,
+    mut wins: EventReader<GameWon>,
+) {
+    // Always start with the rematch highlighted
+    *selected = EndgameOption::PlayAgain;
+
+    // The match was won on the frame before this screen appeared
+    let result = wins.read().last().map_or_else(
+        || GameResult::from_score(&score),
+        |won| GameResult::from(won.winner),
+    );
+    let (message, color) = result_message(*mode, &roles, result);
+    let text_color = theme.colors().text;
+
+    // Summary lines and their font sizes
+    let details = if *mode == GameMode::Survival {
+        // The record may not have been updated yet this frame
+        let best = survival.best.max(timer.elapsed);
+        vec![
+            (format!("You survived {}", timer.formatted()), 40.0),
+            (format!("Returns: {}", survival.returns), 30.0),
+            (format!("Session Best: {}", format_duration(best)), 30.0),
+        ]
     } else {
-        ("Defeat!", Color::srgba(0.89, 0.24, 0.1, 1.0)) // Rust orange (228/255, 61/255, 26/255)
+        vec![
+            (format!("Final Score: {} - {}", score.p1, score.p2), 40.0),
+            (format!("Match Time: {}", timer.formatted()), 30.0),
+        ]
     };
 
     commands
@@ -52,15 +171,15 @@ fn spawn_endgame_screen(mut commands: Commands, score: Res<Score>) {
                 height: Val::Percent(100.0),
                 ..default()
             },
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+            BackgroundColor(overlay(ENDGAME_OVERLAY_ALPHA)),
             Visibility::default(),
         ))
         .with_children(|parent| {
-            // Victory/Defeat message
+            // Result message
             parent.spawn((
                 Text::new(message),
                 TextFont {
-                    font_size: 80.0,
+                    font_size: theme.font_size(80.0),
                     ..default()
                 },
                 TextColor(color),
@@ -70,43 +189,149 @@ fn spawn_endgame_screen(mut commands: Commands, score: Res<Score>) {
                 },
             ));
 
-            // Final score
-            parent.spawn((
-                Text::new(format!("Final Score: {} - {}", score.p1, score.p2)),
-                TextFont {
-                    font_size: 40.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                Node {
-                    margin: UiRect::bottom(Val::Px(20.0)),
-                    ..default()
-                },
-            ));
+            // Final score, match duration or survival summary
+            for (detail, font_size) in details {
+                parent.spawn((
+                    Text::new(detail),
+                    TextFont {
+                        font_size: theme.font_size(font_size),
+                        ..default()
+                    },
+                    TextColor(text_color),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+            }
 
-            // Play again prompt
-            parent.spawn((
-                Text::new("Press SPACE to play again"),
-                TextFont {
-                    font_size: 40.0,
-                    ..default()
-                },
-                TextColor(Color::WHITE),
-                Node::default(),
-            ));
+            // Lifetime records broken this match
+            for callout in records.callouts() {
+                parent.spawn((
+                    Text::new(callout),
+                    TextFont {
+                        font_size: theme.font_size(30.0),
+                        ..default()
+                    },
+                    TextColor(RECORD_COLOR),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            // Handicaps, so a win against a slowed down opponent is
+            // remembered as such
+            if let Some(note) = handicap_note(*mode, &handicaps) {
+                parent.spawn((
+                    Text::new(note),
+                    TextFont {
+                        font_size: theme.font_size(20.0),
+                        ..default()
+                    },
+                    TextColor(theme.colors().get(Themed::DimText)),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(20.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            // Selectable options
+            for option in [EndgameOption::PlayAgain, EndgameOption::BackToMenu] {
+                parent.spawn((
+                    EndgameOptionText(option),
+                    Text::new(option_text(option, *selected)),
+                    TextFont {
+                        font_size: theme.font_size(40.0),
+                        ..default()
+                    },
+                    TextColor(option_color(option, *selected, text_color)),
+                    Node {
+                        margin: UiRect::bottom(Val::Px(10.0)),
+                        ..default()
+                    },
+                ));
+            }
         });
 }
 
+/// Lists the players who didn't play with an even handicap, or `None` if
+/// nobody had one. Survival mode has no second paddle to list.
+fn handicap_note(mode: GameMode, handicaps: &Handicaps) -> Option<String> {
+    let notes: Vec<String> = [(Player::P1, "P1"), (Player::P2, "P2")]
+        .into_iter()
+        .filter(|(player, _)| mode != GameMode::Survival || *player == Player::P1)
+        .filter(|(player, _)| !handicaps.get(*player).is_even())
+        .map(|(player, name)| format!("{} {}", name, handicaps.get(player).label()))
+        .collect();
+    (!notes.is_empty()).then(|| format!("Handicaps: {}", notes.join("  ·  ")))
+}
+
+/// Formats an option's text, marking the selected one
+fn option_text(option: EndgameOption, selected: EndgameOption) -> String {
+    if option == selected {
+        format!("> {} <", option.label())
+    } else {
+        option.label().to_string()
+    }
+}
+
+/// Returns the text color for an option, highlighting the selected one
+fn option_color(option: EndgameOption, selected: EndgameOption, text_color: Color) -> Color {
+    if option == selected {
+        SELECTED_COLOR
+    } else {
+        text_color
+    }
+}
+
 /// Handles keyboard input on the victory screen
+///
+/// Arrow keys move the selection, and Enter or Space confirms it.
 fn handle_endgame_input(
     keyboard: Res<ButtonInput<KeyCode>>,
+    mut selected: ResMut<EndgameOption>,
     mut next_state: ResMut<NextState<GameState>>,
     mut score: ResMut<Score>,
+    rules: Res<ScoreRules>,
+    mut timer: ResMut<MatchTimer>,
+    mut survival: ResMut<SurvivalStats>,
 ) {
-    if keyboard.just_pressed(KeyCode::Space) {
-        // Reset score and start new game
-        score.reset();
-        next_state.set(GameState::Playing);
+    if keyboard.any_just_pressed([
+        KeyCode::ArrowUp,
+        KeyCode::ArrowDown,
+        KeyCode::ArrowLeft,
+        KeyCode::ArrowRight,
+    ]) {
+        *selected = selected.toggle();
+    }
+
+    if keyboard.any_just_pressed([KeyCode::Space, KeyCode::Enter]) {
+        // Either way the finished game's score and time are cleared
+        score.reset(&rules);
+        timer.reset();
+        survival.reset_run();
+        match *selected {
+            EndgameOption::PlayAgain => next_state.set(GameState::Playing),
+            EndgameOption::BackToMenu => next_state.set(GameState::Menu),
+        }
+    }
+}
+
+/// Updates option text and colors when the selection changes
+fn update_option_highlight(
+    selected: Res<EndgameOption>,
+    theme: Res<Theme>,
+    mut query: Query<(&EndgameOptionText, &mut Text, &mut TextColor)>,
+) {
+    if !selected.is_changed() {
+        return;
+    }
+    for (option, mut text, mut color) in query.iter_mut() {
+        **text = option_text(option.0, *selected);
+        color.0 = option_color(option.0, *selected, theme.colors().text);
     }
 }
 
@@ -116,3 +341,74 @@ fn despawn_endgame_screen(mut commands: Commands, screen: Query<Entity, With<End
         commands.entity(entity).despawn_recursive();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Controller::{self, Ai, Human};
+
+    /// The headline for a classic match between `p1` and `p2`
+    fn message(p1: Controller, p2: Controller, result: GameResult) -> (&'static str, Color) {
+        result_message(GameMode::Classic, &PlayerRoles { p1, p2 }, result)
+    }
+
+    #[test]
+    fn human_against_ai_is_told_victory_or_defeat() {
+        assert_eq!(
+            message(Human, Ai, GameResult::P1Win),
+            ("Victory!", WIN_COLOR)
+        );
+        assert_eq!(
+            message(Human, Ai, GameResult::P2Win),
+            ("Defeat!", LOSS_COLOR)
+        );
+    }
+
+    #[test]
+    fn human_on_the_second_slot_sees_their_own_result() {
+        assert_eq!(
+            message(Ai, Human, GameResult::P2Win),
+            ("Victory!", WIN_COLOR)
+        );
+        assert_eq!(
+            message(Ai, Human, GameResult::P1Win),
+            ("Defeat!", LOSS_COLOR)
+        );
+    }
+
+    #[test]
+    fn two_humans_are_told_who_won() {
+        assert_eq!(
+            message(Human, Human, GameResult::P1Win),
+            ("Player 1 Wins!", WIN_COLOR)
+        );
+        assert_eq!(
+            message(Human, Human, GameResult::P2Win),
+            ("Player 2 Wins!", WIN_COLOR)
+        );
+    }
+
+    #[test]
+    fn two_ais_are_told_who_won() {
+        assert_eq!(
+            message(Ai, Ai, GameResult::P1Win),
+            ("Player 1 Wins!", WIN_COLOR)
+        );
+        assert_eq!(
+            message(Ai, Ai, GameResult::P2Win),
+            ("Player 2 Wins!", WIN_COLOR)
+        );
+    }
+
+    #[test]
+    fn draws_and_survival_ignore_the_roles() {
+        for (p1, p2) in [(Human, Ai), (Ai, Human), (Human, Human), (Ai, Ai)] {
+            assert_eq!(message(p1, p2, GameResult::Draw), ("Draw", DRAW_COLOR));
+            let roles = PlayerRoles { p1, p2 };
+            assert_eq!(
+                result_message(GameMode::Survival, &roles, GameResult::P2Win),
+                ("Game Over", LOSS_COLOR)
+            );
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! Ghost Opponent Module
+//!
+//! This module records how the human plays, so their next opponent can be a
+//! ghost of themselves, including:
+//! - Where the human's paddle was for each situation of the ball
+//! - How many balls they returned and missed
+//! - Keeping the last finished match's recording as the ghost
+//! - Saving the ghost between sessions on native builds
+//!
+//! Situations are bucketed by the ball's distance from the paddle, its
+//! height, its vertical speed and whether it is coming or going, measured
+//! from the paddle's point of view so a ghost can play either side. Each
+//! bucket keeps the average paddle height seen in it. The ghost paddle,
+//! driven from the player module, heads for the height of the nearest known
+//! situation and leaves unknown ones to the regular AI.
+//!
+//! Only games with a single local human are recorded, and only finished
+//! matches replace the ghost. Browsers, with no file system, keep the ghost
+//! for the session only.
+
+use crate::ball::Ball;
+use crate::board::BoardConfig;
+use crate::player::{PaddleConfig, PaddleHit, Player, PlayerRoles, PlayerSide};
+use crate::replay::not_replaying;
+use crate::score::{MatchRestarted, PointScored};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// File the ghost is saved to and loaded from
+#[cfg(not(target_arch = "wasm32"))]
+const GHOST_FILE: &str = "rusty_pong_ghost.ron";
+
+/// Version of the ghost file format.
+/// Bump whenever [`GhostRecording`] changes shape.
+const GHOST_FORMAT: u32 = 1;
+
+/// Width of a distance bucket in world units
+const DISTANCE_BUCKET: f32 = 1.0;
+
+/// Height of a ball height bucket in world units
+const HEIGHT_BUCKET: f32 = 0.5;
+
+/// Size of a vertical speed bucket in world units per second
+const SPEED_BUCKET: f32 = 2.0;
+
+/// A situation of the ball as seen from a paddle: whether it is coming
+/// toward the paddle, and the buckets of its distance, height and vertical
+/// speed
+type Situation = (bool, i16, i16, i16);
+
+/// Paddle heights seen in one situation
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct GhostCell {
+    /// Sum of the paddle heights seen
+    total_y: f32,
+    /// Number of heights seen
+    samples: u32,
+}
+
+impl GhostCell {
+    /// Average paddle height in this situation
+    fn mean_y(&self) -> f32 {
+        self.total_y / self.samples as f32
+    }
+}
+
+/// Resource holding the recording the ghost opponent plays from: the last
+/// finished match with a single local human.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct GhostRecording {
+    /// File format version, see [`GHOST_FORMAT`]
+    format: u32,
+    /// Paddle heights by situation
+    cells: BTreeMap<Situation, GhostCell>,
+    /// Balls the human returned
+    pub returns: u32,
+    /// Points the human conceded
+    pub misses: u32,
+}
+
+impl Default for GhostRecording {
+    fn default() -> Self {
+        Self {
+            format: GHOST_FORMAT,
+            cells: BTreeMap::new(),
+            returns: 0,
+            misses: 0,
+        }
+    }
+}
+
+/// Buckets the ball's situation relative to a paddle at `paddle_x` whose
+/// scoop faces `facing` (1.0 for right, -1.0 for left)
+fn situation(paddle_x: f32, facing: f32, ball_pos: Vec2, ball_vel: Vec2) -> Situation {
+    let coming = ball_vel.x * facing < 0.0;
+    let distance = (ball_pos.x - paddle_x) * facing;
+    (
+        coming,
+        (distance / DISTANCE_BUCKET).floor() as i16,
+        (ball_pos.y / HEIGHT_BUCKET).floor() as i16,
+        (ball_vel.y / SPEED_BUCKET).floor() as i16,
+    )
+}
+
+impl GhostRecording {
+    /// Whether nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Notes the paddle's height for the ball's current situation
+    pub fn record(&mut self, paddle_x: f32, facing: f32, ball_pos: Vec2, ball_vel: Vec2, y: f32) {
+        let cell = self
+            .cells
+            .entry(situation(paddle_x, facing, ball_pos, ball_vel))
+            .or_default();
+        cell.total_y += y;
+        cell.samples += 1;
+    }
+
+    /// Height the recorded paddle took in the nearest known situation to
+    /// the ball's current one, or `None` if nothing close was recorded.
+    ///
+    /// Situations one bucket away in any direction count as close, with
+    /// fewer differences preferred and more samples breaking ties.
+    pub fn target_y(
+        &self,
+        paddle_x: f32,
+        facing: f32,
+        ball_pos: Vec2,
+        ball_vel: Vec2,
+    ) -> Option<f32> {
+        let (coming, distance, height, speed) = situation(paddle_x, facing, ball_pos, ball_vel);
+
+        let mut nearest: Option<(i16, u32, f32)> = None;
+        for d in -1..=1 {
+            for h in -1..=1 {
+                for s in -1..=1 {
+                    let key = (coming, distance + d, height + h, speed + s);
+                    let Some(cell) = self.cells.get(&key) else {
+                        continue;
+                    };
+                    let offset = d.abs() + h.abs() + s.abs();
+                    let closer = nearest.is_none_or(|(best, samples, _)| {
+                        offset < best || (offset == best && cell.samples > samples)
+                    });
+                    if closer {
+                        nearest = Some((offset, cell.samples, cell.mean_y()));
+                    }
+                }
+            }
+        }
+        nearest.map(|(_, _, y)| y)
+    }
+
+    /// Writes the recording to a RON file
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save(&self, path: &str) -> std::io::Result<()> {
+        let contents = ron::to_string(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    /// Reads a recording from a RON file, rejecting other format versions
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load(path: &str) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+
+        let contents = std::fs::read_to_string(path)?;
+        let recording: Self =
+            ron::from_str(&contents).map_err(|error| Error::new(ErrorKind::InvalidData, error))?;
+        if recording.format != GHOST_FORMAT {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unsupported ghost format {}", recording.format),
+            ));
+        }
+        Ok(recording)
+    }
+}
+
+/// Resource holding the current match's recording, which becomes the ghost
+/// once the match is finished
+#[derive(Debug, Resource, Default)]
+struct GhostRecorder(GhostRecording);
+
+/// Loads the ghost saved by an earlier session, if any.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_ghost(mut ghost: ResMut<GhostRecording>) {
+    match GhostRecording::load(GHOST_FILE) {
+        Ok(recording) => *ghost = recording,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => warn!("Can't load ghost from {}: {}", GHOST_FILE, error),
+    }
+}
+
+/// Starts a new recording when a match begins, including when it is
+/// started over with the restart key.
+fn start_ghost_recording(mut recorder: ResMut<GhostRecorder>) {
+    recorder.0 = GhostRecording::default();
+}
+
+/// Notes where the human's paddle is after each physics step.
+fn record_ghost_tick(
+    board: Res<BoardConfig>,
+    config: Res<PaddleConfig>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
+    mut recorder: ResMut<GhostRecorder>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    paddle_query: Query<(&Player, &Transform)>,
+) {
+    let Some(human) = roles.sole_human() else {
+        return;
+    };
+    let Some((ball_transform, ball_velocity)) = ball_query.iter().next() else {
+        return;
+    };
+
+    // Paddles are measured from where they rest, ignoring punches
+    let paddle_x = side.paddle_x(human, &config, &board);
+    let facing = if side.is_left(human) { 1.0 } else { -1.0 };
+    for (player, transform) in paddle_query.iter() {
+        if *player == human {
+            recorder.0.record(
+                paddle_x,
+                facing,
+                ball_transform.translation.truncate(),
+                ball_velocity.linvel,
+                transform.translation.y,
+            );
+        }
+    }
+}
+
+/// Counts the human's returns and misses.
+fn record_ghost_outcomes(
+    roles: Res<PlayerRoles>,
+    mut recorder: ResMut<GhostRecorder>,
+    mut hits: EventReader<PaddleHit>,
+    mut points: EventReader<PointScored>,
+) {
+    let Some(human) = roles.sole_human() else {
+        hits.clear();
+        points.clear();
+        return;
+    };
+
+    for hit in hits.read() {
+        if hit.player == human {
+            recorder.0.returns += 1;
+        }
+    }
+    for point in points.read() {
+        if point.scorer != human {
+            recorder.0.misses += 1;
+        }
+    }
+}
+
+/// Makes the finished match's recording the new ghost, saving it on native
+/// builds.
+fn keep_ghost_recording(mut recorder: ResMut<GhostRecorder>, mut ghost: ResMut<GhostRecording>) {
+    if recorder.0.is_empty() {
+        return;
+    }
+    *ghost = std::mem::take(&mut recorder.0);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Err(error) = ghost.save(GHOST_FILE) {
+        warn!("Failed to save ghost to {}: {}", GHOST_FILE, error);
+    }
+}
+
+/// Plugin that records the human's play for the ghost opponent.
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GhostRecording>()
+            .init_resource::<GhostRecorder>()
+            // A new match starts from the splash screen, the menu or a rematch
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                start_ghost_recording,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                start_ghost_recording,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                start_ghost_recording,
+            )
+            // Or from the restart key, once the frame's restart is done
+            .add_systems(
+                PostUpdate,
+                start_ghost_recording.run_if(on_event::<MatchRestarted>),
+            )
+            .add_systems(
+                FixedUpdate,
+                record_ghost_tick
+                    .after(PhysicsSet::Writeback)
+                    .run_if(in_state(GameState::Playing).and(not_replaying)),
+            )
+            .add_systems(
+                Update,
+                record_ghost_outcomes.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::GameOver), keep_ghost_recording);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Startup, load_ghost);
+    }
+}
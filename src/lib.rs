@@ -0,0 +1,229 @@
+//! Rusty Pong - A Pong clone built with Bevy
+//!
+//! This is the game's library. It sets up the core game systems, manages the
+//! game state, and coordinates all the various plugins that make up the
+//! game's functionality. The `rusty_pong` binary is a thin wrapper around
+//! [`build_app`].
+//!
+//! The game can also be embedded in another Bevy app by adding
+//! [`RustyPongPlugin`] next to Bevy's own plugins, or driven by tests
+//! through the public modules.
+//!
+//! Game Flow:
+//! 1. Starts at splash screen (Splash state)
+//! 2. Press space to begin gameplay straight away (Playing state), or any
+//!    other key for the main menu (Menu state)
+//! 3. From the menu, set up the match and play (Playing state)
+//! 4. Game can be paused at any time (Paused state), and left for the menu
+//! 5. When a player wins, shows victory/defeat screen (GameOver state)
+//! 6. From victory/defeat, can start a new game (returns to Playing state)
+//!    or go back to the menu (returns to Menu state)
+//! 7. From the menu, a saved match can be watched (Replay state)
+//! 8. Online, the host plays as usual while the joining player watches the
+//!    host's match and controls their paddle from afar (Online state)
+
+use bevy::app::{App, Plugin, PluginGroup};
+use bevy::prelude::{AppExtStates, States};
+use bevy::prelude::{IntoSystemConfigs, Update};
+use bevy::DefaultPlugins;
+use bevy_rapier2d::plugin::{NoUserData, RapierPhysicsPlugin};
+
+// Import all our game's plugins
+use crate::assist::AssistPlugin;
+use crate::attract::AttractPlugin;
+use crate::audio::MusicPlugin;
+use crate::ball::BallPlugin;
+use crate::board::{BoardPlugin, PIXELS_PER_METER};
+use crate::camera::CameraPlugin;
+use crate::clutch::ClutchPlugin;
+use crate::endgame::EndgamePlugin;
+use crate::ghost::GhostPlugin;
+use crate::menu::MenuPlugin;
+use crate::mode::GameMode;
+use crate::multiball::MultiBallPlugin;
+use crate::net::NetPlugin;
+use crate::obstacle::ObstaclePlugin;
+use crate::options::{settings_closed, OptionsPlugin};
+use crate::pause::{handle_pause, PausePlugin};
+use crate::playback::PlaybackPlugin;
+use crate::player::PlayerPlugin;
+use crate::powerup::PowerUpPlugin;
+use crate::profile::ProfilePlugin;
+use crate::replay::ReplayPlugin;
+use crate::score::ScorePlugin;
+use crate::serve::ServePlugin;
+use crate::settings::SettingsPlugin;
+use crate::sparks::SparksPlugin;
+use crate::speedometer::SpeedometerPlugin;
+use crate::splash::SplashPlugin;
+use crate::survival::SurvivalPlugin;
+use crate::theme::ThemePlugin;
+use crate::touch::TouchPlugin;
+use crate::wallflash::WallFlashPlugin;
+use crate::window::{default_window_plugin, WindowTitlePlugin};
+
+// Declare all our game's modules
+pub mod assist; // Aim assist marker
+pub mod attract; // AI-vs-AI demo behind the splash screen and menu
+pub mod audio; // Handles background music and sound effects
+pub mod ball; // Ball physics and behavior
+pub mod board; // Game board and walls
+pub mod camera; // Camera setup and configuration
+pub mod clutch; // Dramatic saves slow-motion
+#[cfg(any(debug_assertions, feature = "debug-overlay"))]
+pub mod debug; // F3 developer overlay
+pub mod endgame; // Victory/Defeat screen
+pub mod ghost; // Ghost opponent recorded from the human's play
+pub mod menu; // Main menu
+pub mod mode; // Selectable game modes
+pub mod multiball; // Multi-ball mode extra balls
+pub mod net; // Online play against a remote opponent
+pub mod obstacle; // Arcade mode midfield obstacles
+pub mod options; // Settings screen
+pub mod palette; // Named game colors
+pub mod pause; // Pause menu and state management
+pub mod playback; // Watching recorded matches
+pub mod player; // Player paddles and controls
+pub mod powerup; // Arcade mode power-ups
+pub mod profile; // Lifetime player statistics
+pub mod replay; // Match recording
+pub mod score; // Score tracking and display
+pub mod serve; // Optional manual serves
+pub mod settings; // Settings kept between runs
+pub mod sparks; // Sparks off paddle hits
+pub mod speedometer; // Ball speed readout
+pub mod splash; // Splash screen
+pub mod storage; // Data kept between runs
+pub mod survival; // Survival mode rules and statistics
+pub mod theme; // Selectable color themes
+pub mod touch; // Touch screen controls
+pub mod trajectory; // Ball path prediction
+pub mod wallflash; // Wall flashes on goals and bounces
+pub mod window; // Window configuration
+
+/// Represents the different states the game can be in.
+/// The game's behavior and active systems change based on the current state.
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    Splash, // Initial splash screen, entry point of the game
+    Menu,     // Main menu, where matches are set up and started
+    Playing,  // Active gameplay where players compete
+    Paused,   // Game is temporarily paused, showing pause menu
+    GameOver, // Game has ended with a winner, showing victory/defeat screen
+    Replay,   // Watching a recorded match
+    Online,   // Joined an online game, following the host's match
+}
+
+/// Groups all gameplay-related plugins together for better organization
+/// and easier initialization.
+///
+/// Plugins are added in a specific order to ensure proper initialization:
+/// 1. Board setup (walls and center line)
+/// 2. Obstacles (arcade mode only)
+/// 3. Player systems (paddles and controls)
+/// 4. Camera configuration
+/// 5. Ball physics and behavior
+/// 6. Scoring system
+/// 7. Wall flashes
+/// 8. Hit sparks
+/// 9. Manual serve rule
+/// 10. Touch controls
+/// 11. Aim assist
+/// 12. Dramatic saves
+/// 13. Speedometer
+/// 14. Power-ups (arcade mode only)
+/// 15. Extra balls (multi-ball mode only)
+/// 16. Survival mode rules
+/// 17. Match recording
+/// 18. Ghost opponent recording
+/// 19. Audio features
+pub struct GamePlayPlugins;
+
+impl PluginGroup for GamePlayPlugins {
+    fn build(self) -> bevy::app::PluginGroupBuilder {
+        bevy::app::PluginGroupBuilder::start::<Self>()
+            // Add core gameplay plugins in a logical order
+            .add(BoardPlugin) // First setup the game board
+            .add(ObstaclePlugin) // Midfield obstacles for arcade mode
+            .add(PlayerPlugin) // Then add players
+            .add(CameraPlugin) // Setup the camera to view the game
+            .add(BallPlugin) // Add the ball
+            .add(ScorePlugin) // Add scoring system
+            .add(WallFlashPlugin) // Wall flashes on goals and bounces
+            .add(SparksPlugin) // Sparks off paddle hits
+            .add(ServePlugin) // Manual serve rule
+            .add(TouchPlugin) // Touch screen controls
+            .add(AssistPlugin) // Aim assist marker
+            .add(ClutchPlugin) // Dramatic saves slow-motion
+            .add(SpeedometerPlugin) // Ball speed readout
+            .add(PowerUpPlugin) // Arcade mode power-ups
+            .add(MultiBallPlugin) // Multi-ball mode extra balls
+            .add(SurvivalPlugin) // Survival mode rules
+            .add(ReplayPlugin) // Record matches for playback
+            .add(GhostPlugin) // Record the human for the ghost opponent
+            .add(MusicPlugin) // Finally add audio
+    }
+}
+
+/// Plugin that adds the whole game to an app that already has Bevy's
+/// default plugins.
+///
+/// This covers physics, every screen and the gameplay systems, and starts
+/// the game at the splash screen.
+pub struct RustyPongPlugin;
+
+impl Plugin for RustyPongPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            // Add physics engine with scaling configured for our coordinate system
+            // (see `board::PIXELS_PER_METER` for how this relates to the board),
+            // stepped on the fixed timestep set by `board::PhysicsConfig`
+            RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PIXELS_PER_METER)
+                .in_fixed_schedule(),
+            // The player's saved settings, in place before the plugins below
+            // initialize the same resources
+            SettingsPlugin,
+            // Color themes used by every screen
+            ThemePlugin,
+            // Add our game-specific plugins in order of state flow
+            SplashPlugin,      // Initial splash screen
+            MenuPlugin,        // Main menu
+            OptionsPlugin,     // Settings screen over the menu and pause menu
+            AttractPlugin,     // Demo match behind the splash screen and menu
+            PausePlugin,       // Pause functionality
+            EndgamePlugin,     // Victory/defeat screen
+            PlaybackPlugin,    // Watching recorded matches
+            NetPlugin,         // Online play
+            ProfilePlugin,     // Lifetime statistics
+            GamePlayPlugins,   // Core gameplay systems
+            WindowTitlePlugin, // Score and state in the window title
+        ))
+        // Initialize the game state system
+        .init_state::<GameState>()
+        // Initialize the selected game mode
+        .init_resource::<GameMode>()
+        // Add the pause handling system to run during updates, leaving
+        // space to the settings screen while it is open
+        .add_systems(Update, handle_pause.run_if(settings_closed));
+
+        // Developer overlay, left out of release builds unless the
+        // `debug-overlay` feature is enabled
+        #[cfg(any(debug_assertions, feature = "debug-overlay"))]
+        app.add_plugins(debug::DebugPlugin);
+    }
+}
+
+/// Builds the app for the standalone game: Bevy's default plugins with our
+/// window configuration, and the whole game.
+///
+/// Call `run` on the result to start the game.
+pub fn build_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        // Setup default Bevy plugins with our custom window configuration
+        DefaultPlugins.set(default_window_plugin()),
+        RustyPongPlugin,
+    ));
+    app
+}
@@ -0,0 +1,784 @@
+//! Main Menu Module
+//!
+//! This module handles the main menu, between the splash screen and play,
+//! including:
+//! - Play, Mode / Difficulty, Settings, Controls and (native only) Quit
+//! - Keyboard and mouse navigation with a highlighted selection
+//! - The match setup page: game mode, player side, target score and
+//!   tiebreak, court size, opponent, difficulty and per-player paddle speed
+//!   and size handicaps
+//! - Opening the settings screen
+//! - The controls page
+//! - Lifetime statistics from the player's profile
+//! - Letting the attract demo show through
+//! - Transition to gameplay
+//!
+//! Each page is a list of items. Up/down arrows or the mouse move the
+//! highlight, Enter or a click picks the highlighted item, and left/right
+//! arrows change a setting either way. Space starts a match from any page,
+//! and Escape goes back to the main page.
+//!
+//! The online lobby, the last match's replay and the profile reset are
+//! offered here too, by their own modules.
+
+use crate::attract::AttractConfig;
+use crate::board::{BoardConfig, CourtPreset};
+use crate::ghost::GhostRecording;
+use crate::mode::GameMode;
+use crate::net::lobby_idle;
+use crate::options::{settings_closed, SettingsScreen};
+use crate::palette::{RUST_ORANGE, SPLASH_OVERLAY_ALPHA};
+use crate::player::{Controller, Handicaps, PlayerRoles, PlayerSide, RubberBandConfig};
+use crate::profile::{reset_idle, Profile};
+use crate::score::ScoreRules;
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+
+/// Plugin that manages the main menu.
+///
+/// This plugin coordinates:
+/// - Opening the main page whenever the menu is entered
+/// - Building each page as it is opened
+/// - Input handling for navigation, settings and transitioning to gameplay
+/// - Cleanup when leaving the menu
+pub struct MenuPlugin;
+
+/// Lines shown on the controls page.
+///
+/// Arrow keys are spelled out since the default font has no arrow glyphs.
+const CONTROLS: [&str; 4] = [
+    "W/S or UP/DOWN to move  |  SHIFT to charge  |  E to swing",
+    "SPACE to pause  |  M to toggle music  |  N to toggle sound effects",
+    "In the menu: UP/DOWN to pick  |  LEFT/RIGHT to change  |  ENTER to choose",
+    "SPACE to start straight away  |  L to watch the last match  |  ESC to go back",
+];
+
+/// Hint shown at the bottom of the main page
+const MAIN_HINT: &str =
+    "ENTER to choose  |  SPACE to start straight away  |  L to watch the last match";
+
+/// Hint shown at the bottom of the other pages
+const PAGE_HINT: &str = "UP/DOWN to pick  |  LEFT/RIGHT to change  |  ESC to go back";
+
+/// Color used to highlight the selected item
+const SELECTED_COLOR: Color = RUST_ORANGE;
+
+/// The items on the menu's pages. Also used as a resource tracking the
+/// highlighted item.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum MenuItem {
+    #[default]
+    Play, // Start a match
+    MatchSetup, // Open the mode and difficulty page
+    Settings,   // Open the settings screen
+    Controls,   // Open the controls page
+    Quit,       // Close the game
+    Back,       // Return to the main page
+    Mode,       // Game mode
+    Side,       // Side the human defends
+    Rules,      // Target score
+    Court,      // Board size
+    Opponent,   // The AI or the player's ghost
+    Difficulty, // Standard or adaptive AI
+    P1Speed,    // Player 1's paddle speed handicap
+    P1Size,     // Player 1's paddle size handicap
+    P2Speed,    // Player 2's paddle speed handicap
+    P2Size,     // Player 2's paddle size handicap
+}
+
+/// Items on the main page. A browser tab can't close itself, so the web
+/// build has no Quit.
+#[cfg(not(target_arch = "wasm32"))]
+const MAIN_ITEMS: [MenuItem; 5] = [
+    MenuItem::Play,
+    MenuItem::MatchSetup,
+    MenuItem::Settings,
+    MenuItem::Controls,
+    MenuItem::Quit,
+];
+
+/// Items on the main page. A browser tab can't close itself, so the web
+/// build has no Quit.
+#[cfg(target_arch = "wasm32")]
+const MAIN_ITEMS: [MenuItem; 4] = [
+    MenuItem::Play,
+    MenuItem::MatchSetup,
+    MenuItem::Settings,
+    MenuItem::Controls,
+];
+
+/// Items on the match setup page
+const MATCH_SETUP_ITEMS: [MenuItem; 11] = [
+    MenuItem::Mode,
+    MenuItem::Side,
+    MenuItem::Rules,
+    MenuItem::Court,
+    MenuItem::Opponent,
+    MenuItem::Difficulty,
+    MenuItem::P1Speed,
+    MenuItem::P1Size,
+    MenuItem::P2Speed,
+    MenuItem::P2Size,
+    MenuItem::Back,
+];
+
+/// The menu's pages. Also used as a resource tracking the open page.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum MenuPage {
+    #[default]
+    Main, // Play and the other pages
+    MatchSetup, // Mode, difficulty and the rest of the match setup
+    Controls,   // Keys for play and the menu
+}
+
+impl MenuPage {
+    /// The page's items in display order
+    fn items(&self) -> &'static [MenuItem] {
+        match self {
+            MenuPage::Main => &MAIN_ITEMS,
+            MenuPage::MatchSetup => &MATCH_SETUP_ITEMS,
+            MenuPage::Controls => &[MenuItem::Back],
+        }
+    }
+
+    /// Heading shown at the top of the page
+    fn title(&self) -> &'static str {
+        match self {
+            MenuPage::Main => "RUSTY PONG",
+            MenuPage::MatchSetup => "MODE / DIFFICULTY",
+            MenuPage::Controls => "CONTROLS",
+        }
+    }
+
+    /// The main page's item that opens this page
+    fn opened_by(&self) -> MenuItem {
+        match self {
+            MenuPage::Main => MenuItem::Play,
+            MenuPage::MatchSetup => MenuItem::MatchSetup,
+            MenuPage::Controls => MenuItem::Controls,
+        }
+    }
+
+    /// Returns the page's next item down from `from`, or up if `down` is
+    /// false, wrapping around and skipping the opponent while there is no
+    /// ghost to choose
+    fn step(&self, from: MenuItem, down: bool, ghost: &GhostRecording) -> MenuItem {
+        let items = self.items();
+        let len = items.len();
+        let mut index = items.iter().position(|item| *item == from).unwrap_or(0);
+        loop {
+            index = if down {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            };
+            let item = items[index];
+            if item != MenuItem::Opponent || !ghost.is_empty() {
+                return item;
+            }
+        }
+    }
+}
+
+/// The resources the menu's items show
+type MenuValues<'w> = (
+    Res<'w, GameMode>,
+    Res<'w, PlayerSide>,
+    Res<'w, PlayerRoles>,
+    Res<'w, ScoreRules>,
+    Res<'w, BoardConfig>,
+    Res<'w, GhostRecording>,
+    Res<'w, RubberBandConfig>,
+    Res<'w, Handicaps>,
+);
+
+/// The resources the menu's items change
+type MenuSettings<'w> = (
+    ResMut<'w, GameMode>,
+    ResMut<'w, PlayerSide>,
+    ResMut<'w, PlayerRoles>,
+    ResMut<'w, ScoreRules>,
+    ResMut<'w, BoardConfig>,
+    ResMut<'w, RubberBandConfig>,
+    ResMut<'w, Handicaps>,
+    ResMut<'w, Theme>,
+);
+
+/// Component attached to each item's text
+#[derive(Component)]
+struct MenuItemText(MenuItem);
+
+/// Marker component for identifying menu UI elements.
+/// Used for querying and cleanup when changing pages or leaving the menu.
+#[derive(Component)]
+struct MenuScreen;
+
+/// Marker component for the text showing the player's lifetime statistics.
+#[derive(Component)]
+struct ProfileText;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuPage>()
+            .init_resource::<MenuItem>()
+            // Always open on the main page
+            .add_systems(OnEnter(GameState::Menu), open_main_page)
+            // Handle input and keep the open page up to date while in Menu
+            // state
+            .add_systems(
+                Update,
+                (
+                    // Left alone while an online game is being set up, the
+                    // profile reset is being confirmed or the settings
+                    // screen is open
+                    handle_menu_input.run_if(lobby_idle.and(reset_idle).and(settings_closed)),
+                    spawn_menu_page.run_if(resource_changed::<MenuPage>),
+                    update_item_texts,
+                    update_profile_text,
+                    highlight_selected_item,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Menu)),
+            )
+            // Clean up the menu when leaving Menu state
+            .add_systems(OnExit(GameState::Menu), despawn_menu);
+    }
+}
+
+/// Opens the main page with Play highlighted.
+fn open_main_page(mut page: ResMut<MenuPage>, mut selected: ResMut<MenuItem>) {
+    *page = MenuPage::Main;
+    *selected = MenuItem::Play;
+}
+
+/// Spawns the open page's UI elements, replacing the previous page's.
+///
+/// Creates a full-screen layout containing:
+/// - The page's title
+/// - The page's items, the handicaps in a row of speed and size per player
+/// - The controls, on the controls page
+/// - Lifetime wins, losses and best rally, on the main page
+/// - A hint on the keys for the page
+///
+/// The layout uses flexbox for:
+/// - Vertical stacking of elements
+/// - Center alignment both horizontally and vertically
+/// - Full screen coverage with the theme background, see-through over the
+///   attract demo
+#[allow(clippy::too_many_arguments)]
+fn spawn_menu_page(
+    mut commands: Commands,
+    page: Res<MenuPage>,
+    selected: Res<MenuItem>,
+    values: MenuValues,
+    theme: Res<Theme>,
+    profile: Res<Profile>,
+    attract: Res<AttractConfig>,
+    screen: Query<Entity, With<MenuScreen>>,
+) {
+    for entity in screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let colors = theme.colors();
+    // The attract demo shows through when it plays
+    let background = if attract.enabled {
+        colors.background.with_alpha(SPLASH_OVERLAY_ALPHA)
+    } else {
+        colors.background
+    };
+
+    // Create root container node
+    commands
+        .spawn((
+            // Mark as menu for later cleanup
+            MenuScreen,
+            // Root node layout configuration
+            Node {
+                // Use flexbox layout
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+
+                // Take up full screen
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            // Theme background
+            BackgroundColor(background),
+            Themed::Background,
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            // Page title, largest on the main page
+            let title_size = if *page == MenuPage::Main { 80.0 } else { 60.0 };
+            parent.spawn((
+                Text::new(page.title()),
+                TextFont {
+                    font_size: theme.font_size(title_size),
+                    ..default()
+                },
+                TextColor(colors.text),
+                Themed::Text,
+                Node {
+                    // Add space below the title
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+
+            // Controls, above the way back
+            if *page == MenuPage::Controls {
+                for line in CONTROLS {
+                    parent.spawn((
+                        Text::new(line),
+                        TextFont {
+                            font_size: theme.font_size(24.0),
+                            ..default()
+                        },
+                        TextColor(colors.text),
+                        Themed::Text,
+                        Node {
+                            margin: UiRect::bottom(Val::Px(10.0)),
+                            ..default()
+                        },
+                    ));
+                }
+            }
+
+            for &item in page.items() {
+                match item {
+                    // Spawned in a row with the speed handicap
+                    MenuItem::P1Size | MenuItem::P2Size => {}
+                    MenuItem::P1Speed | MenuItem::P2Speed => {
+                        let size = if item == MenuItem::P1Speed {
+                            MenuItem::P1Size
+                        } else {
+                            MenuItem::P2Size
+                        };
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                column_gap: Val::Px(30.0),
+                                // Add space below the row
+                                margin: UiRect::bottom(Val::Px(10.0)),
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                for item in [item, size] {
+                                    spawn_item(row, item, *selected, &values, &theme, 24.0, 0.0);
+                                }
+                            });
+                    }
+                    // Entries stand out more than settings
+                    _ if *page == MenuPage::Main => {
+                        spawn_item(parent, item, *selected, &values, &theme, 40.0, 10.0)
+                    }
+                    // The way back is set apart from the settings
+                    MenuItem::Back => {
+                        parent
+                            .spawn(Node {
+                                margin: UiRect::top(Val::Px(20.0)),
+                                ..default()
+                            })
+                            .with_children(|back| {
+                                spawn_item(back, item, *selected, &values, &theme, 30.0, 0.0);
+                            });
+                    }
+                    _ => spawn_item(parent, item, *selected, &values, &theme, 30.0, 10.0),
+                }
+            }
+
+            // Lifetime statistics
+            if *page == MenuPage::Main {
+                parent.spawn((
+                    ProfileText,
+                    Text::new(profile.summary()),
+                    TextFont {
+                        font_size: theme.font_size(24.0),
+                        ..default()
+                    },
+                    TextColor(colors.get(Themed::DimText)),
+                    Themed::DimText,
+                    Node {
+                        // Add space above the statistics
+                        margin: UiRect::top(Val::Px(30.0)),
+                        ..default()
+                    },
+                ));
+            }
+
+            // Keys for the page
+            let hint = if *page == MenuPage::Main {
+                MAIN_HINT
+            } else {
+                PAGE_HINT
+            };
+            parent.spawn((
+                Text::new(hint),
+                TextFont {
+                    font_size: theme.font_size(20.0), // Small, secondary text
+                    ..default()
+                },
+                TextColor(colors.get(Themed::DimText)),
+                Themed::DimText,
+                Node {
+                    margin: UiRect::top(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Spawns an item's text, which can be hovered and clicked like a button.
+fn spawn_item(
+    parent: &mut ChildBuilder,
+    item: MenuItem,
+    selected: MenuItem,
+    values: &MenuValues,
+    theme: &Theme,
+    font_size: f32,
+    margin: f32,
+) {
+    let role = item_role(item, &values.5);
+    let color = if item == selected {
+        SELECTED_COLOR
+    } else {
+        theme.colors().get(role)
+    };
+    parent.spawn((
+        MenuItemText(item),
+        Button,
+        Text::new(item_label(item, values)),
+        TextFont {
+            font_size: theme.font_size(font_size),
+            ..default()
+        },
+        TextColor(color),
+        role,
+        Node {
+            margin: UiRect::bottom(Val::Px(margin)),
+            ..default()
+        },
+    ));
+}
+
+/// Formats an item's line.
+fn item_label(item: MenuItem, values: &MenuValues) -> String {
+    let (mode, side, roles, rules, board, ghost, rubber_band, handicaps) = values;
+    match item {
+        MenuItem::Play => "Play".to_string(),
+        MenuItem::MatchSetup => "Mode / Difficulty".to_string(),
+        MenuItem::Settings => "Settings".to_string(),
+        MenuItem::Controls => "Controls".to_string(),
+        MenuItem::Quit => "Quit".to_string(),
+        MenuItem::Back => "Back".to_string(),
+        MenuItem::Mode => mode_label(mode),
+        MenuItem::Side => side_label(side),
+        MenuItem::Rules => rules_label(rules),
+        MenuItem::Court => court_label(board),
+        MenuItem::Opponent => opponent_label(roles, ghost),
+        MenuItem::Difficulty => difficulty_label(rubber_band),
+        MenuItem::P1Speed | MenuItem::P1Size | MenuItem::P2Speed | MenuItem::P2Size => {
+            handicap_label(item, handicaps)
+        }
+    }
+}
+
+/// Theme role of an item's text, greying out the opponent while there is
+/// no ghost.
+fn item_role(item: MenuItem, ghost: &GhostRecording) -> Themed {
+    if item == MenuItem::Opponent && ghost.is_empty() {
+        Themed::DimText
+    } else {
+        Themed::Text
+    }
+}
+
+/// Formats the game mode selection line.
+fn mode_label(mode: &GameMode) -> String {
+    format!("Mode: < {} >", mode.label())
+}
+
+/// Formats the player side selection line.
+fn side_label(side: &PlayerSide) -> String {
+    format!("Side: < {} >  (TAB to switch)", side.label())
+}
+
+/// Formats the scoring rules selection line.
+fn rules_label(rules: &ScoreRules) -> String {
+    format!("Rules: < {} >  (D for deuce)", rules.label())
+}
+
+/// Formats the court size selection line, naming the preset if the board
+/// matches one.
+fn court_label(board: &BoardConfig) -> String {
+    let name = CourtPreset::of(board).map_or("Custom", |preset| preset.name());
+    format!("Court: < {} {}x{} >", name, board.width, board.height)
+}
+
+/// Formats the opponent selection line.
+fn opponent_label(roles: &PlayerRoles, ghost: &GhostRecording) -> String {
+    if ghost.is_empty() {
+        return "Opponent: AI  (finish a match to unlock your ghost)".to_string();
+    }
+    let opponent = match roles.p2 {
+        Controller::Ghost => format!(
+            "Your Ghost, {} returns / {} misses",
+            ghost.returns, ghost.misses
+        ),
+        _ => "AI".to_string(),
+    };
+    format!("Opponent: < {} >  (G to change)", opponent)
+}
+
+/// Formats the difficulty selection line. Adaptive difficulty eases off or
+/// tightens up the AI as the score gap grows.
+fn difficulty_label(rubber_band: &RubberBandConfig) -> String {
+    let difficulty = if rubber_band.enabled {
+        "Adaptive"
+    } else {
+        "Standard"
+    };
+    format!("Difficulty: < {} >", difficulty)
+}
+
+/// Formats a handicap selection.
+fn handicap_label(item: MenuItem, handicaps: &Handicaps) -> String {
+    let (name, multiplier) = match item {
+        MenuItem::P1Speed => ("P1 Speed", handicaps.p1.speed),
+        MenuItem::P1Size => ("P1 Size", handicaps.p1.height),
+        MenuItem::P2Speed => ("P2 Speed", handicaps.p2.speed),
+        _ => ("P2 Size", handicaps.p2.height),
+    };
+    format!("{}: < {:.0}% >", name, multiplier * 100.0)
+}
+
+/// Handles keyboard and mouse input in the menu.
+///
+/// Up/down arrows move the highlight between the open page's items, and
+/// hovering an item highlights it. Enter or a click picks the highlighted
+/// item: Play starts the game, the other entries open their pages, the
+/// settings screen or quit, Back returns to the main page, and a setting
+/// moves on to its next value. Left/right arrows change the highlighted
+/// setting either way.
+///
+/// Each setting also keeps a shortcut: tab switches the side the player
+/// defends, D switches between winning by 2 and sudden death at deuce, G
+/// switches between the AI and the player's ghost once there is one, and T
+/// cycles the color themes. Space starts the game from any page, and
+/// Escape goes back to the main page, or quits from it on native builds.
+#[allow(clippy::too_many_arguments)]
+fn handle_menu_input(
+    keyboard: Res<ButtonInput<KeyCode>>, // Keyboard input resource
+    interactions: Query<(&Interaction, &MenuItemText), Changed<Interaction>>, // Mouse over the items
+    mut settings: MenuSettings,     // Everything the items change
+    ghost: Res<GhostRecording>,     // The player's ghost, if any
+    mut page: ResMut<MenuPage>,     // Open page
+    mut selected: ResMut<MenuItem>, // Highlighted item
+    mut next_state: ResMut<NextState<GameState>>, // For state transitions
+    mut exit: EventWriter<AppExit>, // For quitting
+    mut settings_screen: ResMut<SettingsScreen>, // Settings screen, opened from here
+) {
+    // Hovering highlights an item, and clicking picks it
+    let mut picked = None;
+    for (interaction, item) in interactions.iter() {
+        match interaction {
+            Interaction::Hovered => *selected = item.0,
+            Interaction::Pressed => {
+                *selected = item.0;
+                picked = Some(item.0);
+            }
+            Interaction::None => {}
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        *selected = page.step(*selected, true, &ghost);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        *selected = page.step(*selected, false, &ghost);
+    }
+
+    // Left/right change the highlighted setting
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        change_setting(*selected, true, &mut settings, &ghost);
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        change_setting(*selected, false, &mut settings, &ghost);
+    }
+
+    // Shortcuts
+    if keyboard.just_pressed(KeyCode::Tab) {
+        change_setting(MenuItem::Side, true, &mut settings, &ghost);
+    }
+    if keyboard.just_pressed(KeyCode::KeyD) {
+        let (_, _, _, rules, ..) = &mut settings;
+        rules.tiebreak = rules.tiebreak.toggle();
+    }
+    if keyboard.just_pressed(KeyCode::KeyG) {
+        change_setting(MenuItem::Opponent, true, &mut settings, &ghost);
+    }
+    if keyboard.just_pressed(KeyCode::KeyT) {
+        let (.., theme) = &mut settings;
+        **theme = theme.next();
+    }
+
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        picked = Some(*selected);
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        if *page != MenuPage::Main {
+            picked = Some(MenuItem::Back);
+        } else if page.items().contains(&MenuItem::Quit) {
+            picked = Some(MenuItem::Quit);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Space) {
+        picked = Some(MenuItem::Play);
+    }
+
+    match picked {
+        Some(MenuItem::Play) => next_state.set(GameState::Playing), // Start the game
+        Some(MenuItem::MatchSetup) => open_page(MenuPage::MatchSetup, &mut page, &mut selected),
+        Some(MenuItem::Settings) => settings_screen.open(GameState::Menu),
+        Some(MenuItem::Controls) => open_page(MenuPage::Controls, &mut page, &mut selected),
+        Some(MenuItem::Quit) => {
+            exit.send(AppExit::Success);
+        }
+        Some(MenuItem::Back) => {
+            // Back on the entry that opened the page
+            let entry = page.opened_by();
+            *page = MenuPage::Main;
+            *selected = entry;
+        }
+        Some(item) => change_setting(item, true, &mut settings, &ghost),
+        None => {}
+    }
+}
+
+/// Opens `to`, highlighting its first item.
+fn open_page(to: MenuPage, page: &mut MenuPage, selected: &mut MenuItem) {
+    *page = to;
+    *selected = to.items()[0];
+}
+
+/// Changes a setting to its next value, or previous if `forward` is false.
+/// Items that aren't settings are left alone.
+fn change_setting(
+    item: MenuItem,
+    forward: bool,
+    settings: &mut MenuSettings,
+    ghost: &GhostRecording,
+) {
+    let (mode, side, roles, rules, board, rubber_band, handicaps, _) = settings;
+    match item {
+        MenuItem::Mode if forward => **mode = mode.next(),
+        MenuItem::Mode => **mode = mode.previous(),
+        MenuItem::Side => **side = side.toggle(),
+        MenuItem::Rules if forward => rules.next_target(),
+        MenuItem::Rules => rules.previous_target(),
+        MenuItem::Court => {
+            let preset = CourtPreset::of(board).unwrap_or_default();
+            let preset = if forward {
+                preset.next()
+            } else {
+                preset.previous()
+            };
+            preset.apply(board);
+        }
+        MenuItem::Opponent => toggle_opponent(roles, ghost),
+        MenuItem::Difficulty => rubber_band.enabled = !rubber_band.enabled,
+        MenuItem::P1Speed => handicaps.p1.step_speed(forward),
+        MenuItem::P1Size => handicaps.p1.step_height(forward),
+        MenuItem::P2Speed => handicaps.p2.step_speed(forward),
+        MenuItem::P2Size => handicaps.p2.step_height(forward),
+        MenuItem::Play
+        | MenuItem::MatchSetup
+        | MenuItem::Settings
+        | MenuItem::Controls
+        | MenuItem::Quit
+        | MenuItem::Back => {}
+    }
+}
+
+/// Switches the opponent between the AI and the player's ghost, if there
+/// is one.
+fn toggle_opponent(roles: &mut PlayerRoles, ghost: &GhostRecording) {
+    if ghost.is_empty() {
+        return;
+    }
+    roles.p2 = match roles.p2 {
+        Controller::Ghost => Controller::Ai,
+        _ => Controller::Ghost,
+    };
+}
+
+/// Keeps the items' texts in sync with the settings they show, and the
+/// opponent line's theme role with whether a ghost is available.
+fn update_item_texts(
+    values: MenuValues,
+    mut query: Query<(&MenuItemText, &mut Text, &mut Themed)>,
+) {
+    let (mode, side, roles, rules, board, ghost, rubber_band, handicaps) = &values;
+    let changed = mode.is_changed()
+        || side.is_changed()
+        || roles.is_changed()
+        || rules.is_changed()
+        || board.is_changed()
+        || ghost.is_changed()
+        || rubber_band.is_changed()
+        || handicaps.is_changed();
+    if !changed {
+        return;
+    }
+    for (item, mut text, mut themed) in query.iter_mut() {
+        **text = item_label(item.0, &values);
+        *themed = item_role(item.0, ghost);
+    }
+}
+
+/// Keeps the statistics text in sync with the profile, such as after a
+/// reset.
+fn update_profile_text(profile: Res<Profile>, mut query: Query<&mut Text, With<ProfileText>>) {
+    if !profile.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        **text = profile.summary();
+    }
+}
+
+/// Highlights the selected item, leaving the others in their theme colors.
+///
+/// Runs every frame, since theme changes and the opponent line's
+/// availability recolor the text too.
+fn highlight_selected_item(
+    selected: Res<MenuItem>,
+    theme: Res<Theme>,
+    mut query: Query<(&MenuItemText, &Themed, &mut TextColor)>,
+) {
+    let colors = theme.colors();
+    for (item, role, mut color) in query.iter_mut() {
+        let wanted = if item.0 == *selected {
+            SELECTED_COLOR
+        } else {
+            colors.get(*role)
+        };
+        if color.0 != wanted {
+            color.0 = wanted;
+        }
+    }
+}
+
+/// Cleans up the menu's entities when leaving the menu.
+///
+/// Queries for all entities marked with the MenuScreen component
+/// and recursively despawns them and their children.
+fn despawn_menu(mut commands: Commands, screen: Query<Entity, With<MenuScreen>>) {
+    for entity in screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
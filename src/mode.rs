@@ -0,0 +1,54 @@
+//! Game Mode Module
+//!
+//! This module defines the selectable game modes. The mode is chosen in the
+//! menu and stored in a resource so gameplay plugins can enable or disable
+//! their features based on it.
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+
+/// The ruleset variant the next game will be played with.
+///
+/// Gameplay plugins read this resource to decide which optional features
+/// to spawn, for example the midfield obstacles in arcade mode, or to skip
+/// the AI opponent entirely in survival mode.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Classic, // Traditional pong with nothing between the paddles
+    Arcade,    // Adds moving obstacles in the midfield
+    MultiBall, // Extra balls join each rally
+    Survival,  // Solo defense against an accelerating ball
+}
+
+impl GameMode {
+    /// Human readable name of the mode, used by the menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Arcade => "Arcade",
+            GameMode::MultiBall => "Multi-ball",
+            GameMode::Survival => "Survival",
+        }
+    }
+
+    /// Returns the mode after this one, wrapping around at the end
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Classic => GameMode::Arcade,
+            GameMode::Arcade => GameMode::MultiBall,
+            GameMode::MultiBall => GameMode::Survival,
+            GameMode::Survival => GameMode::Classic,
+        }
+    }
+
+    /// Returns the mode before this one, wrapping around at the start
+    pub fn previous(&self) -> Self {
+        match self {
+            GameMode::Classic => GameMode::Survival,
+            GameMode::Arcade => GameMode::Classic,
+            GameMode::MultiBall => GameMode::Arcade,
+            GameMode::Survival => GameMode::MultiBall,
+        }
+    }
+}
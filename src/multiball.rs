@@ -0,0 +1,140 @@
+//! Multi-Ball Module
+//!
+//! This module implements the multi-ball game mode, where extra balls join
+//! each rally, including:
+//! - Putting a new ball into play at a steady interval during a rally
+//! - Capping how many balls can be in play at once
+//! - Restarting the interval with every serve
+//!
+//! Every ball scores when it reaches a scoring wall, and the next serve only
+//! comes once the last ball has left play, see the score module's
+//! `handle_scoring`. The cap keeps the chaos bounded, and the number of
+//! colliders the physics has to step predictable.
+
+use crate::ball::{create_ball, despawn_balls, Ball, BallAssets, BallConfig};
+use crate::mode::GameMode;
+use crate::replay::not_replaying;
+use crate::score::{Score, ServeStarted};
+use crate::serve::BallHeld;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+/// Largest angle off horizontal an extra ball can set off at, in radians
+const MAX_LAUNCH_ANGLE: f32 = 0.5;
+
+/// Configuration for multi-ball mode
+#[derive(Debug, Resource)]
+pub struct MultiBallConfig {
+    /// Most balls in play at once, the served ball included
+    pub max_balls: usize,
+    /// Time between extra balls during a rally, in seconds
+    pub spawn_interval: f32,
+}
+
+impl Default for MultiBallConfig {
+    fn default() -> Self {
+        Self {
+            max_balls: 3,        // Busy, but still possible to follow
+            spawn_interval: 5.0, // A couple of returns before the next one
+        }
+    }
+}
+
+/// Resource timing the next extra ball
+#[derive(Debug, Resource)]
+struct MultiBallSpawner {
+    /// Time until the next extra ball
+    timer: Timer,
+}
+
+impl MultiBallSpawner {
+    /// A spawner whose first ball comes a full interval from now
+    fn new(config: &MultiBallConfig) -> Self {
+        Self {
+            timer: Timer::from_seconds(config.spawn_interval, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Plugin that adds extra balls in multi-ball mode.
+pub struct MultiBallPlugin;
+
+impl Plugin for MultiBallPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MultiBallConfig>();
+        let spawner = MultiBallSpawner::new(app.world().resource::<MultiBallConfig>());
+
+        app.insert_resource(spawner)
+            // After the scored balls are gone, so a rally that has just
+            // ended doesn't get another ball
+            .add_systems(
+                Update,
+                (restart_spawner_on_serve, spawn_extra_balls)
+                    .chain()
+                    .after(despawn_balls)
+                    .run_if(
+                        in_state(GameState::Playing)
+                            .and(resource_equals(GameMode::MultiBall))
+                            .and(not_replaying),
+                    ),
+            );
+    }
+}
+
+/// Restarts the interval when a new rally is served, so every rally gets
+/// its first extra ball at the same point.
+fn restart_spawner_on_serve(
+    config: Res<MultiBallConfig>,
+    mut spawner: ResMut<MultiBallSpawner>,
+    mut serves: EventReader<ServeStarted>,
+) {
+    if serves.read().count() > 0 {
+        *spawner = MultiBallSpawner::new(&config);
+    }
+}
+
+/// Puts an extra ball into play from the center once the interval has
+/// passed, as long as a rally is underway and there's room under the cap.
+#[allow(clippy::too_many_arguments)]
+fn spawn_extra_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<MultiBallConfig>,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    score: Res<Score>,
+    mut spawner: ResMut<MultiBallSpawner>,
+    ball_query: Query<Has<BallHeld>, With<Ball>>,
+) {
+    // Only during a rally: a ball is in play, none is waiting on its
+    // server, and the last one hasn't just left
+    let live = ball_query.iter().count();
+    if live == 0 || score.should_serve || ball_query.iter().any(|held| held) {
+        return;
+    }
+
+    // The interval can be changed during a match
+    let interval = Duration::from_secs_f32(config.spawn_interval);
+    if spawner.timer.duration() != interval {
+        spawner.timer.set_duration(interval);
+    }
+
+    if !spawner.timer.tick(time.delta()).just_finished() || live >= config.max_balls {
+        return;
+    }
+
+    let to_right = rand::random::<bool>();
+    let ball = create_ball(
+        &mut commands,
+        &ball_assets,
+        &ball_config,
+        to_right,
+        Vec2::ZERO,
+    );
+    let angle = (rand::random::<f32>() * 2.0 - 1.0) * MAX_LAUNCH_ANGLE;
+    let direction = if to_right { Vec2::X } else { Vec2::NEG_X };
+    let velocity = Vec2::from_angle(angle).rotate(direction) * ball_config.min_velocity;
+    commands.entity(ball).insert(Velocity::linear(velocity));
+}
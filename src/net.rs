@@ -0,0 +1,818 @@
+//! Online Play Module
+//!
+//! This module lets two players on different machines play each other
+//! through a WebSocket relay, including:
+//! - Hosting or joining a game from the main menu with a short code
+//! - Streaming the host's ball, paddles and score to the joining player
+//! - Sending the joining player's paddle and pause keys back to the host
+//! - Returning both players to the menu when either one leaves
+//!
+//! The host runs the match as usual and its physics is the only authority.
+//! The joining player, or guest, takes the AI's place as `Player::P2`. The
+//! guest doesn't simulate anything: it shows the streamed frames with the
+//! playback module's ghost ball and paddles, slightly behind the latest
+//! frame so it can interpolate between them.
+//!
+//! The relay pairs the two connections that use the same game code in their
+//! URL and forwards every text message between them. Messages are RON, like
+//! replay files. Only the ball and paddles are streamed, so online matches
+//! are always played in classic mode.
+
+use crate::ball::Ball;
+use crate::mode::GameMode;
+use crate::options::settings_closed;
+use crate::playback::ReplayPlayback;
+use crate::player::{Controller, Player, PlayerRoles, RemoteInput};
+use crate::profile::reset_idle;
+use crate::replay::Snapshot;
+use crate::score::{MatchTimer, Score, ScoreRules};
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Number of digits in a game code
+const CODE_LENGTH: usize = 4;
+
+/// Keys typed for each digit of a game code, from 0 to 9
+const DIGIT_KEYS: [(KeyCode, KeyCode); 10] = [
+    (KeyCode::Digit0, KeyCode::Numpad0),
+    (KeyCode::Digit1, KeyCode::Numpad1),
+    (KeyCode::Digit2, KeyCode::Numpad2),
+    (KeyCode::Digit3, KeyCode::Numpad3),
+    (KeyCode::Digit4, KeyCode::Numpad4),
+    (KeyCode::Digit5, KeyCode::Numpad5),
+    (KeyCode::Digit6, KeyCode::Numpad6),
+    (KeyCode::Digit7, KeyCode::Numpad7),
+    (KeyCode::Digit8, KeyCode::Numpad8),
+    (KeyCode::Digit9, KeyCode::Numpad9),
+];
+
+/// Seconds of streamed frames the guest keeps
+const LIVE_BUFFER: f32 = 2.0;
+
+/// Configuration for online play
+#[derive(Debug, Resource)]
+pub struct NetConfig {
+    /// Address of the relay server; the game code is added as the path
+    pub relay_url: String,
+    /// State updates sent to the guest per second
+    pub send_rate: f32,
+    /// How far behind the latest streamed frame the guest is shown, in seconds
+    pub interpolation_delay: f32,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            // A relay running on the same machine, for trying it out
+            relay_url: "ws://localhost:9001".into(),
+            // Half the physics rate, smoothed over by interpolation
+            send_rate: 30.0,
+            // About three updates, so a late one doesn't stall the ball
+            interpolation_delay: 0.1,
+        }
+    }
+}
+
+/// Which end of an online game this player is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NetRole {
+    Host,  // Runs the match
+    Guest, // Watches the host's match and sends paddle input
+}
+
+/// Progress through hosting or joining an online game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum LobbyStage {
+    #[default]
+    Idle, // Not online
+    EnteringCode, // The guest is typing the game code
+    Waiting,      // Connecting, or waiting for the other player
+    Connected,    // Playing the other player
+}
+
+/// Resource tracking the online game, if any
+#[derive(Debug, Resource, Default)]
+pub struct NetSession {
+    /// Whether this player hosts or joins, once chosen
+    role: Option<NetRole>,
+    /// Progress through the lobby
+    stage: LobbyStage,
+    /// The game code, as far as it has been typed
+    code: String,
+    /// Message shown in the menu after a game ends unexpectedly
+    notice: Option<String>,
+    /// The host's game mode from before the online match, put back once
+    /// the match ends
+    host_mode: Option<GameMode>,
+}
+
+/// Messages exchanged between host and guest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum NetMessage {
+    /// Guest to host: joined the game
+    Hello,
+    /// Host to guest: the match is starting
+    Welcome,
+    /// Guest to host: the paddle keys being held
+    Input { up: bool, down: bool },
+    /// Guest to host: toggle pause
+    Pause,
+    /// Host to guest: the latest frame and score, and whether play is running
+    State {
+        frame: Snapshot,
+        p1: u32,
+        p2: u32,
+        playing: bool,
+    },
+    /// Either way: leaving the game
+    Bye,
+}
+
+/// Sent for each message received from the other player. A lost connection
+/// arrives as [`NetMessage::Bye`].
+#[derive(Event, Debug)]
+struct NetReceived(NetMessage);
+
+/// Something that happened on a connection
+enum Incoming {
+    Text(String), // A message from the other player
+    Closed,       // The connection has ended
+}
+
+/// WebSocket connection on native builds, run on its own thread so the
+/// game never waits on the network.
+#[cfg(not(target_arch = "wasm32"))]
+mod transport {
+    use super::Incoming;
+    use std::io::ErrorKind;
+    use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+    use std::thread;
+    use std::time::Duration;
+    use tungstenite::stream::MaybeTlsStream;
+    use tungstenite::Message;
+
+    /// How long the connection thread sleeps when there's nothing to do
+    const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+    /// An open connection to the relay
+    pub struct Connection {
+        /// Messages for the thread to send
+        outgoing: Sender<String>,
+        /// Messages and closure reported by the thread
+        incoming: Receiver<Incoming>,
+        /// Whether the closure has already been reported
+        closed: bool,
+    }
+
+    impl Connection {
+        /// Starts connecting to `url`. Messages sent before the connection
+        /// is up are queued.
+        pub fn open(url: &str) -> Self {
+            let (outgoing, outgoing_rx) = mpsc::channel();
+            let (incoming_tx, incoming) = mpsc::channel();
+            let url = url.to_string();
+            thread::spawn(move || run(url, outgoing_rx, incoming_tx));
+            Self {
+                outgoing,
+                incoming,
+                closed: false,
+            }
+        }
+
+        /// Queues a message for sending
+        pub fn send(&self, text: String) {
+            // A closed connection is reported by `poll`
+            let _ = self.outgoing.send(text);
+        }
+
+        /// Returns everything received since the last poll
+        pub fn poll(&mut self) -> Vec<Incoming> {
+            let mut received = Vec::new();
+            loop {
+                match self.incoming.try_recv() {
+                    Ok(incoming) => received.push(incoming),
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        if !self.closed {
+                            self.closed = true;
+                            received.push(Incoming::Closed);
+                        }
+                        break;
+                    }
+                }
+            }
+            received
+        }
+    }
+
+    /// Connects and then relays messages until either side hangs up.
+    /// Dropping the [`Connection`] closes the socket.
+    fn run(url: String, outgoing: Receiver<String>, incoming: Sender<Incoming>) {
+        let Ok((mut socket, _)) = tungstenite::connect(url.as_str()) else {
+            return;
+        };
+        // Reads must not block, so queued messages still go out promptly
+        if let MaybeTlsStream::Plain(stream) = socket.get_mut() {
+            if stream.set_nonblocking(true).is_err() {
+                return;
+            }
+        }
+
+        loop {
+            loop {
+                match outgoing.try_recv() {
+                    Ok(text) => match socket.send(Message::Text(text)) {
+                        Ok(()) => {}
+                        // Left buffered, and flushed by the next read
+                        Err(tungstenite::Error::Io(error))
+                            if error.kind() == ErrorKind::WouldBlock => {}
+                        Err(_) => return,
+                    },
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let _ = socket.close(None);
+                        let _ = socket.flush();
+                        return;
+                    }
+                }
+            }
+
+            match socket.read() {
+                Ok(Message::Text(text)) => {
+                    if incoming.send(Incoming::Text(text)).is_err() {
+                        return;
+                    }
+                }
+                Ok(Message::Close(_)) => return,
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(error)) if error.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(POLL_INTERVAL);
+                }
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+/// WebSocket connection in the browser, using its own socket and event loop.
+#[cfg(target_arch = "wasm32")]
+mod transport {
+    use super::Incoming;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{MessageEvent, WebSocket};
+
+    /// An open connection to the relay
+    pub struct Connection {
+        /// The browser's socket, `None` if it couldn't be created
+        socket: Option<WebSocket>,
+        /// Messages waiting for the socket to open
+        pending: RefCell<Vec<String>>,
+        /// Messages and closure reported by the socket's callbacks
+        incoming: Rc<RefCell<VecDeque<Incoming>>>,
+        /// Callbacks kept alive for as long as the socket
+        _on_message: Closure<dyn FnMut(MessageEvent)>,
+        _on_close: Closure<dyn FnMut(JsValue)>,
+    }
+
+    impl Connection {
+        /// Starts connecting to `url`. Messages sent before the connection
+        /// is up are queued.
+        pub fn open(url: &str) -> Self {
+            let incoming = Rc::new(RefCell::new(VecDeque::new()));
+
+            let queue = incoming.clone();
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                if let Some(text) = event.data().as_string() {
+                    queue.borrow_mut().push_back(Incoming::Text(text));
+                }
+            });
+            let queue = incoming.clone();
+            let on_close = Closure::<dyn FnMut(JsValue)>::new(move |_| {
+                queue.borrow_mut().push_back(Incoming::Closed);
+            });
+
+            let socket = WebSocket::new(url).ok();
+            match &socket {
+                Some(socket) => {
+                    socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+                    socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+                }
+                None => incoming.borrow_mut().push_back(Incoming::Closed),
+            }
+
+            Self {
+                socket,
+                pending: RefCell::new(Vec::new()),
+                incoming,
+                _on_message: on_message,
+                _on_close: on_close,
+            }
+        }
+
+        /// Queues a message for sending
+        pub fn send(&self, text: String) {
+            self.pending.borrow_mut().push(text);
+            self.flush();
+        }
+
+        /// Returns everything received since the last poll
+        pub fn poll(&mut self) -> Vec<Incoming> {
+            self.flush();
+            self.incoming.borrow_mut().drain(..).collect()
+        }
+
+        /// Sends the queued messages once the socket is open
+        fn flush(&self) {
+            let Some(socket) = &self.socket else {
+                return;
+            };
+            if socket.ready_state() != WebSocket::OPEN {
+                return;
+            }
+            for text in self.pending.borrow_mut().drain(..) {
+                // A failed send closes the socket, which is reported by `poll`
+                let _ = socket.send_with_str(&text);
+            }
+        }
+    }
+
+    impl Drop for Connection {
+        fn drop(&mut self) {
+            if let Some(socket) = &self.socket {
+                socket.set_onmessage(None);
+                socket.set_onclose(None);
+                let _ = socket.close();
+            }
+        }
+    }
+}
+
+/// Non-send resource holding the connection to the relay, if open. The
+/// browser's socket can't leave the main thread.
+#[derive(Default)]
+struct NetConnection(Option<transport::Connection>);
+
+impl NetConnection {
+    /// Connects to the relay for the game `code`, replacing any open connection
+    fn open(&mut self, config: &NetConfig, code: &str) {
+        let url = format!("{}/{}", config.relay_url.trim_end_matches('/'), code);
+        self.0 = Some(transport::Connection::open(&url));
+    }
+
+    /// Sends a message to the other player, if connected
+    fn send(&self, message: &NetMessage) {
+        let Some(connection) = &self.0 else {
+            return;
+        };
+        match ron::to_string(message) {
+            Ok(text) => connection.send(text),
+            Err(error) => warn!("Failed to encode {:?}: {}", message, error),
+        }
+    }
+
+    /// Closes the connection
+    fn close(&mut self) {
+        self.0 = None;
+    }
+}
+
+/// Marker component for the lobby text in the menu
+#[derive(Component)]
+struct LobbyText;
+
+/// Run condition that passes while no online game is being set up or
+/// played. The menu's own keys use this to stay out of the way
+/// of a game code being typed.
+pub fn lobby_idle(session: Res<NetSession>) -> bool {
+    session.stage == LobbyStage::Idle
+}
+
+/// Run condition that passes while hosting an online game
+fn hosting(session: Res<NetSession>) -> bool {
+    session.role == Some(NetRole::Host)
+}
+
+/// Run condition that passes while joining or playing someone else's game
+fn joining(session: Res<NetSession>) -> bool {
+    session.role == Some(NetRole::Guest)
+}
+
+/// Run condition that passes while playing against a guest
+fn hosting_match(session: Res<NetSession>) -> bool {
+    session.role == Some(NetRole::Host) && session.stage == LobbyStage::Connected
+}
+
+/// Returns the digit typed with `key`, if any
+fn typed_digit(key: &KeyCode) -> Option<char> {
+    DIGIT_KEYS
+        .iter()
+        .position(|(digit, numpad)| key == digit || key == numpad)
+        .and_then(|index| char::from_digit(index as u32, 10))
+}
+
+/// Handles hosting and joining in the menu.
+///
+/// H hosts a game under a new random code, and J starts typing the code of
+/// a game to join, which Enter submits. Escape backs out of either.
+///
+/// Runs after the frame's other systems, so the menu's keys, Escape
+/// included, see the lobby as it was when the key was pressed.
+fn handle_lobby_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: Res<NetConfig>,
+    mut session: ResMut<NetSession>,
+    mut connection: NonSendMut<NetConnection>,
+) {
+    match session.stage {
+        LobbyStage::Idle => {
+            if keyboard.just_pressed(KeyCode::KeyH) {
+                let code: String = (0..CODE_LENGTH)
+                    .filter_map(|_| char::from_digit(rand::random::<u32>() % 10, 10))
+                    .collect();
+                connection.open(&config, &code);
+                *session = NetSession {
+                    role: Some(NetRole::Host),
+                    stage: LobbyStage::Waiting,
+                    code,
+                    notice: None,
+                    host_mode: None,
+                };
+            } else if keyboard.just_pressed(KeyCode::KeyJ) {
+                *session = NetSession {
+                    role: Some(NetRole::Guest),
+                    stage: LobbyStage::EnteringCode,
+                    ..default()
+                };
+            }
+        }
+        LobbyStage::EnteringCode => {
+            for key in keyboard.get_just_pressed() {
+                if let Some(digit) = typed_digit(key) {
+                    if session.code.len() < CODE_LENGTH {
+                        session.code.push(digit);
+                    }
+                }
+            }
+            if keyboard.just_pressed(KeyCode::Backspace) {
+                session.code.pop();
+            }
+            if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+                && session.code.len() == CODE_LENGTH
+            {
+                connection.open(&config, &session.code);
+                connection.send(&NetMessage::Hello);
+                session.stage = LobbyStage::Waiting;
+            }
+            if keyboard.just_pressed(KeyCode::Escape) {
+                *session = NetSession::default();
+            }
+        }
+        LobbyStage::Waiting => {
+            if keyboard.just_pressed(KeyCode::Escape) {
+                connection.close();
+                *session = NetSession::default();
+            }
+        }
+        LobbyStage::Connected => {}
+    }
+}
+
+/// Reads everything received from the other player.
+fn poll_connection(
+    mut connection: NonSendMut<NetConnection>,
+    mut received: EventWriter<NetReceived>,
+) {
+    let Some(open) = connection.0.as_mut() else {
+        return;
+    };
+
+    for incoming in open.poll() {
+        match incoming {
+            Incoming::Text(text) => match ron::from_str(&text) {
+                Ok(message) => {
+                    received.send(NetReceived(message));
+                }
+                Err(error) => warn!("Ignoring unreadable online message: {}", error),
+            },
+            Incoming::Closed => {
+                received.send(NetReceived(NetMessage::Bye));
+            }
+        }
+    }
+}
+
+/// Starts the match when a guest joins, and applies the guest's input.
+///
+/// The guest replaces the AI opponent. Online matches are always classic,
+/// since obstacles, power-ups and the survival wall aren't streamed, so the
+/// host's own mode is kept in the session until the match ends.
+#[allow(clippy::too_many_arguments)]
+fn handle_host_messages(
+    mut received: EventReader<NetReceived>,
+    connection: NonSend<NetConnection>,
+    mut session: ResMut<NetSession>,
+    mut roles: ResMut<PlayerRoles>,
+    mut mode: ResMut<GameMode>,
+    mut remote_input: ResMut<RemoteInput>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for NetReceived(message) in received.read() {
+        match message {
+            NetMessage::Hello if session.stage == LobbyStage::Waiting => {
+                connection.send(&NetMessage::Welcome);
+                roles.p2 = Controller::Remote;
+                session.host_mode = Some(*mode);
+                *mode = GameMode::Classic;
+                *remote_input = RemoteInput::default();
+                session.stage = LobbyStage::Connected;
+                next_state.set(GameState::Playing);
+            }
+            NetMessage::Input { up, down } => {
+                *remote_input = RemoteInput {
+                    up: *up,
+                    down: *down,
+                };
+            }
+            NetMessage::Pause => match state.get() {
+                GameState::Playing => next_state.set(GameState::Paused),
+                GameState::Paused => next_state.set(GameState::Playing),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Joins the host's match when welcomed, and feeds its frames into the
+/// guest's view.
+fn handle_guest_messages(
+    mut received: EventReader<NetReceived>,
+    mut session: ResMut<NetSession>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for NetReceived(message) in received.read() {
+        match message {
+            NetMessage::Welcome if session.stage == LobbyStage::Waiting => {
+                session.stage = LobbyStage::Connected;
+                *playback = ReplayPlayback {
+                    playing: true,
+                    live: true,
+                    ..default()
+                };
+                next_state.set(GameState::Online);
+            }
+            NetMessage::State {
+                frame,
+                p1,
+                p2,
+                playing,
+            } if session.stage == LobbyStage::Connected => {
+                playback.recording.push_frame(*frame, LIVE_BUFFER);
+                playback.recording.record_point(*p1, *p2);
+                playback.playing = *playing;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns to the menu with a message when the other player leaves
+/// or the connection is lost.
+fn handle_disconnect(
+    mut received: EventReader<NetReceived>,
+    mut session: ResMut<NetSession>,
+    mut connection: NonSendMut<NetConnection>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let left = received
+        .read()
+        .any(|NetReceived(message)| matches!(message, NetMessage::Bye));
+    if !left {
+        return;
+    }
+
+    let notice = match (session.stage, session.role) {
+        (LobbyStage::Idle | LobbyStage::EnteringCode, _) => return,
+        (LobbyStage::Waiting, _) => format!("Couldn't connect to game {}", session.code),
+        (LobbyStage::Connected, Some(NetRole::Host)) => "Your opponent left the game".into(),
+        (LobbyStage::Connected, _) => "The host left the game".into(),
+    };
+    warn!("{}", notice);
+
+    connection.close();
+    *session = NetSession {
+        notice: Some(notice),
+        host_mode: session.host_mode,
+        ..default()
+    };
+    if *state.get() != GameState::Menu {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// Ends any online game on returning to the menu.
+///
+/// The other player is told, and the AI opponent, the host's own game mode
+/// and a fresh score are restored for the next local game.
+#[allow(clippy::too_many_arguments)]
+fn leave_game(
+    mut session: ResMut<NetSession>,
+    mut connection: NonSendMut<NetConnection>,
+    mut roles: ResMut<PlayerRoles>,
+    mut mode: ResMut<GameMode>,
+    mut score: ResMut<Score>,
+    rules: Res<ScoreRules>,
+    mut timer: ResMut<MatchTimer>,
+) {
+    if let Some(host_mode) = session.host_mode.take() {
+        *mode = host_mode;
+    }
+    if session.stage == LobbyStage::Connected {
+        connection.send(&NetMessage::Bye);
+        connection.close();
+        *session = NetSession::default();
+    }
+
+    if roles.p2 == Controller::Remote {
+        roles.p2 = Controller::Ai;
+        score.reset(&rules);
+        timer.reset();
+    }
+}
+
+/// Streams the ball, paddles and score to the guest at the configured rate.
+///
+/// Frames are stamped with the host's clock, which the guest only uses to
+/// space them out.
+#[allow(clippy::too_many_arguments)]
+fn send_host_state(
+    time: Res<Time>,
+    config: Res<NetConfig>,
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    connection: NonSend<NetConnection>,
+    mut since_sent: Local<f32>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    paddle_query: Query<(&Player, &Transform)>,
+) {
+    *since_sent += time.delta_secs();
+    if *since_sent < 1.0 / config.send_rate {
+        return;
+    }
+    *since_sent = 0.0;
+
+    let frame = Snapshot {
+        time: time.elapsed_secs(),
+        ..Snapshot::capture(ball_query.iter().next(), paddle_query.iter())
+    };
+    connection.send(&NetMessage::State {
+        frame,
+        p1: score.p1,
+        p2: score.p2,
+        playing: *state.get() == GameState::Playing,
+    });
+}
+
+/// Sends the guest's paddle keys whenever they change, and handles pausing
+/// and leaving.
+fn send_guest_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    connection: NonSend<NetConnection>,
+    mut held: Local<(bool, bool)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let up = keyboard.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]);
+    let down = keyboard.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]);
+    if (up, down) != *held {
+        *held = (up, down);
+        connection.send(&NetMessage::Input { up, down });
+    }
+
+    if keyboard.just_pressed(KeyCode::Space) {
+        connection.send(&NetMessage::Pause);
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// Keeps the guest's view just behind the latest streamed frame, so there
+/// is usually a later frame to interpolate toward.
+fn follow_host(config: Res<NetConfig>, mut playback: ResMut<ReplayPlayback>) {
+    playback.position = (playback.recording.duration() - config.interpolation_delay).max(0.0);
+}
+
+/// Spawns the lobby text along the bottom of the menu.
+fn spawn_lobby_text(mut commands: Commands, theme: Res<Theme>) {
+    commands.spawn((
+        LobbyText,
+        Themed::Text,
+        Text::default(),
+        TextFont {
+            font_size: theme.font_size(20.0),
+            ..default()
+        },
+        TextColor(theme.colors().text),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(20.0),
+            ..default()
+        },
+        // Drawn over the menu's background
+        GlobalZIndex(1),
+    ));
+}
+
+/// Describes the lobby's progress, or how to go online
+fn lobby_message(session: &NetSession) -> String {
+    match (session.stage, session.role) {
+        (LobbyStage::EnteringCode, _) => format!(
+            "Enter game code: {:_<width$}  |  ENTER to join  |  ESC to cancel",
+            session.code,
+            width = CODE_LENGTH
+        ),
+        (LobbyStage::Waiting, Some(NetRole::Host)) => format!(
+            "Hosting game {}, waiting for an opponent  |  ESC to cancel",
+            session.code
+        ),
+        (LobbyStage::Waiting, _) => format!("Joining game {}...  |  ESC to cancel", session.code),
+        _ => match &session.notice {
+            Some(notice) => format!("{}  |  H to host online  |  J to join", notice),
+            None => "H to host an online game  |  J to join one".to_string(),
+        },
+    }
+}
+
+/// Keeps the lobby text in sync with the session.
+fn update_lobby_text(session: Res<NetSession>, mut query: Query<&mut Text, With<LobbyText>>) {
+    for mut text in query.iter_mut() {
+        **text = lobby_message(&session);
+    }
+}
+
+/// Removes the lobby text when leaving the menu.
+fn despawn_lobby_text(mut commands: Commands, query: Query<Entity, With<LobbyText>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Plugin that lets two players play each other online.
+pub struct NetPlugin;
+
+impl Plugin for NetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NetConfig>()
+            .init_resource::<NetSession>()
+            .init_non_send_resource::<NetConnection>()
+            .add_event::<NetReceived>()
+            // Lobby in the menu
+            .add_systems(
+                OnEnter(GameState::Menu),
+                (leave_game, spawn_lobby_text, update_lobby_text).chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                handle_lobby_input.run_if(
+                    in_state(GameState::Menu)
+                        .and(reset_idle)
+                        .and(settings_closed),
+                ),
+            )
+            .add_systems(
+                Update,
+                update_lobby_text
+                    .run_if(in_state(GameState::Menu).and(resource_changed::<NetSession>)),
+            )
+            .add_systems(OnExit(GameState::Menu), despawn_lobby_text)
+            // Messages are handled in every state, so a lost connection is
+            // noticed wherever the players are
+            .add_systems(
+                Update,
+                (
+                    poll_connection,
+                    handle_host_messages.run_if(hosting),
+                    handle_guest_messages.run_if(joining),
+                    handle_disconnect,
+                )
+                    .chain(),
+            )
+            .add_systems(Update, send_host_state.run_if(hosting_match))
+            .add_systems(
+                Update,
+                (send_guest_input, follow_host).run_if(in_state(GameState::Online)),
+            );
+    }
+}
@@ -0,0 +1,173 @@
+//! Obstacle Module
+//!
+//! This module implements the midfield obstacles used by the arcade game mode.
+//! Obstacles are small rectangular blocks that drift up and down near the
+//! center line and deflect the ball, including:
+//!
+//! - Obstacle configuration (size, speed, placement)
+//! - Spawning kinematic rigid bodies when arcade mode is selected
+//! - Sine-driven vertical oscillation
+//! - Cleanup when leaving gameplay
+//!
+//! The AI's intersection prediction does not account for obstacles, so it
+//! can be fooled by a deflection. That is intentional and part of the
+//! arcade challenge.
+
+use crate::board::{board_collision_groups, BoardConfig};
+use crate::mode::GameMode;
+use crate::player::PaddleConfig;
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Minimum clearance kept between an obstacle and the paddles' reach
+const PADDLE_CLEARANCE: f32 = 1.0;
+
+/// Configuration for the arcade mode obstacles
+#[derive(Debug, Resource)]
+pub struct ObstacleConfig {
+    /// Number of obstacles to spawn (1 is centered, 2 mirror each other)
+    pub count: usize,
+    /// Width of each obstacle in world units
+    pub width: f32,
+    /// Height of each obstacle in world units
+    pub height: f32,
+    /// Horizontal distance from the center line for mirrored obstacles
+    pub x_offset: f32,
+    /// Maximum vertical distance travelled from the board's center
+    pub amplitude: f32,
+    /// Oscillation speed in radians per second
+    pub speed: f32,
+}
+
+impl Default for ObstacleConfig {
+    fn default() -> Self {
+        Self {
+            count: 2,
+            width: 0.3,
+            height: 1.2,
+            x_offset: 2.0,
+            amplitude: 3.0,
+            speed: 1.2,
+        }
+    }
+}
+
+/// Returns the oscillation amplitude, limited so obstacles stay inside the
+/// top and bottom walls.
+fn clamped_amplitude(config: &ObstacleConfig, board: &BoardConfig) -> f32 {
+    let max_amplitude = (board.half_height() - board.wall_thickness - config.height / 2.0).max(0.0);
+    config.amplitude.clamp(0.0, max_amplitude)
+}
+
+/// Component for a midfield obstacle
+#[derive(Component, Debug)]
+struct Obstacle {
+    /// Phase offset of the sine oscillation so obstacles don't move in lockstep
+    phase: f32,
+}
+
+/// Spawns the obstacles when entering gameplay in arcade mode.
+///
+/// Obstacle positions are clamped so they never reach into the paddles'
+/// x range or through the top and bottom walls, regardless of configuration.
+/// Obstacles sit above the center line on the z-axis so the dashes are
+/// hidden behind them.
+fn spawn_obstacles(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<ObstacleConfig>,
+    paddle_config: Res<PaddleConfig>,
+    board: Res<BoardConfig>,
+    theme: Res<Theme>,
+) {
+    // Keep the obstacles well clear of the area the paddles can reach
+    let max_x = paddle_config.line_offset(&board) - paddle_config.curve_depth - PADDLE_CLEARANCE;
+    let x_offset = config
+        .x_offset
+        .clamp(0.0, (max_x - config.width / 2.0).max(0.0));
+
+    let amplitude = clamped_amplitude(&config, &board);
+
+    for i in 0..config.count {
+        // A single obstacle sits on the center line, otherwise alternate sides
+        let x = if config.count == 1 {
+            0.0
+        } else if i % 2 == 0 {
+            -x_offset
+        } else {
+            x_offset
+        };
+        let phase = i as f32 * std::f32::consts::PI;
+        let y = amplitude * (time.elapsed_secs() * config.speed + phase).sin();
+
+        commands.spawn((
+            Obstacle { phase },
+            Sprite {
+                color: theme.colors().wall,
+                custom_size: Some(Vec2::new(config.width, config.height)),
+                ..default()
+            },
+            Themed::Wall,
+            // Drawn in front of the center line dashes
+            Transform::from_xyz(x, y, 1.0),
+            // Kinematic bodies are moved by their transform but still push the ball
+            RigidBody::KinematicPositionBased,
+            Collider::cuboid(config.width / 2.0, config.height / 2.0),
+            Restitution {
+                coefficient: board.wall_restitution,
+                combine_rule: CoefficientCombineRule::Multiply,
+            },
+            Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            },
+            ActiveCollisionTypes::all(),
+            board_collision_groups(),
+        ));
+    }
+}
+
+/// Moves the obstacles up and down along a sine wave.
+fn move_obstacles(
+    time: Res<Time>,
+    config: Res<ObstacleConfig>,
+    board: Res<BoardConfig>,
+    mut query: Query<(&Obstacle, &mut Transform)>,
+) {
+    let amplitude = clamped_amplitude(&config, &board);
+
+    for (obstacle, mut transform) in query.iter_mut() {
+        let angle = time.elapsed_secs() * config.speed + obstacle.phase;
+        transform.translation.y = amplitude * angle.sin();
+    }
+}
+
+/// Removes all obstacles when leaving gameplay.
+fn cleanup_obstacles(mut commands: Commands, query: Query<Entity, With<Obstacle>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Plugin that manages the arcade mode obstacles.
+///
+/// Obstacles only exist while playing in [`GameMode::Arcade`], and are
+/// removed whenever the game leaves the Playing state.
+pub struct ObstaclePlugin;
+
+impl Plugin for ObstaclePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ObstacleConfig>()
+            // Spawn obstacles when gameplay starts in arcade mode
+            .add_systems(
+                OnEnter(GameState::Playing),
+                spawn_obstacles.run_if(resource_equals(GameMode::Arcade)),
+            )
+            // Animate obstacles during gameplay
+            .add_systems(Update, move_obstacles.run_if(in_state(GameState::Playing)))
+            // Remove obstacles when leaving gameplay
+            .add_systems(OnExit(GameState::Playing), cleanup_obstacles);
+    }
+}
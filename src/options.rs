@@ -0,0 +1,418 @@
+//! Settings Screen Module
+//!
+//! This module shows the settings screen, opened from the main menu or the
+//! pause menu, including:
+//! - A row per setting showing its current value
+//! - Keyboard and mouse navigation with a highlighted row
+//! - Applying each change straight away, so it can be previewed behind the
+//!   screen, such as a new theme recoloring the menu
+//! - Returning to the screen it was opened from
+//!
+//! The screen is drawn over the menu or pause menu rather than replacing
+//! them, so closing it simply uncovers the screen it was opened from. The
+//! state it was opened in is kept, and the screen closes itself if the game
+//! leaves that state, such as when an online opponent leaves. While it is
+//! open, [`settings_closed`] keeps the covered screen's keys out of the
+//! way.
+//!
+//! The rows are listed in [`SETTING_ROWS`], so a new setting is one more
+//! entry there. Changes are saved by the settings module once the screen
+//! closes.
+
+use crate::assist::AssistSettings;
+use crate::audio::AudioSettings;
+use crate::camera::GraphicsSettings;
+use crate::clutch::ClutchConfig;
+use crate::palette::{RUST_ORANGE, SETTINGS_OVERLAY_ALPHA};
+use crate::serve::ServeConfig;
+use crate::speedometer::SpeedometerConfig;
+use crate::theme::{Theme, Themed};
+use crate::wallflash::WallFlashConfig;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+/// Color used to highlight the selected row
+const SELECTED_COLOR: Color = RUST_ORANGE;
+
+/// Hint shown at the bottom of the settings screen
+const SETTINGS_HINT: &str = "UP/DOWN to pick  |  LEFT/RIGHT to change  |  ESC to go back";
+
+/// A setting shown on the settings screen
+pub struct SettingRow {
+    /// Name shown before the value
+    pub name: &'static str,
+    /// The setting's current value, as shown
+    pub value: fn(&World) -> String,
+    /// Moves the setting to its next value, or its previous one if the flag
+    /// is false. Changes are applied straight away.
+    pub change: fn(&mut World, bool),
+}
+
+/// The settings screen's rows in display order
+pub const SETTING_ROWS: &[SettingRow] = &[
+    SettingRow {
+        name: "Theme",
+        value: |world| world.resource::<Theme>().label().to_string(),
+        change: |world, forward| {
+            let mut theme = world.resource_mut::<Theme>();
+            *theme = if forward {
+                theme.next()
+            } else {
+                theme.previous()
+            };
+        },
+    },
+    SettingRow {
+        name: "Music",
+        value: |world| on_off(world.resource::<AudioSettings>().music_enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<AudioSettings>().music_enabled),
+    },
+    SettingRow {
+        name: "Sound effects",
+        value: |world| on_off(world.resource::<AudioSettings>().sfx_enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<AudioSettings>().sfx_enabled),
+    },
+    SettingRow {
+        name: "Aim assist",
+        value: |world| world.resource::<AssistSettings>().label().to_string(),
+        change: |world, _| toggle(&mut world.resource_mut::<AssistSettings>().aim_assist),
+    },
+    SettingRow {
+        name: "Dramatic saves",
+        value: |world| world.resource::<ClutchConfig>().label().to_string(),
+        change: |world, _| toggle(&mut world.resource_mut::<ClutchConfig>().enabled),
+    },
+    SettingRow {
+        name: "Manual serve",
+        value: |world| on_off(world.resource::<ServeConfig>().manual),
+        change: |world, _| toggle(&mut world.resource_mut::<ServeConfig>().manual),
+    },
+    SettingRow {
+        name: "CRT effect",
+        value: |world| on_off(world.resource::<GraphicsSettings>().crt_enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<GraphicsSettings>().crt_enabled),
+    },
+    SettingRow {
+        name: "Action zoom",
+        value: |world| on_off(world.resource::<GraphicsSettings>().zoom_enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<GraphicsSettings>().zoom_enabled),
+    },
+    SettingRow {
+        name: "Center line pulse",
+        value: |world| on_off(world.resource::<GraphicsSettings>().center_line_pulse),
+        change: |world, _| toggle(&mut world.resource_mut::<GraphicsSettings>().center_line_pulse),
+    },
+    SettingRow {
+        name: "Background grid",
+        value: |world| on_off(world.resource::<GraphicsSettings>().background_grid),
+        change: |world, _| toggle(&mut world.resource_mut::<GraphicsSettings>().background_grid),
+    },
+    SettingRow {
+        name: "Hit sparks",
+        value: |world| on_off(world.resource::<GraphicsSettings>().hit_sparks),
+        change: |world, _| toggle(&mut world.resource_mut::<GraphicsSettings>().hit_sparks),
+    },
+    SettingRow {
+        name: "Speedometer",
+        value: |world| on_off(world.resource::<SpeedometerConfig>().enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<SpeedometerConfig>().enabled),
+    },
+    SettingRow {
+        name: "Wall flashes",
+        value: |world| on_off(world.resource::<WallFlashConfig>().enabled),
+        change: |world, _| toggle(&mut world.resource_mut::<WallFlashConfig>().enabled),
+    },
+];
+
+/// Shows an on/off setting
+fn on_off(enabled: bool) -> String {
+    if enabled { "On" } else { "Off" }.to_string()
+}
+
+/// Flips an on/off setting
+fn toggle(enabled: &mut bool) {
+    *enabled = !*enabled;
+}
+
+/// Resource tracking the settings screen
+#[derive(Resource, Debug, Default)]
+pub struct SettingsScreen {
+    /// State the screen was opened in, or `None` while it is closed
+    opened_in: Option<GameState>,
+    /// Highlighted row. One past the last row is the way back.
+    selected: usize,
+}
+
+impl SettingsScreen {
+    /// Opens the screen over the current `state`, with the first row
+    /// highlighted
+    pub fn open(&mut self, state: GameState) {
+        *self = Self {
+            opened_in: Some(state),
+            selected: 0,
+        };
+    }
+
+    /// Whether the screen is open
+    pub fn is_open(&self) -> bool {
+        self.opened_in.is_some()
+    }
+
+    /// Closes the screen, uncovering the one it was opened from
+    fn close(&mut self) {
+        self.opened_in = None;
+    }
+}
+
+/// Run condition that passes while the settings screen is closed. The
+/// screens it covers use this to keep their keys out of the way.
+pub fn settings_closed(screen: Res<SettingsScreen>) -> bool {
+    !screen.is_open()
+}
+
+/// Run condition that passes while the settings screen is open
+fn settings_open(screen: Res<SettingsScreen>) -> bool {
+    screen.is_open()
+}
+
+/// Component attached to each row's text, holding its index into
+/// [`SETTING_ROWS`]. One past the last row is the way back.
+#[derive(Component)]
+struct SettingRowText(usize);
+
+/// Marker component for identifying settings screen UI elements.
+#[derive(Component)]
+struct SettingsOverlay;
+
+/// Plugin that shows the settings screen.
+pub struct OptionsPlugin;
+
+impl Plugin for OptionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsScreen>()
+            .add_systems(
+                Update,
+                (
+                    close_if_left,
+                    sync_settings_overlay.run_if(resource_changed::<SettingsScreen>),
+                    (update_row_texts, highlight_selected_row).run_if(settings_open),
+                )
+                    .chain(),
+            )
+            // After the frame's other systems, so the covered screen's keys
+            // see the screen as it was when the key was pressed
+            .add_systems(PostUpdate, handle_settings_input.run_if(settings_open));
+    }
+}
+
+/// Closes the screen if the game has left the state it was opened in.
+fn close_if_left(state: Res<State<GameState>>, mut screen: ResMut<SettingsScreen>) {
+    if screen
+        .opened_in
+        .is_some_and(|opened_in| opened_in != *state.get())
+    {
+        screen.close();
+    }
+}
+
+/// Handles keyboard and mouse input on the settings screen.
+///
+/// Up/down arrows move the highlight between the rows, and hovering a row
+/// highlights it. Left/right arrows change the highlighted setting, and
+/// Enter or a click moves it to its next value. Escape, or picking Back,
+/// closes the screen.
+///
+/// The frame the screen was opened in is skipped, so the key that opened
+/// it isn't read again.
+fn handle_settings_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    interactions: Query<(&Interaction, &SettingRowText), Changed<Interaction>>,
+    mut screen: ResMut<SettingsScreen>,
+) {
+    if screen.is_changed() {
+        return;
+    }
+
+    // One past the last row is the way back
+    let back = SETTING_ROWS.len();
+    let mut picked = None;
+    for (interaction, row) in interactions.iter() {
+        match interaction {
+            Interaction::Hovered => screen.selected = row.0,
+            Interaction::Pressed => {
+                screen.selected = row.0;
+                picked = Some(row.0);
+            }
+            Interaction::None => {}
+        }
+    }
+
+    if keyboard.just_pressed(KeyCode::ArrowDown) {
+        screen.selected = (screen.selected + 1) % (back + 1);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowUp) {
+        screen.selected = (screen.selected + back) % (back + 1);
+    }
+    if keyboard.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter]) {
+        picked = Some(screen.selected);
+    }
+
+    // Left/right change the highlighted setting, and picking a setting moves
+    // it on
+    let forward = if keyboard.just_pressed(KeyCode::ArrowRight) {
+        Some(true)
+    } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        Some(false)
+    } else {
+        picked.map(|_| true)
+    };
+    if let (Some(row), Some(forward)) = (SETTING_ROWS.get(screen.selected), forward) {
+        let change = row.change;
+        commands.queue(move |world: &mut World| change(world, forward));
+    }
+
+    if keyboard.just_pressed(KeyCode::Escape) || picked == Some(back) {
+        screen.close();
+    }
+}
+
+/// Shows or hides the settings screen to match whether it is open.
+///
+/// The screen covers the whole window with a see-through theme background,
+/// so changes can be previewed on the screen behind it. The rows' values
+/// are filled in by [`update_row_texts`].
+fn sync_settings_overlay(
+    mut commands: Commands,
+    screen: Res<SettingsScreen>,
+    theme: Res<Theme>,
+    query: Query<Entity, With<SettingsOverlay>>,
+) {
+    if !screen.is_open() {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    if !query.is_empty() {
+        return;
+    }
+
+    let colors = theme.colors();
+    commands
+        .spawn((
+            SettingsOverlay,
+            Node {
+                position_type: PositionType::Absolute,
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(colors.background.with_alpha(SETTINGS_OVERLAY_ALPHA)),
+            Themed::Background,
+            // Keeps the mouse off the covered screen's items
+            FocusPolicy::Block,
+            // Drawn over the menu and the lobby text
+            GlobalZIndex(2),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("SETTINGS"),
+                TextFont {
+                    font_size: theme.font_size(60.0),
+                    ..default()
+                },
+                TextColor(colors.text),
+                Themed::Text,
+                Node {
+                    // Add space below the title
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            // A row per setting, then the way back set apart from them
+            for index in 0..=SETTING_ROWS.len() {
+                let back = index == SETTING_ROWS.len();
+                parent.spawn((
+                    SettingRowText(index),
+                    Button,
+                    Text::default(),
+                    TextFont {
+                        font_size: theme.font_size(26.0),
+                        ..default()
+                    },
+                    TextColor(colors.text),
+                    Themed::Text,
+                    Node {
+                        margin: if back {
+                            UiRect::top(Val::Px(14.0))
+                        } else {
+                            UiRect::bottom(Val::Px(6.0))
+                        },
+                        ..default()
+                    },
+                ));
+            }
+
+            parent.spawn((
+                Text::new(SETTINGS_HINT),
+                TextFont {
+                    font_size: theme.font_size(20.0), // Small, secondary text
+                    ..default()
+                },
+                TextColor(colors.get(Themed::DimText)),
+                Themed::DimText,
+                Node {
+                    margin: UiRect::top(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Keeps each row's text in sync with its setting's value.
+///
+/// The values come from anywhere in the world, so this reads them every
+/// frame the screen is open, only touching the texts that changed.
+fn update_row_texts(world: &mut World) {
+    let labels: Vec<String> = SETTING_ROWS
+        .iter()
+        .map(|row| format!("{}: < {} >", row.name, (row.value)(world)))
+        .collect();
+
+    let mut query = world.query::<(&SettingRowText, &mut Text)>();
+    for (row, mut text) in query.iter_mut(world) {
+        let label = labels.get(row.0).map_or("Back", String::as_str);
+        if text.0 != label {
+            text.0 = label.to_string();
+        }
+    }
+}
+
+/// Highlights the selected row, leaving the others in the theme's text
+/// color.
+///
+/// Runs every frame, since theme changes recolor the text too.
+fn highlight_selected_row(
+    screen: Res<SettingsScreen>,
+    theme: Res<Theme>,
+    mut query: Query<(&SettingRowText, &mut TextColor)>,
+) {
+    let text = theme.colors().text;
+    for (row, mut color) in query.iter_mut() {
+        let wanted = if row.0 == screen.selected {
+            SELECTED_COLOR
+        } else {
+            text
+        };
+        if color.0 != wanted {
+            color.0 = wanted;
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Color Palette Module
+//!
+//! This module defines the game's named colors in one place, so every
+//! screen and game object draws from the same palette:
+//!
+//! - Foreground white for paddles, ball, walls and text
+//! - Black background
+//! - Accent colors for wins, losses and highlights
+//! - Translucent overlays for menus drawn over the board
+//!
+//! All colors use sRGB components in the 0.0 to 1.0 range. Colors picked
+//! as 0 to 255 channel values are divided down, so they can't end up out of
+//! range and clamp to a different color.
+
+use bevy::prelude::*;
+
+/// Paddles, ball, walls, center line and regular UI text
+pub const FOREGROUND: Color = Color::srgb(1.0, 1.0, 1.0);
+
+/// Board and menu background
+pub const BACKGROUND: Color = Color::srgb(0.0, 0.0, 0.0);
+
+/// Complementary green, used for wins and rewards
+pub const VICTORY_GREEN: Color = Color::srgb(26.0 / 255.0, 228.0 / 255.0, 61.0 / 255.0);
+
+/// Rust orange, used for losses, penalties and highlights
+pub const RUST_ORANGE: Color = Color::srgb(228.0 / 255.0, 61.0 / 255.0, 26.0 / 255.0);
+
+/// Warning red, used for stunned paddles
+pub const STUN_RED: Color = Color::srgb(0.9, 0.1, 0.1);
+
+/// Alpha of the pause menu overlay, letting the frozen board show through
+pub const PAUSE_OVERLAY_ALPHA: f32 = 0.7;
+
+/// Alpha of the endgame screen overlay, mostly hiding the board
+pub const ENDGAME_OVERLAY_ALPHA: f32 = 0.9;
+
+/// Alpha of the splash screen and menu over the attract demo, keeping the
+/// settings readable
+pub const SPLASH_OVERLAY_ALPHA: f32 = 0.8;
+
+/// Alpha of the settings screen over the menu or pause menu, light enough
+/// to preview changes on the screen behind it
+pub const SETTINGS_OVERLAY_ALPHA: f32 = 0.85;
+
+/// Returns the background color at the given overlay alpha
+pub fn overlay(alpha: f32) -> Color {
+    BACKGROUND.with_alpha(alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_component_is_in_range() {
+        let colors = [FOREGROUND, BACKGROUND, VICTORY_GREEN, RUST_ORANGE, STUN_RED];
+        let alphas = [
+            PAUSE_OVERLAY_ALPHA,
+            ENDGAME_OVERLAY_ALPHA,
+            SPLASH_OVERLAY_ALPHA,
+            SETTINGS_OVERLAY_ALPHA,
+        ];
+
+        for color in colors.into_iter().chain(alphas.map(overlay)) {
+            let srgba = color.to_srgba();
+            for component in srgba.to_f32_array() {
+                assert!(
+                    (0.0..=1.0).contains(&component),
+                    "{color:?} has a component out of range"
+                );
+            }
+        }
+    }
+}
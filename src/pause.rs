@@ -4,12 +4,51 @@
 //! - Pause menu UI creation and cleanup
 //! - State transitions between Playing and Paused states
 //! - Space key input handling for pause toggling
+//! - Escape key handling for leaving to the main menu
+//! - S key handling for opening the settings screen
+//! - A short grace period after resuming, with the ball held still
 //!
 //! The pause system uses Bevy's UI system for menu rendering and
 //! state system for game state management.
+//!
+//! The balls stay where they are while paused, with the physics halted.
+//! During the grace period the pause menu's dimming fades away while every
+//! ball waits with its velocity stored, then carries on at exactly that
+//! velocity. Pausing again during the grace period simply starts a new one
+//! on the next resume.
 
+use crate::ball::Ball;
+use crate::options::{settings_closed, SettingsScreen};
+use crate::palette::{overlay, PAUSE_OVERLAY_ALPHA};
+use crate::score::{MatchTimer, Score, ScoreRules};
+use crate::survival::SurvivalStats;
+use crate::theme::Theme;
 use crate::GameState;
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Hint shown on the pause menu for the Escape key
+const ESCAPE_HINT: &str = "Press ESC for the main menu";
+
+/// Hint shown on the pause menu for the settings screen
+const SETTINGS_HINT: &str = "Press S for settings";
+
+/// How long balls are held still after resuming, in seconds
+const RESUME_GRACE: f32 = 0.5;
+
+/// Resource timing the grace period after resuming from the pause menu, or
+/// `None` outside one
+#[derive(Resource, Debug, Default)]
+pub struct ResumeGrace(pub Option<Timer>);
+
+/// Component holding the velocity of a ball held still during the grace
+/// period
+#[derive(Component, Debug)]
+struct Frozen(Vec2);
+
+/// Marker component for the dimming that fades out during the grace period
+#[derive(Component)]
+struct GraceOverlay;
 
 /// Marker component for identifying pause menu entities.
 /// Used for querying and cleanup when the pause state exits.
@@ -21,15 +60,48 @@ struct PauseMenu;
 /// Responsible for:
 /// - Spawning the pause menu when entering paused state
 /// - Cleaning up the menu when exiting paused state
+/// - Handling the Escape and S keys
+/// - Holding the ball still for a moment after resuming
 pub struct PausePlugin;
 
 impl Plugin for PausePlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<ResumeGrace>()
             // Spawn pause menu when entering paused state
             .add_systems(OnEnter(GameState::Paused), spawn_pause_menu)
             // Cleanup menu when exiting paused state
-            .add_systems(OnExit(GameState::Paused), despawn_pause_menu);
+            .add_systems(OnExit(GameState::Paused), despawn_pause_menu)
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Paused,
+                    entered: GameState::Playing,
+                },
+                start_resume_grace,
+            )
+            // Before the physics step, so a held ball doesn't move at all.
+            // The speed clamp leaves a stopped ball alone.
+            .add_systems(
+                FixedUpdate,
+                hold_balls_during_grace
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                fade_grace_overlay.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), end_resume_grace)
+            // Escape runs after the space handler so it wins if both are
+            // pressed. Both keys are left to the settings screen while it
+            // is open.
+            .add_systems(
+                Update,
+                (
+                    handle_back_to_menu.after(handle_pause),
+                    handle_open_settings,
+                )
+                    .run_if(settings_closed),
+            );
     }
 }
 
@@ -38,12 +110,16 @@ impl Plugin for PausePlugin {
 /// Creates a full-screen, semi-transparent overlay with:
 /// - Centered "PAUSED" text in large font
 /// - "Press SPACE to continue" prompt below
+/// - A hint that Escape leads to the main menu
+/// - A hint that S opens the settings screen
 ///
 /// The menu uses flexbox layout for:
 /// - Vertical stacking of elements
 /// - Center alignment both horizontally and vertically
 /// - Full screen coverage
-fn spawn_pause_menu(mut commands: Commands) {
+fn spawn_pause_menu(mut commands: Commands, theme: Res<Theme>) {
+    let text_color = theme.colors().text;
+
     commands
         .spawn((
             // Mark as pause menu for later cleanup
@@ -62,7 +138,7 @@ fn spawn_pause_menu(mut commands: Commands) {
                 ..default()
             },
             // Semi-transparent black overlay
-            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            BackgroundColor(overlay(PAUSE_OVERLAY_ALPHA)),
             Visibility::default(),
         ))
         .with_children(|parent| {
@@ -70,10 +146,10 @@ fn spawn_pause_menu(mut commands: Commands) {
             parent.spawn((
                 Text::new("PAUSED"),
                 TextFont {
-                    font_size: 80.0, // Large, prominent text
+                    font_size: theme.font_size(80.0), // Large, prominent text
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                TextColor(text_color),
                 Node {
                     // Add space below the title
                     margin: UiRect::bottom(Val::Px(20.0)),
@@ -85,15 +161,118 @@ fn spawn_pause_menu(mut commands: Commands) {
             parent.spawn((
                 Text::new("Press SPACE to continue"),
                 TextFont {
-                    font_size: 40.0, // Smaller than title
+                    font_size: theme.font_size(40.0), // Smaller than title
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                TextColor(text_color),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            // Escape hint
+            parent.spawn((
+                Text::new(ESCAPE_HINT),
+                TextFont {
+                    font_size: theme.font_size(30.0),
+                    ..default()
+                },
+                TextColor(text_color),
+                Node {
+                    margin: UiRect::bottom(Val::Px(10.0)),
+                    ..default()
+                },
+            ));
+
+            // Settings hint
+            parent.spawn((
+                Text::new(SETTINGS_HINT),
+                TextFont {
+                    font_size: theme.font_size(30.0),
+                    ..default()
+                },
+                TextColor(text_color),
                 Node::default(),
             ));
         });
 }
 
+/// Starts the grace period when resuming, dimmed like the pause menu.
+fn start_resume_grace(mut commands: Commands, mut grace: ResMut<ResumeGrace>) {
+    grace.0 = Some(Timer::from_seconds(RESUME_GRACE, TimerMode::Once));
+    commands.spawn((
+        GraceOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(overlay(PAUSE_OVERLAY_ALPHA)),
+    ));
+}
+
+/// Holds every ball still during the grace period, storing its velocity,
+/// and gives each ball its stored velocity back once the period ends.
+///
+/// Balls served during the grace period are held too.
+fn hold_balls_during_grace(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut grace: ResMut<ResumeGrace>,
+    mut ball_query: Query<(Entity, &mut Velocity, Option<&Frozen>), With<Ball>>,
+) {
+    let Some(timer) = grace.0.as_mut() else {
+        return;
+    };
+    let finished = timer.tick(time.delta()).finished();
+
+    for (entity, mut velocity, frozen) in ball_query.iter_mut() {
+        match (frozen, finished) {
+            (None, false) => {
+                commands.entity(entity).insert(Frozen(velocity.linvel));
+                velocity.linvel = Vec2::ZERO;
+            }
+            (Some(frozen), true) => {
+                velocity.linvel = frozen.0;
+                commands.entity(entity).remove::<Frozen>();
+            }
+            _ => {}
+        }
+    }
+    if finished {
+        grace.0 = None;
+    }
+}
+
+/// Fades the grace period's dimming out, removing it once the period ends.
+fn fade_grace_overlay(
+    mut commands: Commands,
+    grace: Res<ResumeGrace>,
+    mut query: Query<(Entity, &mut BackgroundColor), With<GraceOverlay>>,
+) {
+    for (entity, mut background) in query.iter_mut() {
+        match &grace.0 {
+            Some(timer) => background.0 = overlay(PAUSE_OVERLAY_ALPHA * timer.fraction_remaining()),
+            None => commands.entity(entity).despawn_recursive(),
+        }
+    }
+}
+
+/// Ends any grace period when play stops, such as pausing again during it.
+/// The held balls are despawned with the rest of play.
+fn end_resume_grace(
+    mut commands: Commands,
+    mut grace: ResMut<ResumeGrace>,
+    query: Query<Entity, With<GraceOverlay>>,
+) {
+    grace.0 = None;
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 /// Cleans up the pause menu when exiting the paused state.
 ///
 /// Queries for all entities with the PauseMenu component and
@@ -106,7 +285,7 @@ fn despawn_pause_menu(mut commands: Commands, pause_menu: Query<Entity, With<Pau
 
 /// System that handles pausing and unpausing the game when space is pressed.
 /// Only toggles between Playing and Paused states, ignoring other states
-/// (like the splash screen).
+/// (like the menu).
 ///
 /// # State Transitions
 /// - Playing → Paused: When space pressed during gameplay
@@ -121,7 +300,40 @@ pub(crate) fn handle_pause(
         match current_state.get() {
             GameState::Playing => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::Playing),
-            _ => (), // Do nothing in other states (like Splash)
+            _ => (), // Do nothing in other states (like Menu)
         }
     }
 }
+
+/// System that leaves the pause menu for the main menu when Escape is
+/// pressed. The game itself is quit from the menu.
+///
+/// The abandoned match is cleared the same way as leaving the endgame screen.
+fn handle_back_to_menu(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut score: ResMut<Score>,
+    rules: Res<ScoreRules>,
+    mut timer: ResMut<MatchTimer>,
+    mut survival: ResMut<SurvivalStats>,
+) {
+    if keyboard.just_pressed(KeyCode::Escape) && *current_state.get() == GameState::Paused {
+        score.reset(&rules);
+        timer.reset();
+        survival.reset_run();
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// System that opens the settings screen over the pause menu when S is
+/// pressed. Closing it returns to the pause menu.
+fn handle_open_settings(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    current_state: Res<State<GameState>>,
+    mut settings_screen: ResMut<SettingsScreen>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyS) && *current_state.get() == GameState::Paused {
+        settings_screen.open(GameState::Paused);
+    }
+}
@@ -0,0 +1,374 @@
+//! Replay Playback Module
+//!
+//! This module lets players watch a recorded match from the main menu,
+//! including:
+//! - Loading the saved replay file, or the last match on the web
+//! - Ghost ball and paddles driven by the recorded frames
+//! - The score at the current point of the recording
+//! - Play/pause and seek controls
+//!
+//! Playback has its own game state, so none of the gameplay systems run
+//! while a replay is shown, and live physics is frozen until it ends.
+//! Files from an incompatible version of the game are rejected with a
+//! warning and the splash screen stays up.
+//!
+//! The same ghosts show online matches to the joining player, with frames
+//! streamed in by the net module as the host plays them.
+
+use crate::ball::BallAssets;
+use crate::net::lobby_idle;
+use crate::options::settings_closed;
+use crate::player::{create_paddle_mesh, PaddleConfig};
+use crate::replay::Recorder;
+use crate::score::format_duration;
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+/// Seconds skipped by each press of the left or right arrow
+const SEEK_STEP: f32 = 5.0;
+
+/// Resource holding the recording being watched and the playback position
+#[derive(Debug, Resource, Default)]
+pub struct ReplayPlayback {
+    /// The recording being watched
+    pub recording: Recorder,
+    /// Seconds into the recording currently shown
+    pub position: f32,
+    /// Whether playback is advancing
+    pub playing: bool,
+    /// Whether the frames are being streamed from an online host
+    pub live: bool,
+}
+
+/// Marker component for everything spawned for playback
+#[derive(Component)]
+struct PlaybackEntity;
+
+/// Marker component for the ghost ball
+#[derive(Component)]
+struct GhostBall;
+
+/// Component naming which recorded paddle a ghost paddle shows
+#[derive(Component)]
+enum GhostPaddle {
+    P1,
+    P2,
+}
+
+/// Marker component for the score text
+#[derive(Component)]
+struct PlaybackScoreText;
+
+/// Marker component for the position and controls text
+#[derive(Component)]
+struct PlaybackStatusText;
+
+/// Loads the recording to watch.
+///
+/// Native builds read the file saved at the end of the last match, while
+/// browsers, with no file system, replay the last match played this session.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_recording(_recorder: &Recorder) -> Option<Recorder> {
+    match Recorder::load(crate::replay::REPLAY_FILE) {
+        Ok(recording) => Some(recording),
+        Err(error) => {
+            warn!("Can't play replay: {}", error);
+            None
+        }
+    }
+}
+
+/// Loads the recording to watch.
+///
+/// Native builds read the file saved at the end of the last match, while
+/// browsers, with no file system, replay the last match played this session.
+#[cfg(target_arch = "wasm32")]
+fn load_recording(recorder: &Recorder) -> Option<Recorder> {
+    Some(recorder.clone())
+}
+
+/// Starts playback when L is pressed in the menu.
+fn open_replay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    recorder: Res<Recorder>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyL) {
+        return;
+    }
+
+    let Some(recording) = load_recording(&recorder) else {
+        return;
+    };
+    if recording.frames.is_empty() {
+        warn!("Can't play replay: the recording is empty");
+        return;
+    }
+
+    *playback = ReplayPlayback {
+        recording,
+        position: 0.0,
+        playing: true,
+        live: false,
+    };
+    next_state.set(GameState::Replay);
+}
+
+/// Freezes live physics and spawns the ghost entities and playback UI.
+fn spawn_playback(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    ball_assets: Res<BallAssets>,
+    paddle_config: Res<PaddleConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut rapier_query: Query<&mut RapierConfiguration>,
+) {
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = false;
+    }
+
+    let colors = theme.colors();
+
+    // Hidden until a frame with a ball is shown
+    commands.spawn((
+        PlaybackEntity,
+        GhostBall,
+        Mesh2d(ball_assets.mesh.clone()),
+        MeshMaterial2d(ball_assets.material.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+
+    let paddle_mesh = create_paddle_mesh(&mut meshes, &paddle_config);
+    let paddle_material = materials.add(ColorMaterial::from(colors.paddle));
+    for paddle in [GhostPaddle::P1, GhostPaddle::P2] {
+        commands.spawn((
+            PlaybackEntity,
+            paddle,
+            Mesh2d(paddle_mesh.clone()),
+            MeshMaterial2d(paddle_material.clone()),
+            Themed::Paddle,
+            Transform::default(),
+            Visibility::Hidden,
+        ));
+    }
+
+    commands.spawn((
+        PlaybackEntity,
+        PlaybackScoreText,
+        Themed::Text,
+        Text::default(),
+        TextFont {
+            font_size: theme.font_size(40.0),
+            ..default()
+        },
+        TextColor(colors.text),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Px(20.0),
+            ..default()
+        },
+    ));
+
+    commands.spawn((
+        PlaybackEntity,
+        PlaybackStatusText,
+        Themed::Text,
+        Text::default(),
+        TextFont {
+            font_size: theme.font_size(20.0),
+            ..default()
+        },
+        TextColor(colors.text),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(20.0),
+            ..default()
+        },
+    ));
+}
+
+/// Handles the playback controls.
+///
+/// Space plays or pauses, the left and right arrows seek, and Escape
+/// returns to the menu. Playing from the end starts over.
+fn handle_playback_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let duration = playback.recording.duration();
+
+    if keyboard.just_pressed(KeyCode::Space) {
+        if !playback.playing && playback.position >= duration {
+            playback.position = 0.0;
+        }
+        playback.playing = !playback.playing;
+    }
+    if keyboard.just_pressed(KeyCode::ArrowLeft) {
+        playback.position = (playback.position - SEEK_STEP).max(0.0);
+    }
+    if keyboard.just_pressed(KeyCode::ArrowRight) {
+        playback.position = (playback.position + SEEK_STEP).min(duration);
+    }
+    if keyboard.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+/// Advances playback at the original pacing, stopping at the end.
+fn advance_playback(time: Res<Time>, mut playback: ResMut<ReplayPlayback>) {
+    if !playback.playing {
+        return;
+    }
+
+    let duration = playback.recording.duration();
+    playback.position += time.delta_secs();
+    if playback.position >= duration {
+        playback.position = duration;
+        playback.playing = false;
+    }
+}
+
+/// Moves the ghost entities to the frame at the playback position,
+/// interpolated between the recorded frames.
+///
+/// Right-hand paddles are turned so the scoop faces the center, as they
+/// are in play.
+fn show_playback_frame(
+    playback: Res<ReplayPlayback>,
+    mut ball_query: Query<(&mut Transform, &mut Visibility), With<GhostBall>>,
+    mut paddle_query: Query<(&GhostPaddle, &mut Transform, &mut Visibility), Without<GhostBall>>,
+) {
+    let Some(frame) = playback.recording.interpolated_frame_at(playback.position) else {
+        return;
+    };
+
+    for (mut transform, mut visibility) in ball_query.iter_mut() {
+        match frame.ball {
+            Some(ball) => {
+                transform.translation = Vec2::from(ball.position).extend(0.0);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    for (paddle, mut transform, mut visibility) in paddle_query.iter_mut() {
+        let position = match paddle {
+            GhostPaddle::P1 => frame.p1,
+            GhostPaddle::P2 => frame.p2,
+        };
+        match position {
+            Some(position) => {
+                transform.translation = Vec2::from(position).extend(0.0);
+                transform.rotation = if position[0] < 0.0 {
+                    Quat::IDENTITY
+                } else {
+                    Quat::from_rotation_z(std::f32::consts::PI)
+                };
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// Keeps the score and status text in sync with the playback position.
+///
+/// Scores are listed in the same left-to-right order as the paddles.
+fn update_playback_text(
+    playback: Res<ReplayPlayback>,
+    mut score_query: Query<&mut Text, With<PlaybackScoreText>>,
+    mut status_query: Query<&mut Text, (With<PlaybackStatusText>, Without<PlaybackScoreText>)>,
+) {
+    let recording = &playback.recording;
+    let (p1, p2) = recording.score_at(playback.position);
+    let p1_left = recording
+        .frame_at(playback.position)
+        .and_then(|frame| frame.p1)
+        .is_none_or(|[x, _]| x < 0.0);
+    let (left, right) = if p1_left { (p1, p2) } else { (p2, p1) };
+
+    for mut text in score_query.iter_mut() {
+        **text = format!("{} - {}", left, right);
+    }
+
+    let status = match (playback.live, playback.playing) {
+        (true, true) => {
+            "ONLINE  |  W/S or UP/DOWN to move  |  SPACE to pause  |  ESC to leave".to_string()
+        }
+        (true, false) => "PAUSED  |  SPACE to resume  |  ESC to leave".to_string(),
+        (false, playing) => format!(
+            "{} {} / {}  |  SPACE to play/pause  |  LEFT/RIGHT to seek  |  ESC for menu",
+            if playing { "PLAYING" } else { "PAUSED" },
+            format_duration(Duration::from_secs_f32(playback.position)),
+            format_duration(Duration::from_secs_f32(recording.duration())),
+        ),
+    };
+    for mut text in status_query.iter_mut() {
+        **text = status.clone();
+    }
+}
+
+/// Removes the playback entities and unfreezes live physics.
+fn despawn_playback(
+    mut commands: Commands,
+    mut rapier_query: Query<&mut RapierConfiguration>,
+    query: Query<Entity, With<PlaybackEntity>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = true;
+    }
+}
+
+/// Plugin that plays back recorded matches.
+pub struct PlaybackPlugin;
+
+impl Plugin for PlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayPlayback>()
+            .add_systems(
+                Update,
+                open_replay.run_if(
+                    in_state(GameState::Menu)
+                        .and(lobby_idle)
+                        .and(settings_closed),
+                ),
+            )
+            .add_systems(OnEnter(GameState::Replay), spawn_playback)
+            .add_systems(
+                Update,
+                (
+                    handle_playback_input,
+                    advance_playback,
+                    show_playback_frame,
+                    update_playback_text,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Replay)),
+            )
+            .add_systems(OnExit(GameState::Replay), despawn_playback)
+            // Online guests watch the host's match through the same ghosts
+            .add_systems(OnEnter(GameState::Online), spawn_playback)
+            .add_systems(
+                Update,
+                (show_playback_frame, update_playback_text)
+                    .chain()
+                    .run_if(in_state(GameState::Online)),
+            )
+            .add_systems(OnExit(GameState::Online), despawn_playback);
+    }
+}
@@ -3,51 +3,191 @@
 //! This module implements the player paddle mechanics for the Pong game, including both
 //! human-controlled and AI-controlled paddles.
 
-use crate::ball::Ball;
+use crate::attract::attract_running;
+use crate::ball::{Ball, BallConfig, BallOwner, SpeedBoost};
+use crate::board::{paddle_collision_groups, BoardConfig, Wall};
+use crate::ghost::GhostRecording;
+use crate::mode::GameMode;
+use crate::palette::{RUST_ORANGE, STUN_RED};
+use crate::replay::not_replaying;
+use crate::score::{MatchRestarted, Score, ServeStarted};
+use crate::serve::{AiServeConfig, AiServePlan};
+use crate::theme::{Theme, Themed};
+use crate::touch::TouchTarget;
+use crate::trajectory::{ball_height_limit, predict_intersection};
 use crate::GameState;
-use bevy::app::{App, Plugin, Startup, Update};
+use bevy::app::{App, Plugin, Update};
 use bevy::prelude::*;
 use bevy::render::mesh::Indices;
 use bevy::render::render_asset::RenderAssetUsages;
 use bevy::render::render_resource::PrimitiveTopology;
 use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Configuration constants for paddle physics and gameplay
-#[derive(Debug, Resource)]
+///
+/// Handicaps scale the speed and height per paddle, see [`PaddleStats`].
+#[derive(Debug, Clone, Resource)]
 pub struct PaddleConfig {
     /// Movement speed in world units per second
     pub speed: f32,
-    /// X-coordinate for left paddle position
-    pub left_x: f32,
-    /// X-coordinate for right paddle position
-    pub right_x: f32,
+    /// Whether paddles steered with keys speed up and slow down rather than
+    /// moving at full speed straight away
+    pub momentum: bool,
+    /// How quickly a key-steered paddle speeds up, in world units per
+    /// second squared
+    pub acceleration: f32,
+    /// How quickly a key-steered paddle slows down or turns around, in
+    /// world units per second squared
+    pub deceleration: f32,
+    /// Distance from each paddle to the scoring wall behind it
+    pub wall_gap: f32,
     /// Total height of the paddle
     pub height: f32,
     /// Depth of the paddle's curve
     pub curve_depth: f32,
-    /// Number of segments used to create the curved shape
-    pub segments: usize,
+    /// Number of segments used to draw the curved shape, between
+    /// [`MIN_MESH_SEGMENTS`] and [`MAX_MESH_SEGMENTS`]
+    pub mesh_segments: usize,
+    /// Number of straight edges approximating the curve in the collider,
+    /// between [`MIN_COLLIDER_SEGMENTS`] and [`MAX_COLLIDER_SEGMENTS`]
+    pub collider_segments: usize,
     /// Mass of the paddle for physics calculations
     pub mass: f32,
+    /// Bounciness of the paddle, multiplied with the ball's, see
+    /// [`BoardConfig`]. Sets how much speed a plain return keeps.
+    pub restitution: f32,
     /// Duration of punch animation in seconds
     pub punch_duration: f32,
     /// Distance paddle moves during punch
     pub punch_distance: f32,
+    /// Extra speed multiplier given to the ball by a well-timed swing
+    pub swing_boost: f32,
+    /// Time needed to fully charge a shot in seconds
+    pub charge_time: f32,
+    /// Extra speed multiplier given to the ball by a fully charged hit
+    pub charge_boost: f32,
+    /// How long a charged or swung hit may exceed the normal speed cap in seconds
+    pub charge_boost_duration: f32,
+    /// Speed multiplier while dashing
+    pub dash_speed: f32,
+    /// How long a dash lasts in seconds
+    pub dash_duration: f32,
+    /// Time after a dash before the next one is allowed, in seconds
+    pub dash_cooldown: f32,
+    /// Longest gap between the two taps of a double tap, in seconds
+    pub dash_window: f32,
+    /// How long a paddle hit on its back or tip can't move, in seconds
+    pub stun_duration: f32,
+    /// Contacts this close to the paddle's flat back count as hitting the
+    /// back, in world units
+    pub stun_back_depth: f32,
+    /// Contacts further from the paddle's middle than this fraction of its
+    /// half height count as hitting a tip
+    pub stun_tip_fraction: f32,
+}
+
+/// Fewest segments the paddle is drawn with; any fewer and the curve
+/// collapses into a flat slab
+pub const MIN_MESH_SEGMENTS: usize = 4;
+
+/// Most segments the paddle is drawn with; beyond this the extra triangles
+/// are smaller than a pixel at any window size
+pub const MAX_MESH_SEGMENTS: usize = 1000;
+
+/// Fewest edges the paddle collider is built from; two edges are the least
+/// that still bulge toward the ball
+pub const MIN_COLLIDER_SEGMENTS: usize = 2;
+
+/// Most edges the paddle collider is built from; beyond this the hull only
+/// costs more in collision checks without bouncing any differently
+pub const MAX_COLLIDER_SEGMENTS: usize = 64;
+
+impl PaddleConfig {
+    /// Distance from the center of the board to either paddle's line
+    pub fn line_offset(&self, board: &BoardConfig) -> f32 {
+        (board.half_width() - self.wall_gap).max(0.0)
+    }
+
+    /// Number of segments the paddle is drawn with, clamped to the supported
+    /// range
+    fn mesh_segment_count(&self) -> usize {
+        self.mesh_segments
+            .clamp(MIN_MESH_SEGMENTS, MAX_MESH_SEGMENTS)
+    }
+
+    /// Number of edges the paddle collider is built from, clamped to the
+    /// supported range
+    fn collider_segment_count(&self) -> usize {
+        self.collider_segments
+            .clamp(MIN_COLLIDER_SEGMENTS, MAX_COLLIDER_SEGMENTS)
+    }
 }
 
 impl Default for PaddleConfig {
     fn default() -> Self {
         Self {
             speed: 20.0,
-            left_x: -7.65,
-            right_x: 7.65,
+            // Full speed in about an eighth of a second, and a short coast
+            // of under half a paddle once the key is let go
+            momentum: true,
+            acceleration: 160.0,
+            deceleration: 240.0,
+            wall_gap: 0.35,
             height: 2.0,
             curve_depth: 0.3,
-            segments: 100,
+            mesh_segments: 100,
+            // Enough to keep bounces off the curve smooth, see `create_paddle_collider`
+            collider_segments: 12,
             mass: 0.1,
+            // Slightly inelastic for better control
+            restitution: 0.9,
             punch_duration: 0.05,
             punch_distance: 0.15,
+            swing_boost: 1.3,
+            charge_time: 1.0,
+            charge_boost: 0.5,
+            charge_boost_duration: 2.0,
+            // A quick step rather than a teleport, and rare enough to matter
+            dash_speed: 3.0,
+            dash_duration: 0.15,
+            dash_cooldown: 1.0,
+            dash_window: 0.2,
+            // Long enough to punish a sloppy block, short enough to recover
+            // before the ball comes back
+            stun_duration: 0.4,
+            stun_back_depth: 0.03,
+            stun_tip_fraction: 0.92,
+        }
+    }
+}
+
+/// Optional gameplay rules for how the paddles play.
+///
+/// By default returns are left to the physics engine, where the paddle's
+/// curve bends them subtly. Arcade deflection instead sets the return's
+/// angle from where the ball met the paddle, as in classic Pong: flat from
+/// the middle, steepening toward the tips. The AI keeps its simple straight
+/// line prediction either way.
+#[derive(Debug, Resource)]
+pub struct RulesConfig {
+    /// Whether returns are angled by where the ball met the paddle
+    pub arcade_deflection: bool,
+    /// Steepest return angle from the horizontal, off a paddle's tip, in
+    /// radians
+    pub max_deflection_angle: f32,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            // Pure physics unless asked for
+            arcade_deflection: false,
+            // Steep enough to reward aiming, without returns that crawl
+            // along the walls
+            max_deflection_angle: std::f32::consts::FRAC_PI_3,
         }
     }
 }
@@ -61,12 +201,34 @@ pub struct AiConfig {
     pub movement_deadzone: f32,
     /// Offset from center for optimal hit point
     pub hit_point_offset: f32,
+    /// How the AI decides which side of center to meet the ball with
+    pub aim: AiAim,
     /// Chance to make a prediction error (0.0 - 1.0)
     pub error_chance: f32,
     /// Maximum prediction error amount in world units
     pub max_error: f32,
     /// Chance to completely miss the ball (0.0 - 1.0)
     pub miss_chance: f32,
+    /// Distance from the target at which the paddle starts slowing down
+    pub easing_distance: f32,
+    /// Time taken to accelerate to full speed from rest (seconds)
+    pub ease_in_time: f32,
+    /// Chance to prepare a charged shot when going for a return (0.0 - 1.0)
+    pub charge_chance: f32,
+    /// Chance to dash when the paddle would otherwise reach the ball too
+    /// late (0.0 - 1.0)
+    pub dash_chance: f32,
+    /// Only react once the ball crosses the midline toward the AI's side
+    pub react_after_midline: bool,
+    /// How strongly the AI leans toward where the human's returns usually
+    /// go, from 0.0 (not at all) to 1.0. See [`AiAdaptation`].
+    pub adaptation_strength: f32,
+    /// How much each earlier return still counts when a new one comes in
+    /// (0.0 - 1.0), so the AI keeps up as the human changes their game
+    pub adaptation_decay: f32,
+    /// Time without an incoming ball before the AI drifts back to its rest
+    /// position (seconds)
+    pub recenter_delay: f32,
 }
 
 /// Configuration for a challenging AI opponent
@@ -96,6 +258,12 @@ impl Default for AiConfig {
             // can be targeted
             hit_point_offset: 0.4,
 
+            // Which way the hit point is offset
+            // Following the ball's direction is easy to read once
+            // noticed; aiming for the weak side makes returns harder
+            // to anticipate
+            aim: AiAim::BallDirection,
+
             // Chance to misread the ball's trajectory
             // Most noticeable when handling steep angles or after
             // multiple bounces, representing the limits of its
@@ -113,28 +281,409 @@ impl Default for AiConfig {
             // the ball approaches at extreme angles, simulating
             // the challenge of handling powerful shots
             miss_chance: 0.05,
+
+            // Distance over which the paddle decelerates into its target
+            // Like a human easing off a key as the paddle lines up,
+            // which also means it can't snap into place at the last moment
+            easing_distance: 0.6,
+
+            // Time to reach full speed when starting a movement
+            // Mimics the small delay of a key press ramping up
+            ease_in_time: 0.08,
+
+            // Chance to charge up a return
+            // Roughly matches how often a human can time a charged hit,
+            // so the mechanic doesn't tilt the game either way
+            charge_chance: 0.2,
+
+            // Chance to dash for a ball it can't otherwise reach
+            // Often enough to rescue some wide shots, but a well placed
+            // ball still gets past it
+            dash_chance: 0.5,
+
+            // Whether to wait for the ball to cross the midline
+            // Off by default; turning it on gives the AI a human-like
+            // reaction delay that makes fast cross-court shots harder to read
+            react_after_midline: false,
+
+            // How much the AI learns from the human's returns
+            // Enough to punish a player who keeps hitting the same spot,
+            // while the pull is capped so it never reads them perfectly
+            adaptation_strength: 0.5,
+
+            // How quickly old returns are forgotten
+            // Roughly the last half dozen returns matter
+            adaptation_decay: 0.85,
+
+            // How long to wait before heading back to the middle
+            // Long enough that it doesn't twitch between a return and the
+            // next shot, short enough to be ready for the next rally
+            recenter_delay: 0.6,
+        }
+    }
+}
+
+/// How the AI chooses which side of its paddle's center meets the ball.
+///
+/// The paddle's curve sends the ball toward the side of center it touches,
+/// so the offset decides where the return goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AiAim {
+    #[default]
+    BallDirection, // Against the ball's vertical direction, sending it on its way
+    Fixed,    // Always the same side of center, whatever the ball's direction
+    WeakSide, // Away from where the human's paddle is
+}
+
+impl AiAim {
+    /// Returns the next aim, wrapping around
+    pub fn next(&self) -> Self {
+        match self {
+            AiAim::BallDirection => AiAim::Fixed,
+            AiAim::Fixed => AiAim::WeakSide,
+            AiAim::WeakSide => AiAim::BallDirection,
+        }
+    }
+}
+
+/// Configuration for "rubber-band" difficulty, which eases off the AI
+/// when the human is behind and tightens it up when they're ahead.
+///
+/// Once the gap is wider than the dead zone, each further point of the
+/// human's deficit adds the per-point steps to the AI's
+/// [`AiConfig::error_chance`], [`AiConfig::miss_chance`] and
+/// [`AiConfig::update_rate`], and to the AI's
+/// [`AiServeConfig::fast_weight`], and each point of their lead takes them
+/// away, within the given bounds. Only applies to games with exactly one human.
+/// Offered in the menu as the "Adaptive" difficulty.
+#[derive(Debug, Resource)]
+pub struct RubberBandConfig {
+    /// Whether the AI adapts to the score gap
+    pub enabled: bool,
+    /// Score gap, either way, that is played at the base difficulty
+    pub dead_zone: u32,
+    /// Change in error chance per point of score gap
+    pub error_step: f32,
+    /// Change in miss chance per point of score gap
+    pub miss_step: f32,
+    /// Change in time between AI decisions per point of score gap, in seconds
+    pub update_rate_step: f32,
+    /// Change in the relative chance of a fast AI serve per point of score
+    /// gap
+    pub fast_serve_step: f32,
+    /// Lowest and highest error chance the adjustment can reach
+    pub error_bounds: (f32, f32),
+    /// Lowest and highest miss chance the adjustment can reach
+    pub miss_bounds: (f32, f32),
+    /// Shortest and longest time between AI decisions the adjustment can
+    /// reach, in seconds
+    pub update_rate_bounds: (f32, f32),
+    /// Lowest and highest relative chance of a fast AI serve the adjustment
+    /// can reach
+    pub fast_serve_bounds: (f32, f32),
+}
+
+impl Default for RubberBandConfig {
+    fn default() -> Self {
+        Self {
+            // Off by default so the AI plays the same every game
+            enabled: false,
+            // Close games are left alone; the AI adjusts from a 3 point gap
+            dead_zone: 2,
+            // A five point deficit beyond the dead zone roughly doubles the
+            // AI's mistakes and makes it noticeably slower to react
+            error_step: 0.025,
+            miss_step: 0.01,
+            update_rate_step: 0.02,
+            // ...and serve fast about half as often, against the slow
+            // serves' even odds
+            fast_serve_step: -0.1,
+            // Never flawless, and never so sloppy it stops trying
+            error_bounds: (0.04, 0.3),
+            miss_bounds: (0.01, 0.15),
+            update_rate_bounds: (0.2, 0.45),
+            // Fast serves stay in the mix, but never dominate
+            fast_serve_bounds: (0.25, 2.0),
+        }
+    }
+}
+
+/// Smallest handicap multiplier
+const MIN_HANDICAP: f32 = 0.5;
+
+/// Largest handicap multiplier
+const MAX_HANDICAP: f32 = 1.5;
+
+/// Change in a handicap multiplier per step in the menu
+const HANDICAP_STEP: f32 = 0.1;
+
+/// Moves a handicap multiplier one step up or down, within the allowed
+/// range. Rounded to whole steps so repeated changes don't drift.
+fn step_multiplier(value: f32, up: bool) -> f32 {
+    let step = if up { HANDICAP_STEP } else { -HANDICAP_STEP };
+    (((value + step) / HANDICAP_STEP).round() * HANDICAP_STEP).clamp(MIN_HANDICAP, MAX_HANDICAP)
+}
+
+/// A player's handicap, as multipliers of the paddle's normal speed and
+/// height from [`PaddleConfig`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Handicap {
+    /// Paddle speed multiplier, from 0.5 to 1.5
+    pub speed: f32,
+    /// Paddle height multiplier, from 0.5 to 1.5
+    pub height: f32,
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+impl Handicap {
+    /// Whether the paddle plays at its normal speed and height
+    pub fn is_even(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Moves the speed multiplier one step up or down
+    pub fn step_speed(&mut self, up: bool) {
+        self.speed = step_multiplier(self.speed, up);
+    }
+
+    /// Moves the height multiplier one step up or down
+    pub fn step_height(&mut self, up: bool) {
+        self.height = step_multiplier(self.height, up);
+    }
+
+    /// Text shown for the handicap on the endgame screen
+    pub fn label(&self) -> String {
+        format!(
+            "Speed {:.0}%, Size {:.0}%",
+            self.speed * 100.0,
+            self.height * 100.0
+        )
+    }
+}
+
+/// Resource holding each player's handicap, chosen in the menu
+/// before a game. Even handicaps play exactly like the plain
+/// [`PaddleConfig`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Handicaps {
+    /// Handicap of `Player::P1`
+    pub p1: Handicap,
+    /// Handicap of `Player::P2`
+    pub p2: Handicap,
+}
+
+impl Handicaps {
+    /// Returns the given player's handicap
+    pub fn get(&self, player: Player) -> Handicap {
+        match player {
+            Player::P1 => self.p1,
+            Player::P2 => self.p2,
+        }
+    }
+}
+
+/// Component holding a paddle's effective speed and height, after its
+/// player's handicap. Movement and collision code read these rather than
+/// [`PaddleConfig`] so each paddle can differ.
+#[derive(Component, Debug, Clone, Copy)]
+pub(crate) struct PaddleStats {
+    /// Movement speed in world units per second
+    pub(crate) speed: f32,
+    /// Total height of the paddle, before power-ups scale it
+    pub(crate) height: f32,
+}
+
+impl PaddleStats {
+    /// Applies a handicap to the configured speed and height
+    fn new(config: &PaddleConfig, handicap: Handicap) -> Self {
+        Self {
+            speed: config.speed * handicap.speed,
+            height: config.height * handicap.height,
         }
     }
 }
 
 /// Component that identifies which player a paddle belongs to
-#[derive(Component, Clone, Debug)]
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Player {
-    P1, // Human player (left paddle)
-    P2, // AI player (right paddle)
+    P1, // Human player
+    P2, // AI player
+}
+
+impl Player {
+    /// Returns the other player
+    pub fn opponent(&self) -> Self {
+        match self {
+            Player::P1 => Player::P2,
+            Player::P2 => Player::P1,
+        }
+    }
+}
+
+/// Who controls a player's paddle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    Human,  // Keyboard input
+    Ai,     // Computer opponent
+    Ghost,  // Computer opponent replaying the human's last match
+    Remote, // Online opponent, see `RemoteInput`
+}
+
+/// Resource recording who controls each scoring slot.
+///
+/// Screens that talk to "the player" use this rather than assuming the
+/// human is `Player::P1`, so results read correctly for human-vs-AI,
+/// local multiplayer and AI-vs-AI games alike.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerRoles {
+    /// Controller of `Player::P1`
+    pub p1: Controller,
+    /// Controller of `Player::P2`
+    pub p2: Controller,
+}
+
+impl Default for PlayerRoles {
+    fn default() -> Self {
+        Self {
+            p1: Controller::Human,
+            p2: Controller::Ai,
+        }
+    }
+}
+
+impl PlayerRoles {
+    /// Returns the controller of the given player
+    pub fn controller(&self, player: Player) -> Controller {
+        match player {
+            Player::P1 => self.p1,
+            Player::P2 => self.p2,
+        }
+    }
+
+    /// Returns the single local human player in a game against the AI, a
+    /// ghost or an online opponent, or `None` when both or neither players
+    /// are local humans
+    pub fn sole_human(&self) -> Option<Player> {
+        use Controller::{Ai, Ghost, Human, Remote};
+        match (self.p1, self.p2) {
+            (Human, Ai | Ghost | Remote) => Some(Player::P1),
+            (Ai | Ghost | Remote, Human) => Some(Player::P2),
+            _ => None,
+        }
+    }
+}
+
+/// Resource holding the keys an online opponent is holding, as last sent
+/// by their game
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct RemoteInput {
+    /// Whether they are moving up
+    pub up: bool,
+    /// Whether they are moving down
+    pub down: bool,
+}
+
+/// Resource selecting which side of the board the human player defends.
+///
+/// `Player::P1` is always the human and `Player::P2` the AI; this only
+/// decides where each paddle is placed, which wall each player defends and
+/// which way each player serves.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PlayerSide {
+    #[default]
+    Left, // Human on the left, AI on the right
+    Right, // Human on the right, AI on the left
+}
+
+impl PlayerSide {
+    /// Text shown for the side in the menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlayerSide::Left => "Left",
+            PlayerSide::Right => "Right",
+        }
+    }
+
+    /// Returns the other side
+    pub fn toggle(&self) -> Self {
+        match self {
+            PlayerSide::Left => PlayerSide::Right,
+            PlayerSide::Right => PlayerSide::Left,
+        }
+    }
+
+    /// Returns true if the given player's paddle is on the left side
+    pub fn is_left(&self, player: Player) -> bool {
+        (player == Player::P1) == (*self == PlayerSide::Left)
+    }
+
+    /// Returns the x-coordinate of the given player's paddle on `board`
+    pub fn paddle_x(&self, player: Player, config: &PaddleConfig, board: &BoardConfig) -> f32 {
+        let offset = config.line_offset(board);
+        if self.is_left(player) {
+            -offset
+        } else {
+            offset
+        }
+    }
+
+    /// Returns the player defending a wall, or `None` for the top and
+    /// bottom walls
+    pub fn defender(&self, wall: &Wall) -> Option<Player> {
+        let left_player = if *self == PlayerSide::Left {
+            Player::P1
+        } else {
+            Player::P2
+        };
+        match wall {
+            Wall::Left => Some(left_player),
+            Wall::Right => Some(left_player.opponent()),
+            Wall::Top | Wall::Bottom => None,
+        }
+    }
 }
 
 /// Represents the current movement state of the AI paddle
-#[derive(Debug)]
-enum MovementState {
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MovementState {
+    #[default]
     Idle,
-    MovingUp(f32),   // Contains target Y position
-    MovingDown(f32), // Contains target Y position
+    MovingUp(f32),    // Contains target Y position
+    MovingDown(f32),  // Contains target Y position
+    Recentering(f32), // Contains the rest Y position
+}
+
+/// Read-only snapshot of an AI paddle's state, for the debug overlay,
+/// tuning tools and tests.
+///
+/// Every AI paddle has one, refreshed each frame by the AI's decision
+/// making. Changing it has no effect on the AI.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default)]
+pub struct AiDebugInfo {
+    /// Current movement state
+    pub movement_state: MovementState,
+    /// Last predicted height where a ball crosses the paddle's line
+    pub last_prediction: Option<f32>,
+    /// Time left of the current upward movement in seconds
+    pub move_up_remaining: f32,
+    /// Time left of the current downward movement in seconds
+    pub move_down_remaining: f32,
+    /// Time until the AI next decides where to go in seconds
+    pub next_decision: f32,
 }
 
 /// Component for AI-controlled paddles that simulates human-like input behavior
 #[derive(Component, Debug)]
-struct AiPaddle {
+#[require(AiDebugInfo)]
+pub(crate) struct AiPaddle {
     /// Timer to control AI decision rate
     update_timer: Timer,
     /// Timer for upward movement duration
@@ -145,6 +694,8 @@ struct AiPaddle {
     movement_state: MovementState,
     /// Last predicted intersection point
     last_prediction: Option<f32>,
+    /// Time since the AI last predicted an incoming ball (seconds)
+    unpredicted_time: f32,
 }
 
 impl Default for AiPaddle {
@@ -158,10 +709,166 @@ impl Default for AiPaddle {
             move_down_timer: Timer::from_seconds(0.0, TimerMode::Once),
             movement_state: MovementState::Idle,
             last_prediction: None,
+            unpredicted_time: 0.0,
+        }
+    }
+}
+
+impl AiPaddle {
+    /// Starts moving the paddle from `current_y` toward `target_y`, unless
+    /// it is already within the movement deadzone
+    fn head_for(&mut self, current_y: f32, target_y: f32, speed: f32, deadzone: f32) {
+        let diff = target_y - current_y;
+        if diff.abs() <= deadzone {
+            return;
+        }
+
+        let duration = Duration::from_secs_f32(calculate_movement_duration(
+            current_y, target_y, speed, 0.1, // Minimum duration
+            0.5, // Maximum duration
+        ));
+        if diff > 0.0 {
+            self.movement_state = MovementState::MovingUp(target_y);
+            self.move_up_timer.set_duration(duration);
+            self.move_up_timer.reset();
+        } else {
+            self.movement_state = MovementState::MovingDown(target_y);
+            self.move_down_timer.set_duration(duration);
+            self.move_down_timer.reset();
+        }
+    }
+
+    /// Starts drifting the paddle from `current_y` back to `rest_y`, with
+    /// the same timed movement as [`AiPaddle::head_for`]
+    fn recenter(&mut self, current_y: f32, rest_y: f32, speed: f32, deadzone: f32) {
+        if (rest_y - current_y).abs() <= deadzone {
+            return;
+        }
+        self.head_for(current_y, rest_y, speed, deadzone);
+        self.movement_state = MovementState::Recentering(rest_y);
+    }
+
+    /// Snapshot of the AI's state for [`AiDebugInfo`]
+    fn debug_info(&self) -> AiDebugInfo {
+        AiDebugInfo {
+            movement_state: self.movement_state,
+            last_prediction: self.last_prediction,
+            move_up_remaining: self.move_up_timer.remaining_secs(),
+            move_down_remaining: self.move_down_timer.remaining_secs(),
+            next_decision: self.update_timer.remaining_secs(),
+        }
+    }
+
+    /// The movement timer for heading from `current_y` to `target_y`
+    fn timer_toward(&self, current_y: f32, target_y: f32) -> &Timer {
+        if target_y > current_y {
+            &self.move_up_timer
+        } else {
+            &self.move_down_timer
+        }
+    }
+}
+
+/// Number of returns after which the AI fully trusts what it has learned
+const ADAPTATION_SAMPLES: f32 = 10.0;
+
+/// Resource holding what the AI has learned about the human this match: a
+/// rolling histogram of where their returns cross the AI's goal line, split
+/// into top, middle and bottom thirds.
+///
+/// The AI rests closer to the favored height between rallies, and leans its
+/// hit point toward it when going for a return. The lean grows over the
+/// first returns of a match and never exceeds the hit point offset, so the
+/// AI anticipates without becoming perfect.
+#[derive(Debug, Resource, Default)]
+pub(crate) struct AiAdaptation {
+    /// Recent returns per third, from the top down, older ones fading out
+    zones: [f32; 3],
+    /// Returns seen this match
+    samples: u32,
+}
+
+impl AiAdaptation {
+    /// Returns the third of the board, from the top down, that a height
+    /// within `limit` of the center falls in
+    fn zone(y: f32, limit: f32) -> usize {
+        let third = 2.0 * limit / 3.0;
+        if y > limit - third {
+            0
+        } else if y < third - limit {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Notes a return crossing the goal line at `y`
+    fn record(&mut self, y: f32, limit: f32, decay: f32) {
+        for count in self.zones.iter_mut() {
+            *count *= decay;
+        }
+        self.zones[Self::zone(y, limit)] += 1.0;
+        self.samples += 1;
+    }
+
+    /// Each third's share of recent returns, from the top down
+    pub(crate) fn shares(&self) -> [f32; 3] {
+        let total: f32 = self.zones.iter().sum();
+        if total <= 0.0 {
+            return [0.0; 3];
+        }
+        self.zones.map(|count| count / total)
+    }
+
+    /// Average height of recent returns, by the centers of their thirds, or
+    /// `None` before the first return
+    fn favored_y(&self, limit: f32) -> Option<f32> {
+        if self.samples == 0 {
+            return None;
         }
+        let centers = [2.0 * limit / 3.0, 0.0, -2.0 * limit / 3.0];
+        let shares = self.shares();
+        Some((0..3).map(|i| centers[i] * shares[i]).sum())
+    }
+
+    /// How far to lean from `y` toward the favored height, scaled by the
+    /// strength and by how many returns have been seen
+    fn lean(&self, y: f32, limit: f32, strength: f32) -> f32 {
+        let confidence = (self.samples as f32 / ADAPTATION_SAMPLES).min(1.0);
+        self.favored_y(limit).map_or(0.0, |favored| {
+            (favored - y) * strength.clamp(0.0, 1.0) * confidence
+        })
     }
 }
 
+/// Component for a paddle replaying the human's last match.
+///
+/// Ghost paddles also have an [`AiPaddle`], which takes over in situations
+/// the [`GhostRecording`] never saw.
+#[derive(Component, Debug, Default)]
+pub(crate) struct GhostPaddle {
+    /// Height the recording puts the paddle at right now, if known
+    target: Option<f32>,
+}
+
+/// Marker component for a paddle moved by an online opponent
+#[derive(Component, Debug)]
+pub(crate) struct RemotePaddle;
+
+/// Sent when a paddle hits the ball, after any charge or swing boost has
+/// been applied
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PaddleHit {
+    /// The player whose paddle hit the ball
+    pub player: Player,
+    /// The ball's speed leaving the paddle
+    pub ball_speed: f32,
+    /// Charge released into the ball, from 0.0 (none) to 1.0 (full)
+    pub charge: f32,
+    /// Where the ball met the paddle, in world coordinates
+    pub position: Vec2,
+}
+
 /// Component to track paddle punch state and animation
 #[derive(Component, Debug)]
 struct PunchState {
@@ -169,6 +876,8 @@ struct PunchState {
     timer: Timer,
     /// Whether paddle is currently in punch state
     is_punching: bool,
+    /// Whether the current punch is a player-triggered swing
+    is_swinging: bool,
     /// Original x position to return to after punch
     rest_x: f32,
 }
@@ -178,11 +887,185 @@ impl Default for PunchState {
         Self {
             timer: Timer::from_seconds(PaddleConfig::default().punch_duration, TimerMode::Once),
             is_punching: false,
+            is_swinging: false,
             rest_x: 0.0,
         }
     }
 }
 
+/// Component tracking how charged a paddle's next return is
+///
+/// The human charges by holding Shift, the AI charges at random when it
+/// lines up a return. A charged hit boosts the ball above the normal speed
+/// cap and exaggerates the punch animation, then the charge is consumed.
+#[derive(Component, Debug, Default)]
+pub struct Charge {
+    /// Charge level from 0.0 (none) to 1.0 (full)
+    pub level: f32,
+}
+
+/// Component holding the vertical speed of a paddle steered with keys, in
+/// world units per second, for [`PaddleConfig::momentum`]
+#[derive(Component, Debug, Default)]
+pub(crate) struct PaddleVelocity(f32);
+
+/// Component tracking a paddle's dash, a short burst of extra speed.
+///
+/// The human dashes by double-tapping up or down, and the AI dashes at
+/// random when it would otherwise reach the ball too late. Each dash is
+/// followed by a cooldown before the next.
+#[derive(Component, Debug)]
+pub(crate) struct Dash {
+    /// Time left in the current dash
+    burst: Timer,
+    /// Time until the next dash, counted from the start of the last one
+    cooldown: Timer,
+    /// Direction (true for up) and time of the last tap, for spotting a
+    /// double tap
+    last_tap: Option<(bool, f32)>,
+}
+
+impl Dash {
+    /// Creates a dash that is ready to use
+    fn new(config: &PaddleConfig) -> Self {
+        let finished = |seconds: f32| {
+            let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+            timer.tick(timer.duration());
+            timer
+        };
+        Self {
+            burst: finished(config.dash_duration),
+            cooldown: finished(config.dash_duration + config.dash_cooldown),
+            last_tap: None,
+        }
+    }
+
+    /// Whether the paddle is dashing
+    fn is_dashing(&self) -> bool {
+        !self.burst.finished()
+    }
+
+    /// Whether a new dash can start
+    fn is_ready(&self) -> bool {
+        self.cooldown.finished()
+    }
+
+    /// Starts a dash if the cooldown allows it
+    fn start(&mut self) {
+        if self.is_ready() {
+            self.burst.reset();
+            self.cooldown.reset();
+        }
+    }
+
+    /// Notes a tap up or down at time `now`, starting a dash if it follows a
+    /// tap the same way within `window` seconds
+    fn tap(&mut self, up: bool, now: f32, window: f32) {
+        match self.last_tap {
+            Some((last_up, last_time)) if last_up == up && now - last_time <= window => {
+                self.start();
+                self.last_tap = None;
+            }
+            _ => self.last_tap = Some((up, now)),
+        }
+    }
+}
+
+/// Component for a paddle stunned by taking the ball on its back or tip,
+/// which can't move until the stun wears off
+#[derive(Component, Debug)]
+pub(crate) struct Stunned {
+    /// Time left in the stun
+    timer: Timer,
+    /// Red copy of the paddle flashed over it while stunned
+    flash: Entity,
+}
+
+/// Marker component for the red copy flashed over a stunned paddle
+#[derive(Component)]
+struct StunFlash;
+
+/// Resource holding the material stunned paddles flash in
+#[derive(Resource)]
+struct StunMaterial(Handle<ColorMaterial>);
+
+/// Time between a stunned paddle's flashes turning on or off, in seconds
+const STUN_FLASH_PERIOD: f32 = 0.08;
+
+/// Component for the small pip showing when a paddle's next dash is ready
+#[derive(Component)]
+struct DashPip(Entity);
+
+/// Width and height of a ready dash pip in world units
+const DASH_PIP_SIZE: f32 = 0.12;
+
+/// Gap between the top of a paddle and its dash pip in world units
+const DASH_PIP_GAP: f32 = 0.15;
+
+/// Alpha of a dash pip while its cooldown runs
+const DASH_PIP_COOLDOWN_ALPHA: f32 = 0.3;
+
+/// Marker component for the charge bar UI root
+#[derive(Component)]
+struct ChargeBar;
+
+/// Marker component for the charge bar fill
+#[derive(Component)]
+struct ChargeBarFill;
+
+/// Highest the center of a paddle `height` tall can go above or below the
+/// center of `board` without reaching into a wall
+fn paddle_limit(board: &BoardConfig, height: f32) -> f32 {
+    (board.inner_half_height() - height / 2.0).max(0.0)
+}
+
+/// Shortens a vertical move of `step` from `y` so it stops at `limit` above
+/// or below the center
+fn clamp_step(y: f32, step: f32, limit: f32) -> f32 {
+    (y + step).clamp(-limit, limit) - y
+}
+
+/// Direction held on a pair of up and down keys: 1.0 for up, -1.0 for down,
+/// or 0.0 for neither or both
+fn key_axis(up: bool, down: bool) -> f32 {
+    match (up, down) {
+        (true, false) => 1.0,
+        (false, true) => -1.0,
+        _ => 0.0,
+    }
+}
+
+/// Distance a paddle steered with keys moves this frame, updating its
+/// velocity toward `axis` (1.0 up, -1.0 down, 0.0 neither) times `speed`.
+///
+/// Without momentum, or while dashing, the paddle moves at the target
+/// speed straight away. Otherwise it speeds up at the configured
+/// acceleration, and slows down, coasts to a stop or turns around at the
+/// configured deceleration.
+fn keyed_step(
+    velocity: &mut PaddleVelocity,
+    axis: f32,
+    speed: f32,
+    dashing: bool,
+    config: &PaddleConfig,
+    delta: f32,
+) -> f32 {
+    let target = axis * speed;
+    if !config.momentum || dashing {
+        velocity.0 = target;
+    } else {
+        let slowing = target.abs() < velocity.0.abs() || target * velocity.0 < 0.0;
+        let rate = if slowing {
+            config.deceleration
+        } else {
+            config.acceleration
+        };
+        let max_change = rate * delta;
+        velocity.0 += (target - velocity.0).clamp(-max_change, max_change);
+    }
+    velocity.0 * delta
+}
+
 /// Calculate the duration needed to move to a target position
 fn calculate_movement_duration(
     current_pos: f32,
@@ -202,36 +1085,103 @@ fn calculate_movement_duration(
     duration.clamp(min_duration, max_duration)
 }
 
-/// Predicts where the ball will intersect with a paddle's x-position
-fn predict_intersection(ball_pos: Vec2, ball_vel: Vec2, paddle_x: f32) -> Option<f32> {
-    // Check if ball is moving toward paddle
-    let moving_toward =
-        (paddle_x > ball_pos.x && ball_vel.x > 0.0) || (paddle_x < ball_pos.x && ball_vel.x < 0.0);
+/// Smallest fraction of full speed used while easing, so the paddle
+/// never stalls short of its target
+const MIN_EASE: f32 = 0.15;
 
-    if moving_toward {
-        // Calculate intersection time and position
-        let time = (paddle_x - ball_pos.x) / ball_vel.x;
-        let y = ball_pos.y + (ball_vel.y * time);
-        Some(y)
-    } else {
-        None
+/// Calculates how far the AI paddle should move this frame toward its target.
+///
+/// Movement eases in from rest over `ease_in_time` and eases out as the paddle
+/// approaches the target within `easing_distance`. The step never overshoots
+/// the target.
+///
+/// # Arguments
+/// * `remaining` - Signed distance left to the target
+/// * `max_step` - Distance covered this frame at full speed
+/// * `elapsed` - Time since the current movement started
+/// * `ai_config` - AI tuning for the easing curve
+fn eased_step(remaining: f32, max_step: f32, elapsed: f32, ai_config: &AiConfig) -> f32 {
+    let distance = remaining.abs();
+    let ease_out = (distance / ai_config.easing_distance).clamp(MIN_EASE, 1.0);
+    let ease_in = (elapsed / ai_config.ease_in_time).clamp(MIN_EASE, 1.0);
+    let step = (max_step * ease_out.min(ease_in)).min(distance);
+    step * remaining.signum()
+}
+
+/// Signed offset of the AI's hit point from the ball, following
+/// [`AiConfig::aim`].
+///
+/// # Arguments
+/// * `ball_vel_y` - Ball's vertical velocity
+/// * `opponent_y` - Height of the opponent's paddle, if there is one; weak
+///   side aiming falls back to following the ball without it
+fn hit_point_offset(ai_config: &AiConfig, ball_vel_y: f32, opponent_y: Option<f32>) -> f32 {
+    let offset = ai_config.hit_point_offset;
+    match (ai_config.aim, opponent_y) {
+        (AiAim::Fixed, _) => offset,
+        // Meeting the ball below center sends it down, away from a high paddle
+        (AiAim::WeakSide, Some(y)) => offset * y.signum(),
+        (AiAim::BallDirection | AiAim::WeakSide, _) => {
+            if ball_vel_y > 0.0 {
+                -offset
+            } else {
+                offset
+            }
+        }
     }
 }
 
 /// System that controls AI paddle movement by simulating human-like input
+///
+/// With no ball heading its way, the AI lines up for its next serve if it
+/// has one planned, or otherwise rests where [`AiAdaptation`] expects the
+/// human's next return. Each paddle's [`AiDebugInfo`] is refreshed once its
+/// decision is made.
+#[allow(clippy::too_many_arguments)]
 fn ai_decision_making(
     time: Res<Time>,
+    board: Res<BoardConfig>,
     paddle_config: Res<PaddleConfig>,
+    ball_config: Res<BallConfig>,
     ai_config: Res<AiConfig>,
+    side: Res<PlayerSide>,
+    serve_plan: Res<AiServePlan>,
+    adaptation: Res<AiAdaptation>,
     ball_query: Query<(&Transform, &Velocity), With<Ball>>,
-    mut ai_query: Query<(&Transform, &mut AiPaddle)>,
+    mut ai_query: Query<(
+        &Player,
+        &Transform,
+        &PaddleStats,
+        &mut AiPaddle,
+        &mut AiDebugInfo,
+        &mut Charge,
+        &mut Dash,
+    )>,
+    opponent_query: Query<(&Player, &Transform)>,
 ) {
-    for (paddle_transform, mut ai) in ai_query.iter_mut() {
+    // The inside faces of the top and bottom walls bound both the ball and
+    // the paddle
+    let ball_limit = ball_height_limit(&board, ball_config.size);
+
+    for (player, paddle_transform, stats, mut ai, mut debug_info, mut charge, mut dash) in
+        ai_query.iter_mut()
+    {
+        let paddle_limit = paddle_limit(&board, stats.height);
+
+        // Normally the AI defends whichever side the human didn't pick, but
+        // the attract demo has an AI on both sides
+        let paddle_x = side.paddle_x(*player, &paddle_config, &board);
+        let opponent_y = opponent_query
+            .iter()
+            .find(|(other, _)| **other == player.opponent())
+            .map(|(_, transform)| transform.translation.y);
+
         // Update movement timers
         ai.move_up_timer.tick(time.delta());
         ai.move_down_timer.tick(time.delta());
 
         // Reset movement state if timers are finished
+        let current_y = paddle_transform.translation.y;
         match ai.movement_state {
             MovementState::MovingUp(_) if ai.move_up_timer.finished() => {
                 ai.movement_state = MovementState::Idle;
@@ -239,138 +1189,425 @@ fn ai_decision_making(
             MovementState::MovingDown(_) if ai.move_down_timer.finished() => {
                 ai.movement_state = MovementState::Idle;
             }
+            MovementState::Recentering(rest_y) if ai.timer_toward(current_y, rest_y).finished() => {
+                ai.movement_state = MovementState::Idle;
+            }
             _ => {}
         }
 
+        // The decision rate can change during a match, such as with
+        // rubber-band difficulty
+        let update_rate = Duration::from_secs_f32(ai_config.update_rate);
+        if ai.update_timer.duration() != update_rate {
+            ai.update_timer.set_duration(update_rate);
+        }
+
         if ai.update_timer.tick(time.delta()).just_finished() {
-            if let Ok((ball_transform, ball_velocity)) = ball_query.get_single() {
-                if let Some(predicted_y) = predict_intersection(
-                    ball_transform.translation.truncate(),
-                    ball_velocity.linvel,
-                    paddle_config.right_x,
-                ) {
-                    // Decide if we're going to try to hit the ball
-                    if rand::random::<f32>() < ai_config.miss_chance {
-                        // Intentionally miss by moving in wrong direction
-                        let miss_y = if predicted_y > 0.0 { -2.0 } else { 2.0 };
-                        let current_y = paddle_transform.translation.y;
-                        let diff = miss_y - current_y;
-
-                        if diff.abs() > ai_config.movement_deadzone {
-                            let duration = calculate_movement_duration(
-                                current_y,
-                                miss_y,
-                                paddle_config.speed,
-                                0.1,
-                                0.5,
-                            );
-
-                            if diff > 0.0 {
-                                ai.movement_state = MovementState::MovingUp(miss_y);
-                                ai.move_up_timer
-                                    .set_duration(Duration::from_secs_f32(duration));
-                                ai.move_up_timer.reset();
-                            } else {
-                                ai.movement_state = MovementState::MovingDown(miss_y);
-                                ai.move_down_timer
-                                    .set_duration(Duration::from_secs_f32(duration));
-                                ai.move_down_timer.reset();
-                            }
-                        }
+            // Track the ball that will reach the paddle soonest
+            let incoming = ball_query
+                .iter()
+                // Optionally ignore balls still in the opponent's half, like
+                // a player waiting to read the shot before committing
+                .filter(|(ball_transform, _)| {
+                    !ai_config.react_after_midline || ball_transform.translation.x * paddle_x > 0.0
+                })
+                .filter_map(|(ball_transform, ball_velocity)| {
+                    predict_intersection(
+                        ball_transform.translation.truncate(),
+                        ball_velocity.linvel,
+                        paddle_x,
+                        ball_limit,
+                    )
+                    .map(|(y, time)| (y, time, ball_velocity))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            if incoming.is_some() {
+                ai.unpredicted_time = 0.0;
+                // A ball is on its way, so stop drifting back to rest
+                if let MovementState::Recentering(_) = ai.movement_state {
+                    ai.movement_state = MovementState::Idle;
+                }
+            } else {
+                ai.unpredicted_time += ai.update_timer.duration().as_secs_f32();
+            }
+
+            if let Some((predicted_y, arrival, ball_velocity)) = incoming {
+                // Decide if we're going to try to hit the ball
+                if rand::random::<f32>() < ai_config.miss_chance {
+                    // Intentionally miss by moving in wrong direction
+                    let miss_y: f32 = if predicted_y > 0.0 { -2.0 } else { 2.0 };
+                    let miss_y = miss_y.clamp(-paddle_limit, paddle_limit);
+                    ai.head_for(
+                        paddle_transform.translation.y,
+                        miss_y,
+                        stats.speed,
+                        ai_config.movement_deadzone,
+                    );
+                } else {
+                    // Occasionally line up a charged return
+                    charge.level = if rand::random::<f32>() < ai_config.charge_chance {
+                        0.5 + rand::random::<f32>() * 0.5
                     } else {
-                        // Add potential prediction error
-                        let error = if rand::random::<f32>() < ai_config.error_chance {
-                            let error_amount = rand::random::<f32>() * ai_config.max_error;
-                            if rand::random::<bool>() {
-                                error_amount
-                            } else {
-                                -error_amount
-                            }
+                        0.0
+                    };
+
+                    // Add potential prediction error
+                    let error = if rand::random::<f32>() < ai_config.error_chance {
+                        let error_amount = rand::random::<f32>() * ai_config.max_error;
+                        if rand::random::<bool>() {
+                            error_amount
                         } else {
-                            0.0
-                        };
-
-                        // Calculate hit point with error and offset
-                        let optimal_y = predicted_y
-                            + error
-                            + if ball_velocity.linvel.y > 0.0 {
-                                -ai_config.hit_point_offset
-                            } else {
-                                ai_config.hit_point_offset
-                            };
-
-                        let current_y = paddle_transform.translation.y;
-                        let diff = optimal_y - current_y;
-
-                        // Only change movement if difference is significant
-                        if diff.abs() > ai_config.movement_deadzone {
-                            let duration = calculate_movement_duration(
-                                current_y,
-                                optimal_y,
-                                paddle_config.speed,
-                                0.1, // Minimum duration
-                                0.5, // Maximum duration
-                            );
-
-                            if diff > 0.0 {
-                                ai.movement_state = MovementState::MovingUp(optimal_y);
-                                ai.move_up_timer
-                                    .set_duration(Duration::from_secs_f32(duration));
-                                ai.move_up_timer.reset();
-                            } else {
-                                ai.movement_state = MovementState::MovingDown(optimal_y);
-                                ai.move_down_timer
-                                    .set_duration(Duration::from_secs_f32(duration));
-                                ai.move_down_timer.reset();
-                            }
+                            -error_amount
                         }
+                    } else {
+                        0.0
+                    };
+
+                    // Calculate hit point with error and offset, keeping the
+                    // target somewhere the paddle can actually reach
+                    let lean = adaptation
+                        .lean(predicted_y, ball_limit, ai_config.adaptation_strength)
+                        .clamp(-ai_config.hit_point_offset, ai_config.hit_point_offset);
+                    let offset =
+                        hit_point_offset(&ai_config, ball_velocity.linvel.y, opponent_y) + lean;
+                    let optimal_y =
+                        (predicted_y + error + offset).clamp(-paddle_limit, paddle_limit);
+
+                    // Sometimes dash for a ball it would otherwise be late for
+                    let travel_time =
+                        (optimal_y - paddle_transform.translation.y).abs() / stats.speed;
+                    if travel_time > arrival
+                        && dash.is_ready()
+                        && rand::random::<f32>() < ai_config.dash_chance
+                    {
+                        dash.start();
                     }
-                    ai.last_prediction = Some(predicted_y);
+
+                    // Only change movement if difference is significant
+                    ai.head_for(
+                        paddle_transform.translation.y,
+                        optimal_y,
+                        stats.speed,
+                        ai_config.movement_deadzone,
+                    );
                 }
+                ai.last_prediction = Some(predicted_y);
+            } else if let Some(ready_y) = serve_plan.ready_y() {
+                // Line up for the AI's next serve while waiting for it
+                ai.head_for(
+                    paddle_transform.translation.y,
+                    ready_y.clamp(-paddle_limit, paddle_limit),
+                    stats.speed,
+                    ai_config.movement_deadzone,
+                );
+            } else if ai.unpredicted_time >= ai_config.recenter_delay {
+                // Drift back between rallies rather than freezing wherever
+                // the last return left the paddle, resting near where the
+                // human tends to play once the AI has learned that
+                let rest_y = if ai_config.adaptation_strength > 0.0 && adaptation.samples > 0 {
+                    adaptation.lean(0.0, ball_limit, ai_config.adaptation_strength)
+                } else {
+                    0.0
+                };
+                ai.recenter(
+                    paddle_transform.translation.y,
+                    rest_y.clamp(-paddle_limit, paddle_limit),
+                    stats.speed,
+                    ai_config.movement_deadzone,
+                );
             }
         }
+
+        *debug_info = ai.debug_info();
+    }
+}
+
+/// Learns where the human's returns cross the AI's goal line.
+///
+/// The AI always defends `Player::P2`, so every hit by `Player::P1` counts.
+#[allow(clippy::too_many_arguments)]
+fn learn_from_returns(
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    ball_config: Res<BallConfig>,
+    ai_config: Res<AiConfig>,
+    side: Res<PlayerSide>,
+    mut adaptation: ResMut<AiAdaptation>,
+    mut hits: EventReader<PaddleHit>,
+    ball_query: Query<(&Transform, &Velocity, &BallOwner), With<Ball>>,
+) {
+    let paddle_x = side.paddle_x(Player::P2, &paddle_config, &board);
+    let ball_limit = ball_height_limit(&board, ball_config.size);
+
+    for hit in hits.read() {
+        if hit.player != Player::P1 {
+            continue;
+        }
+        let crossing = ball_query
+            .iter()
+            .filter(|(_, _, owner)| owner.0 == Some(hit.player))
+            .find_map(|(transform, velocity, _)| {
+                predict_intersection(
+                    transform.translation.truncate(),
+                    velocity.linvel,
+                    paddle_x,
+                    ball_limit,
+                )
+            });
+        if let Some((y, _)) = crossing {
+            adaptation.record(y, ball_limit, ai_config.adaptation_decay);
+        }
+    }
+}
+
+/// Forgets what the AI learned when a new match begins, including one
+/// restarted mid-game.
+fn reset_adaptation(mut adaptation: ResMut<AiAdaptation>) {
+    *adaptation = AiAdaptation::default();
+}
+
+/// System that looks up where the recorded human stood in the ball's
+/// current situation, for each ghost paddle.
+fn ghost_decision_making(
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    side: Res<PlayerSide>,
+    recording: Res<GhostRecording>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    mut ghost_query: Query<&mut GhostPaddle>,
+) {
+    // Like the AI, the ghost defends whichever side the human didn't pick
+    let paddle_x = side.paddle_x(Player::P2, &paddle_config, &board);
+    let facing = if side.is_left(Player::P2) { 1.0 } else { -1.0 };
+    let ball = ball_query.iter().next();
+
+    for mut ghost in ghost_query.iter_mut() {
+        ghost.target = ball.and_then(|(transform, velocity)| {
+            recording.target_y(
+                paddle_x,
+                facing,
+                transform.translation.truncate(),
+                velocity.linvel,
+            )
+        });
+    }
+}
+
+/// Cycles how the AI aims its returns when F8 is pressed.
+fn cycle_ai_aim(keyboard: Res<ButtonInput<KeyCode>>, mut ai_config: ResMut<AiConfig>) {
+    if keyboard.just_pressed(KeyCode::F8) {
+        ai_config.aim = ai_config.aim.next();
+        info!("AI aim: {:?}", ai_config.aim);
     }
 }
 
-/// Unified system that handles both human and AI paddle movement
+/// Moves a base value by `gap` steps, keeping the result within `bounds`.
+/// With no gap the base value is kept as it is, even outside the bounds.
+fn rubber_band_value(base: f32, gap: f32, step: f32, (min, max): (f32, f32)) -> f32 {
+    if gap == 0.0 {
+        base
+    } else {
+        (base + gap * step).clamp(min, max)
+    }
+}
+
+/// Adjusts the AI's mistake chances, reaction time and serves to the score
+/// gap.
+///
+/// The adjustment applied last time is tracked, so the base values in
+/// [`AiConfig`] and [`AiServeConfig`] are restored as the gap closes or the
+/// feature is disabled, and other changes to the configs are kept.
+fn apply_rubber_band(
+    rubber_band: Res<RubberBandConfig>,
+    roles: Res<PlayerRoles>,
+    score: Res<Score>,
+    mut ai_config: ResMut<AiConfig>,
+    mut serve_config: ResMut<AiServeConfig>,
+    mut applied: Local<(f32, f32, f32, f32)>,
+) {
+    // Points the human is behind by, negative when they're ahead
+    let deficit = match (rubber_band.enabled, roles.sole_human()) {
+        (true, Some(Player::P1)) => score.p2 as f32 - score.p1 as f32,
+        (true, Some(Player::P2)) => score.p1 as f32 - score.p2 as f32,
+        _ => 0.0,
+    };
+    // Only the part of the gap beyond the dead zone counts
+    let gap = deficit.signum() * (deficit.abs() - rubber_band.dead_zone as f32).max(0.0);
+
+    let base_error = ai_config.error_chance - applied.0;
+    let base_miss = ai_config.miss_chance - applied.1;
+    let base_update_rate = ai_config.update_rate - applied.2;
+    let base_fast_serve = serve_config.fast_weight - applied.3;
+
+    let error = rubber_band_value(
+        base_error,
+        gap,
+        rubber_band.error_step,
+        rubber_band.error_bounds,
+    );
+    let miss = rubber_band_value(
+        base_miss,
+        gap,
+        rubber_band.miss_step,
+        rubber_band.miss_bounds,
+    );
+    let update_rate = rubber_band_value(
+        base_update_rate,
+        gap,
+        rubber_band.update_rate_step,
+        rubber_band.update_rate_bounds,
+    );
+
+    let fast_serve = rubber_band_value(
+        base_fast_serve,
+        gap,
+        rubber_band.fast_serve_step,
+        rubber_band.fast_serve_bounds,
+    );
+
+    ai_config.error_chance = error;
+    ai_config.miss_chance = miss;
+    ai_config.update_rate = update_rate;
+    serve_config.fast_weight = fast_serve;
+    *applied = (
+        error - base_error,
+        miss - base_miss,
+        update_rate - base_update_rate,
+        fast_serve - base_fast_serve,
+    );
+}
+
+/// Query filter for paddles played at this machine's keyboard
+type LocalHumanPaddle = (With<Player>, Without<AiPaddle>, Without<RemotePaddle>);
+
+/// Query data for a paddle moved by `paddle_movement`
+type MovingPaddle<'a> = (
+    &'a Player,
+    &'a mut KinematicCharacterController,
+    Option<&'a AiPaddle>,
+    Option<&'a GhostPaddle>,
+    Option<&'a RemotePaddle>,
+    &'a Transform,
+    &'a PaddleStats,
+    &'a mut Charge,
+    &'a mut Dash,
+    &'a mut PaddleVelocity,
+    Has<Stunned>,
+);
+
+/// Unified system that handles human, AI and online paddle movement
+///
+/// Paddles steered with keys, locally or online, build up and lose speed
+/// gradually under [`PaddleConfig::momentum`], see [`keyed_step`].
+///
+/// A dashing paddle moves faster for the length of the dash, and a stunned
+/// one doesn't move at all, whoever controls it. Every paddle's move is
+/// clamped so it stops flush with the top and bottom walls instead of
+/// sticking against them.
+#[allow(clippy::too_many_arguments)]
 fn paddle_movement(
+    board: Res<BoardConfig>,
     config: Res<PaddleConfig>,
+    ai_config: Res<AiConfig>,
     input: Res<ButtonInput<KeyCode>>,
+    remote_input: Res<RemoteInput>,
+    touch: Res<TouchTarget>,
     time: Res<Time>,
-    mut query: Query<(
-        &Player,
-        &mut KinematicCharacterController,
-        Option<&AiPaddle>,
-        &Transform,
-    )>,
+    mut query: Query<MovingPaddle>,
 ) {
-    for (player, mut controller, ai, paddle_transform) in query.iter_mut() {
+    for (
+        player,
+        mut controller,
+        ai,
+        ghost,
+        remote,
+        paddle_transform,
+        stats,
+        mut charge,
+        mut dash,
+        mut velocity,
+        stunned,
+    ) in query.iter_mut()
+    {
+        dash.burst.tick(time.delta());
+        dash.cooldown.tick(time.delta());
+        let speed = if dash.is_dashing() {
+            stats.speed * config.dash_speed
+        } else {
+            stats.speed
+        };
+
         let mut translation = Vec2::ZERO;
-        let move_amount = config.speed * time.delta_secs();
+        let move_amount = speed * time.delta_secs();
+
+        let ghost_target = ghost.and_then(|ghost| ghost.target);
 
-        match (player, ai) {
+        // Keys held by whoever steers the paddle by hand, 1.0 for up and
+        // -1.0 for down
+        let mut axis = None;
+
+        match (player, ai, ghost_target) {
+            // Online opponent, moved by the keys their game sends
+            _ if remote.is_some() => {
+                axis = Some(key_axis(remote_input.up, remote_input.down));
+            }
             // Human player input handling
-            (Player::P1, None) => {
-                if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
-                    translation.y += move_amount;
+            (Player::P1, None, _) => {
+                // Holding Shift builds up charge, releasing it lets the charge go
+                if input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight) {
+                    charge.level = (charge.level + time.delta_secs() / config.charge_time).min(1.0);
+                } else {
+                    charge.level = 0.0;
+                }
+
+                axis = Some(key_axis(
+                    input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]),
+                    input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]),
+                ));
+
+                // A finger on the screen pulls the paddle toward it, at
+                // no more than the paddle's speed and never past a wall
+                if let Some(target_y) = touch.0 {
+                    let limit = paddle_limit(&board, stats.height);
+                    let remaining = target_y.clamp(-limit, limit) - paddle_transform.translation.y;
+                    translation.y += remaining.clamp(-move_amount, move_amount);
                 }
-                if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
-                    translation.y -= move_amount;
+            }
+            // A ghost heads for where the recorded human stood, with the
+            // same easing as the AI
+            (_, Some(_), Some(target_y)) => {
+                let remaining = target_y - paddle_transform.translation.y;
+                if remaining.abs() > ai_config.movement_deadzone {
+                    translation.y +=
+                        eased_step(remaining, move_amount, ai_config.ease_in_time, &ai_config);
                 }
             }
-            // AI player movement
-            (Player::P2, Some(ai)) => {
+            // AI player movement, eased toward the target to look more human
+            (_, Some(ai), None) => {
+                let current_y = paddle_transform.translation.y;
                 match ai.movement_state {
                     MovementState::MovingUp(target_y) if !ai.move_up_timer.finished() => {
                         // Stop moving if we've reached or passed the target
-                        if paddle_transform.translation.y < target_y {
-                            translation.y += move_amount;
-                        }
+                        let remaining = (target_y - current_y).max(0.0);
+                        let elapsed = ai.move_up_timer.elapsed_secs();
+                        translation.y += eased_step(remaining, move_amount, elapsed, &ai_config);
                     }
                     MovementState::MovingDown(target_y) if !ai.move_down_timer.finished() => {
                         // Stop moving if we've reached or passed the target
-                        if paddle_transform.translation.y > target_y {
-                            translation.y -= move_amount;
+                        let remaining = (target_y - current_y).min(0.0);
+                        let elapsed = ai.move_down_timer.elapsed_secs();
+                        translation.y += eased_step(remaining, move_amount, elapsed, &ai_config);
+                    }
+                    MovementState::Recentering(rest_y) => {
+                        // Headed either way, stopping once the rest
+                        // position is reached
+                        let timer = ai.timer_toward(current_y, rest_y);
+                        if !timer.finished() {
+                            let remaining = rest_y - current_y;
+                            let elapsed = timer.elapsed_secs();
+                            translation.y +=
+                                eased_step(remaining, move_amount, elapsed, &ai_config);
                         }
                     }
                     _ => {}
@@ -379,46 +1616,356 @@ fn paddle_movement(
             _ => {}
         }
 
+        if let Some(axis) = axis {
+            translation.y += keyed_step(
+                &mut velocity,
+                axis,
+                speed,
+                dash.is_dashing(),
+                &config,
+                time.delta_secs(),
+            );
+        }
+
+        if stunned {
+            translation = Vec2::ZERO;
+            velocity.0 = 0.0;
+        }
+
+        // Stop cleanly at the walls rather than pushing into them
+        let step = clamp_step(
+            paddle_transform.translation.y,
+            translation.y,
+            paddle_limit(&board, stats.height),
+        );
+        // A paddle stopped by a wall loses its speed, rather than keeping it
+        // to spend against the wall
+        if step.abs() < translation.y.abs() && time.delta_secs() > 0.0 {
+            velocity.0 = step / time.delta_secs();
+        }
+        translation.y = step;
         controller.translation = Some(translation);
     }
 }
 
-/// System that handles paddle-ball collisions and triggers punch animations
+/// System that lets the human player swing their paddle forward
+///
+/// Pressing E or Right Ctrl punches the paddle toward the center line.
+/// The swing lasts `punch_duration`, after which `update_paddle_punch`
+/// returns the paddle, and a new swing can't start until it has.
+fn handle_paddle_swing(
+    config: Res<PaddleConfig>,
+    input: Res<ButtonInput<KeyCode>>,
+    mut paddle_query: Query<(&mut Transform, &mut PunchState), LocalHumanPaddle>,
+) {
+    if !input.any_just_pressed([KeyCode::KeyE, KeyCode::ControlRight]) {
+        return;
+    }
+
+    for (mut transform, mut punch_state) in paddle_query.iter_mut() {
+        if punch_state.is_punching {
+            continue;
+        }
+        punch_state.is_punching = true;
+        punch_state.is_swinging = true;
+        punch_state.timer.reset();
+
+        let punch_direction = if transform.translation.x < 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+        transform.translation.x += config.punch_distance * punch_direction;
+    }
+}
+
+/// System that starts a dash when the human double-taps up or down.
+///
+/// Taps are read every frame so none are missed; the dash itself plays out
+/// in `paddle_movement`.
+fn handle_dash_taps(
+    config: Res<PaddleConfig>,
+    input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut paddle_query: Query<&mut Dash, LocalHumanPaddle>,
+) {
+    let up = input.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]);
+    let down = input.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]);
+    if !up && !down {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    for mut dash in paddle_query.iter_mut() {
+        dash.tap(up, now, config.dash_window);
+    }
+}
+
+/// System that handles paddle-ball collisions, records which player
+/// owns the ball and sends a [`PaddleHit`] for each hit
+///
+/// A charged paddle boosts the ball's speed above the normal cap for a
+/// short while, consuming its charge. A paddle that is mid-swing when it
+/// meets the ball boosts it further.
+///
+/// A paddle that takes the ball on its flat back or the very tip of its
+/// curve is stunned, judged from where the physics contact touched it.
+/// Under the arcade deflection rule, the same contact point sets the
+/// return's angle, keeping the ball's speed.
+///
+/// Paddles are only in collision groups with balls, so any contact that
+/// involves a paddle is a hit.
+#[allow(clippy::too_many_arguments)]
 fn handle_paddle_collisions(
+    mut commands: Commands,
     config: Res<PaddleConfig>,
+    rules: Res<RulesConfig>,
+    rapier_context: ReadDefaultRapierContext,
+    stun_material: Res<StunMaterial>,
     mut collision_events: EventReader<CollisionEvent>,
-    mut paddle_query: Query<(Entity, &mut Transform, &mut PunchState), With<Player>>,
-    ball_query: Query<Entity, With<Ball>>,
+    mut hits: EventWriter<PaddleHit>,
+    mut paddle_query: Query<HitPaddle>,
+    mut ball_query: Query<(Entity, &mut BallOwner, &mut Velocity, &Transform), With<Ball>>,
 ) {
-    let Ok(ball_entity) = ball_query.get_single() else {
+    if ball_query.is_empty() {
+        // Between points, drop contacts left over from the scored ball so
+        // they can't be mistaken for hits once the next ball is served
+        collision_events.clear();
         return;
-    };
+    }
 
     for collision_event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = collision_event {
-            // Skip if neither entity is the ball
-            if *e1 != ball_entity && *e2 != ball_entity {
+            // The ball is whichever side of the contact isn't the paddle,
+            // unless it has already left play
+            let ball = if ball_query.contains(*e1) { *e1 } else { *e2 };
+            let Ok((ball_entity, mut owner, mut velocity, ball_transform)) =
+                ball_query.get_mut(ball)
+            else {
                 continue;
-            }
+            };
 
-            for (paddle_entity, mut transform, mut punch_state) in paddle_query.iter_mut() {
-                if (paddle_entity == *e1 || paddle_entity == *e2) && !punch_state.is_punching {
-                    punch_state.is_punching = true;
-                    punch_state.timer.reset();
+            for (paddle_entity, player, punch_state, mut charge, transform, stats, mesh, stunned) in
+                paddle_query.iter_mut()
+            {
+                if paddle_entity != *e1 && paddle_entity != *e2 {
+                    continue;
+                }
 
-                    let punch_direction = if transform.translation.x < 0.0 {
-                        1.0
-                    } else {
-                        -1.0
-                    };
-                    transform.translation.x += config.punch_distance * punch_direction;
-                    break;
+                // Power-ups scale the paddle's height, and its collider with it
+                let half_height = stats.height * transform.scale.y / 2.0;
+                let contact = paddle_contact_point(&rapier_context, paddle_entity, ball_entity);
+                let stunning =
+                    contact.is_some_and(|point| is_stunning_contact(point, half_height, &config));
+                if stunning {
+                    match stunned {
+                        Some(mut stunned) => stunned.timer.reset(),
+                        None => {
+                            let flash = commands
+                                .spawn((
+                                    StunFlash,
+                                    Mesh2d(mesh.0.clone()),
+                                    MeshMaterial2d(stun_material.0.clone()),
+                                    // Just in front of the paddle
+                                    Transform::from_xyz(0.0, 0.0, 0.5),
+                                ))
+                                .id();
+                            commands
+                                .entity(paddle_entity)
+                                .add_child(flash)
+                                .insert(Stunned {
+                                    timer: Timer::from_seconds(
+                                        config.stun_duration,
+                                        TimerMode::Once,
+                                    ),
+                                    flash,
+                                });
+                        }
+                    }
                 }
+
+                // The last paddle to touch the ball owns it
+                owner.0 = Some(*player);
+
+                if let Some(point) = contact.filter(|_| rules.arcade_deflection) {
+                    velocity.linvel = arcade_deflection(
+                        point,
+                        half_height,
+                        transform.rotation,
+                        velocity.linvel.length(),
+                        rules.max_deflection_angle,
+                    );
+                }
+
+                // Release any stored charge into the ball, plus any swing
+                let charge_level = std::mem::take(&mut charge.level);
+                let swing_factor = if punch_state.is_swinging {
+                    config.swing_boost
+                } else {
+                    1.0
+                };
+                let factor = (1.0 + charge_level * config.charge_boost) * swing_factor;
+                if factor > 1.0 {
+                    velocity.linvel *= factor;
+                    commands
+                        .entity(ball_entity)
+                        .insert(SpeedBoost::new(factor, config.charge_boost_duration));
+                }
+
+                // Power-ups scale the collider itself, so the contact point
+                // only needs turning and moving into place
+                let position = contact.map_or(ball_transform.translation.truncate(), |point| {
+                    transform.translation.truncate()
+                        + (transform.rotation * point.extend(0.0)).truncate()
+                });
+                hits.send(PaddleHit {
+                    player: *player,
+                    ball_speed: velocity.linvel.length(),
+                    charge: charge_level,
+                    position,
+                });
+                break;
             }
         }
     }
 }
 
+/// Query data for a paddle `handle_paddle_collisions` checks for hits
+type HitPaddle<'a> = (
+    Entity,
+    &'a Player,
+    &'a PunchState,
+    &'a mut Charge,
+    &'a Transform,
+    &'a PaddleStats,
+    &'a Mesh2d,
+    Option<&'a mut Stunned>,
+);
+
+/// Where the ball touched a paddle, in the paddle's own coordinates with its
+/// flat back along x = 0 and its scoop toward positive x, or `None` if the
+/// physics engine has no contact point for the pair
+fn paddle_contact_point(context: &RapierContext, paddle: Entity, ball: Entity) -> Option<Vec2> {
+    let pair = context.contact_pair(paddle, ball)?;
+    let paddle_first = pair.collider1() == paddle;
+    for manifold in pair.manifolds() {
+        if let Some(point) = manifold.points().next() {
+            return Some(if paddle_first {
+                point.local_p1()
+            } else {
+                point.local_p2()
+            });
+        }
+    }
+    None
+}
+
+/// Velocity of a return under the arcade deflection rule, for a ball at
+/// `speed` that met a paddle `half_height` tall and turned by `rotation` at
+/// `point`, in the paddle's own coordinates.
+///
+/// The return leaves toward the paddle's scoop at an angle proportional to
+/// the contact's distance from the paddle's middle, reaching `max_angle` at
+/// the tips.
+fn arcade_deflection(
+    point: Vec2,
+    half_height: f32,
+    rotation: Quat,
+    speed: f32,
+    max_angle: f32,
+) -> Vec2 {
+    // Measured in the world, since right-hand paddles are turned around
+    let offset = (rotation * point.extend(0.0)).y / half_height.max(f32::EPSILON);
+    let facing = (rotation * Vec3::X).x.signum();
+    let angle = offset.clamp(-1.0, 1.0) * max_angle;
+    Vec2::new(facing * angle.cos(), angle.sin()) * speed
+}
+
+/// Whether a contact at `point` on a paddle `half_height` tall, in the
+/// paddle's own coordinates, is on its flat back or the tip of its curve
+fn is_stunning_contact(point: Vec2, half_height: f32, config: &PaddleConfig) -> bool {
+    point.x <= config.stun_back_depth || point.y.abs() >= half_height * config.stun_tip_fraction
+}
+
+/// Counts down stuns, flashing each stunned paddle red until it can move
+/// again.
+fn update_stuns(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut paddle_query: Query<(Entity, &mut Stunned)>,
+    mut flash_query: Query<&mut Visibility, With<StunFlash>>,
+) {
+    for (entity, mut stunned) in paddle_query.iter_mut() {
+        if stunned.timer.tick(time.delta()).finished() {
+            commands.entity(stunned.flash).despawn();
+            commands.entity(entity).remove::<Stunned>();
+            continue;
+        }
+
+        if let Ok(mut visibility) = flash_query.get_mut(stunned.flash) {
+            let flashes = (stunned.timer.elapsed_secs() / STUN_FLASH_PERIOD) as u32;
+            *visibility = if flashes.is_multiple_of(2) {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
+
+/// Creates the material stunned paddles flash in.
+fn setup_stun_material(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands.insert_resource(StunMaterial(materials.add(ColorMaterial::from(STUN_RED))));
+}
+
+/// System that starts a paddle's punch animation when it hits the ball.
+///
+/// Charged hits punch further. A paddle already punching carries on with
+/// its current punch.
+fn trigger_paddle_punch(
+    config: Res<PaddleConfig>,
+    mut hits: EventReader<PaddleHit>,
+    mut paddle_query: Query<(&Player, &mut Transform, &mut PunchState)>,
+) {
+    for hit in hits.read() {
+        for (player, mut transform, mut punch_state) in paddle_query.iter_mut() {
+            if *player != hit.player || punch_state.is_punching {
+                continue;
+            }
+
+            punch_state.is_punching = true;
+            punch_state.timer.reset();
+
+            let punch_direction = if transform.translation.x < 0.0 {
+                1.0
+            } else {
+                -1.0
+            };
+            let punch_distance = config.punch_distance * (1.0 + hit.charge * 2.0);
+            transform.translation.x += punch_distance * punch_direction;
+        }
+    }
+}
+
+/// System that returns paddles to rest when a new ball is served.
+///
+/// A punch triggered by the last contacts of the previous point would
+/// otherwise still be playing out as the new ball appears.
+fn reset_punch_on_serve(
+    mut serves: EventReader<ServeStarted>,
+    mut paddle_query: Query<(&mut Transform, &mut PunchState)>,
+) {
+    if serves.read().count() == 0 {
+        return;
+    }
+
+    for (mut transform, mut punch_state) in paddle_query.iter_mut() {
+        transform.translation.x = punch_state.rest_x;
+        punch_state.is_punching = false;
+        punch_state.is_swinging = false;
+    }
+}
+
 /// System to reset paddle position after punch animation
 fn update_paddle_punch(
     time: Res<Time>,
@@ -430,27 +1977,266 @@ fn update_paddle_punch(
             if punch_state.timer.finished() {
                 transform.translation.x = punch_state.rest_x;
                 punch_state.is_punching = false;
+                punch_state.is_swinging = false;
             }
         }
     }
 }
 
-/// Creates mesh and compound collider for paddle
-fn create_paddle_mesh(
+/// Component holding where a ball or paddle was at the end of the previous
+/// physics step, so [`recover_tunneled_balls`] can tell what a ball passed
+/// through during a step
+#[derive(Component, Debug)]
+struct LastStepPosition(Vec2);
+
+/// Query data for a ball that may need recovering from behind a paddle
+type RecoverableBall<'a> = (
+    Entity,
+    &'a mut Transform,
+    &'a mut Velocity,
+    &'a mut BallOwner,
+    Option<&'a mut LastStepPosition>,
+);
+
+/// Query data for a paddle a ball may have slipped through
+type TunneledPaddle<'a> = (
+    Entity,
+    &'a Player,
+    &'a Transform,
+    &'a PaddleStats,
+    Option<&'a mut LastStepPosition>,
+);
+
+/// Safety net for balls that slip through a paddle.
+///
+/// A punch teleports the paddle forward, and at high speeds the solver can
+/// push an overlapping ball out through the paddle's back instead of its
+/// front. Such a ball crosses the paddle's back within its height during a
+/// step and carries on for the wall, without having been returned by that
+/// paddle. Rather than let it score, the ball is put back in front of the
+/// paddle and sent back toward the center, as if the paddle had returned it.
+///
+/// The crossing is found from where the ball and paddle were at the end of
+/// the previous step, so a ball that missed the paddle is left alone even
+/// if the paddle then slides across behind it. Balls that miss the paddle
+/// entirely, or that it really touched, score as usual. A ball or paddle
+/// only just put into play is checked from its next step on.
+fn recover_tunneled_balls(
+    mut commands: Commands,
+    config: Res<PaddleConfig>,
+    ball_config: Res<BallConfig>,
+    side: Res<PlayerSide>,
+    mut ball_query: Query<RecoverableBall, (With<Ball>, Without<Player>)>,
+    mut paddle_query: Query<TunneledPaddle, Without<Ball>>,
+) {
+    for (entity, mut ball_transform, mut velocity, mut owner, last_step) in ball_query.iter_mut() {
+        let Some(mut last_step) = last_step else {
+            let position = ball_transform.translation.truncate();
+            commands.entity(entity).insert(LastStepPosition(position));
+            continue;
+        };
+
+        for (_, player, paddle_transform, stats, paddle_last_step) in paddle_query.iter() {
+            let Some(paddle_last_step) = paddle_last_step else {
+                continue;
+            };
+            // The direction from the paddle's flat back toward the center
+            let facing = if side.is_left(*player) { 1.0 } else { -1.0 };
+            let paddle_pos = paddle_transform.translation.truncate();
+            let ball_pos = ball_transform.translation.truncate();
+
+            // The ball's offset from the paddle's back, toward the center,
+            // at the start and end of the step
+            let start = last_step.0 - paddle_last_step.0;
+            let end = ball_pos - paddle_pos;
+            let crossed = start.x * facing >= 0.0 && end.x * facing < 0.0;
+            if !crossed {
+                continue;
+            }
+
+            // Power-ups scale the paddle's height
+            let half_height = stats.height * paddle_transform.scale.y / 2.0;
+            // How far through the step the ball crossed the paddle's back
+            let t = start.x / (start.x - end.x);
+            let crossing_y = start.y + (end.y - start.y) * t;
+            let within_height = crossing_y.abs() <= half_height;
+            let heading_for_wall = velocity.linvel.x * facing < 0.0;
+            let returned = owner.0 == Some(*player);
+
+            if within_height && heading_for_wall && !returned {
+                // Place the ball just clear of the paddle's deepest point
+                let clearance = config.curve_depth + ball_config.size;
+                ball_transform.translation.x = paddle_pos.x + clearance * facing;
+                velocity.linvel.x = -velocity.linvel.x;
+                owner.0 = Some(*player);
+            }
+        }
+
+        last_step.0 = ball_transform.translation.truncate();
+    }
+
+    for (entity, _, paddle_transform, _, last_step) in paddle_query.iter_mut() {
+        let position = paddle_transform.translation.truncate();
+        match last_step {
+            Some(mut last_step) => last_step.0 = position,
+            None => {
+                commands.entity(entity).insert(LastStepPosition(position));
+            }
+        }
+    }
+}
+
+/// Largest allowed angle between the collider's surface normal and the true
+/// curve's normal at any height, in radians (about 5 degrees)
+const MAX_NORMAL_DEVIATION: f32 = 0.09;
+
+/// Depth of the paddle's curved front at height `y`, measured from its flat back
+fn paddle_curve(y: f32, config: &PaddleConfig) -> f32 {
+    // Parabolic curve function for paddle front
+    let normalized_y = (y + config.height / 2.0) / config.height;
+    config.curve_depth * (4.0 * normalized_y * (1.0 - normalized_y))
+}
+
+/// Points along the paddle's curved front, from bottom to top
+fn paddle_outline(segments: usize, config: &PaddleConfig) -> Vec<Vec2> {
+    let segment_height = config.height / (segments as f32);
+    (0..=segments)
+        .map(|i| {
+            let y = -config.height / 2.0 + (i as f32 * segment_height);
+            Vec2::new(paddle_curve(y, config), y)
+        })
+        .collect()
+}
+
+/// Largest angle between the surface normal of `outline`'s straight edges
+/// and the normal of the true curve, sampled at many heights along the paddle
+fn max_normal_deviation(outline: &[Vec2], config: &PaddleConfig) -> f32 {
+    const SAMPLES: usize = 50;
+
+    (0..=SAMPLES)
+        .map(|i| {
+            let y = -config.height / 2.0 + config.height * (i as f32 / SAMPLES as f32);
+
+            // The curve's slope is the derivative of `paddle_curve`
+            let normalized_y = (y + config.height / 2.0) / config.height;
+            let curve_slope = config.curve_depth * 4.0 * (1.0 - 2.0 * normalized_y) / config.height;
+
+            // The edge of the outline that spans this height
+            let edge = outline
+                .windows(2)
+                .find(|edge| y <= edge[1].y)
+                .unwrap_or(&outline[outline.len() - 2..]);
+            let edge_slope = (edge[1].x - edge[0].x) / (edge[1].y - edge[0].y);
+
+            // Normals are perpendicular to the slopes, so they differ by the same angle
+            (curve_slope.atan() - edge_slope.atan()).abs()
+        })
+        .fold(0.0, f32::max)
+}
+
+/// Creates the paddle's physics shape.
+///
+/// The paddle's front bulges outward, so the whole outline is convex and a
+/// single convex hull over a handful of points along the curve matches it
+/// closely. This keeps the broadphase cheap compared to one collider per
+/// mesh segment, and gives the ball smoothly varying contact normals. The
+/// hull only straightens the curve between points, which debug builds check
+/// stays within [`MAX_NORMAL_DEVIATION`].
+fn create_paddle_collider(config: &PaddleConfig) -> Collider {
+    let segments = config.collider_segment_count();
+    if segments != config.collider_segments {
+        warn!(
+            "Paddle collider segments must be between {} and {}, using {} instead of {}",
+            MIN_COLLIDER_SEGMENTS, MAX_COLLIDER_SEGMENTS, segments, config.collider_segments
+        );
+    }
+    let outline = paddle_outline(segments, config);
+
+    debug_assert!(
+        max_normal_deviation(&outline, config) <= MAX_NORMAL_DEVIATION,
+        "paddle collider with {} segments bends bounces too far from the drawn curve",
+        segments
+    );
+
+    // A flat paddle has no area for a hull, so fall back to its front edge
+    Collider::convex_hull(&outline).unwrap_or_else(|| Collider::polyline(outline, None))
+}
+
+/// Everything a paddle's mesh and collider are built from, with the
+/// lengths stored as bits so shapes can be hashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct PaddleShapeKey {
+    /// Bits of the paddle's height
+    height: u32,
+    /// Bits of the depth of the paddle's curve
+    curve_depth: u32,
+    /// Segments the mesh is drawn with, after clamping
+    mesh_segments: usize,
+    /// Edges the collider is built from, after clamping
+    collider_segments: usize,
+}
+
+impl PaddleShapeKey {
+    /// Key for the shape `config` describes
+    fn new(config: &PaddleConfig) -> Self {
+        Self {
+            height: config.height.to_bits(),
+            curve_depth: config.curve_depth.to_bits(),
+            mesh_segments: config.mesh_segment_count(),
+            collider_segments: config.collider_segment_count(),
+        }
+    }
+}
+
+/// Resource caching the mesh and collider of every paddle shape built so
+/// far, so paddles spawned for each new game reuse them rather than
+/// rebuilding a detailed mesh every time.
+///
+/// A shape's mesh and collider are always built together from the same
+/// configuration, so the pair handed out stays in sync. Only a few shapes
+/// are ever built, one per handicapped height and paddle configuration, so
+/// the cache is never trimmed.
+#[derive(Resource, Default)]
+pub(crate) struct PaddleShapes(HashMap<PaddleShapeKey, (Handle<Mesh>, Collider)>);
+
+impl PaddleShapes {
+    /// Returns the mesh and collider for `config`'s shape, building them
+    /// the first time the shape is asked for
+    fn get_or_build(
+        &mut self,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        config: &PaddleConfig,
+    ) -> (Handle<Mesh>, Collider) {
+        self.0
+            .entry(PaddleShapeKey::new(config))
+            .or_insert_with(|| {
+                (
+                    create_paddle_mesh(meshes, config),
+                    create_paddle_collider(config),
+                )
+            })
+            .clone()
+    }
+}
+
+/// Creates mesh for paddle
+pub(crate) fn create_paddle_mesh(
     meshes: &mut ResMut<Assets<Mesh>>,
     config: &PaddleConfig,
-) -> (Handle<Mesh>, Vec<(Vec2, f32, Collider)>) {
-    let mut compound_collider = vec![];
+) -> Handle<Mesh> {
+    let segments = config.mesh_segment_count();
+    if segments != config.mesh_segments {
+        warn!(
+            "Paddle mesh segments must be between {} and {}, using {} instead of {}",
+            MIN_MESH_SEGMENTS, MAX_MESH_SEGMENTS, segments, config.mesh_segments
+        );
+    }
     let mut all_vertices = vec![];
 
-    // Generate segments for the scoop
-    for i in 0..config.segments {
-        let vertices = generate_segment_vertices(i, config.segments, config);
+    // Generate segments for the scoop, four vertices each
+    for i in 0..segments {
+        let vertices = generate_segment_vertices(i, segments, config);
         all_vertices.extend(vertices.iter().cloned());
-
-        if let Some(collider) = Collider::convex_hull(&vertices) {
-            compound_collider.push((Vec2::ZERO, 0.0, collider));
-        }
     }
 
     // Create the mesh for visualization
@@ -464,7 +2250,7 @@ fn create_paddle_mesh(
 
     // Generate indices for triangulation
     let mut indices = Vec::new();
-    for i in 0..config.segments {
+    for i in 0..segments {
         let base = i as u32 * 4;
         // First triangle
         indices.extend_from_slice(&[base, base + 1, base + 2]);
@@ -475,7 +2261,7 @@ fn create_paddle_mesh(
     mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vertices_3d);
     mesh.insert_indices(Indices::U32(indices));
 
-    (meshes.add(mesh), compound_collider)
+    meshes.add(mesh)
 }
 
 /// Helper function to generate vertices for a segment of the scoop paddle shape
@@ -487,12 +2273,7 @@ fn generate_segment_vertices(
     let segment_height = config.height / (total_segments as f32);
     let y_start = -config.height / 2.0 + (index as f32 * segment_height);
     let y_end = y_start + segment_height;
-
-    // Parabolic curve function for paddle front
-    let curve = |y: f32| -> f32 {
-        let normalized_y = (y + config.height / 2.0) / config.height;
-        config.curve_depth * (4.0 * normalized_y * (1.0 - normalized_y))
-    };
+    let curve = |y: f32| paddle_curve(y, config);
 
     vec![
         Vec2::new(0.0, y_start),            // Back left (flat)
@@ -503,20 +2284,22 @@ fn generate_segment_vertices(
 }
 
 /// Creates a paddle entity with all necessary components
+///
+/// The paddle is placed on the side `side` assigns to the player, with
+/// right-hand paddles rotated so the scoop faces the center.
+#[allow(clippy::too_many_arguments)]
 fn create_paddle(
     commands: &mut Commands,
+    board: &BoardConfig,
     config: &PaddleConfig,
     mesh_handle: Handle<Mesh>,
     material_handle: Handle<ColorMaterial>,
-    is_player_one: bool,
-    compound_collider: Vec<(Vec2, f32, Collider)>,
+    player: Player,
+    side: PlayerSide,
+    collider: Collider,
 ) -> Entity {
-    let x_pos = if is_player_one {
-        config.left_x
-    } else {
-        config.right_x
-    };
-    let rotation = if is_player_one {
+    let x_pos = side.paddle_x(player, config, board);
+    let rotation = if side.is_left(player) {
         Quat::IDENTITY
     } else {
         Quat::from_rotation_z(std::f32::consts::PI)
@@ -528,6 +2311,7 @@ fn create_paddle(
     entity
         .insert(Mesh2d(mesh_handle))
         .insert(MeshMaterial2d(material_handle))
+        .insert(Themed::Paddle)
         .insert(Transform::from_xyz(x_pos, 0.0, 0.0).with_rotation(rotation))
         .insert(GlobalTransform::default())
         .insert(Visibility::default())
@@ -537,17 +2321,24 @@ fn create_paddle(
     // Add physics components
     entity
         .insert(RigidBody::KinematicPositionBased)
-        .insert(KinematicCharacterController::default())
-        .insert(Collider::compound(compound_collider))
+        .insert(KinematicCharacterController {
+            // Only the ball can get in the paddle's way
+            filter_groups: Some(paddle_collision_groups()),
+            ..default()
+        })
+        .insert(collider)
+        .insert(Restitution {
+            coefficient: config.restitution,
+            combine_rule: CoefficientCombineRule::Multiply,
+        })
+        .insert(paddle_collision_groups())
+        // Sweep the paddle's motion too, so the ball's CCD sees where it moved
+        .insert(Ccd::enabled())
         .insert(ActiveEvents::COLLISION_EVENTS)
         .insert(AdditionalMassProperties::Mass(config.mass));
 
     // Add player-specific components
-    if is_player_one {
-        entity.insert(Player::P1);
-    } else {
-        entity.insert(Player::P2).insert(AiPaddle::default());
-    }
+    entity.insert(player);
 
     // Add punch state
     entity.insert(PunchState {
@@ -555,40 +2346,230 @@ fn create_paddle(
         ..default()
     });
 
+    // Add charge shot, dash and momentum state
+    entity.insert((
+        Charge::default(),
+        Dash::new(config),
+        PaddleVelocity::default(),
+    ));
+
     entity.id()
 }
 
-/// Spawns both player paddles: human P1 on left and AI P2 on right
+/// Spawns `player`'s paddle at the height and speed its handicap gives it,
+/// with the mesh and collider for that height from [`PaddleShapes`]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_paddle(
+    commands: &mut Commands,
+    shapes: &mut PaddleShapes,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    board: &BoardConfig,
+    config: &PaddleConfig,
+    material_handle: Handle<ColorMaterial>,
+    player: Player,
+    side: PlayerSide,
+    handicap: Handicap,
+) -> Entity {
+    let stats = PaddleStats::new(config, handicap);
+    let paddle_config = PaddleConfig {
+        height: stats.height,
+        ..config.clone()
+    };
+    let (mesh_handle, collider) = shapes.get_or_build(meshes, &paddle_config);
+
+    let entity = create_paddle(
+        commands,
+        board,
+        &paddle_config,
+        mesh_handle,
+        material_handle,
+        player,
+        side,
+        collider,
+    );
+    commands.entity(entity).insert(stats);
+    entity
+}
+
+/// Spawns the player paddles for the selected game mode: P1 on the chosen
+/// side and, except in survival mode, P2 on the other. Paddles controlled
+/// by the computer according to [`PlayerRoles`] get an AI controller, a
+/// ghost also gets its recording, and a paddle played online is marked as
+/// remote.
+///
+/// Each paddle's mesh and collider are built from its own height, after the
+/// player's handicap, and kept in [`PaddleShapes`] for the next game.
+#[allow(clippy::too_many_arguments)]
 fn spawn_players(
     mut commands: Commands,
+    mut shapes: ResMut<PaddleShapes>,
+    board: Res<BoardConfig>,
+    config: Res<PaddleConfig>,
+    mode: Res<GameMode>,
+    side: Res<PlayerSide>,
+    roles: Res<PlayerRoles>,
+    handicaps: Res<Handicaps>,
+    theme: Res<Theme>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-    let config = PaddleConfig::default();
-
-    // Create paddle mesh and collider
-    let (mesh_handle, compound_collider) = create_paddle_mesh(&mut meshes, &config);
-    let material_handle = materials.add(ColorMaterial::from(Color::WHITE));
-
-    // Spawn player 1 (left paddle)
-    create_paddle(
-        &mut commands,
-        &config,
-        mesh_handle.clone(),
-        material_handle.clone(),
-        true,
-        compound_collider.clone(),
-    );
+    let material_handle = materials.add(ColorMaterial::from(theme.colors().paddle));
 
-    // Spawn player 2 (right paddle)
-    create_paddle(
-        &mut commands,
-        &config,
-        mesh_handle,
-        material_handle,
-        false,
-        compound_collider,
-    );
+    for player in [Player::P1, Player::P2] {
+        // The player defends alone in survival mode
+        if player == Player::P2 && *mode == GameMode::Survival {
+            continue;
+        }
+
+        let entity = spawn_paddle(
+            &mut commands,
+            &mut shapes,
+            &mut meshes,
+            &board,
+            &config,
+            material_handle.clone(),
+            player,
+            *side,
+            handicaps.get(player),
+        );
+
+        match roles.controller(player) {
+            Controller::Human => {}
+            Controller::Ai => {
+                commands.entity(entity).insert(AiPaddle::default());
+            }
+            Controller::Ghost => {
+                commands
+                    .entity(entity)
+                    .insert((AiPaddle::default(), GhostPaddle::default()));
+            }
+            Controller::Remote => {
+                commands.entity(entity).insert(RemotePaddle);
+            }
+        }
+    }
+}
+
+/// Removes the paddles when returning to the menu, so the next
+/// game can spawn the paddles its mode needs
+fn despawn_players(mut commands: Commands, query: Query<Entity, With<Player>>) {
+    for entity in query.iter() {
+        // Along with any stun flash over the paddle
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Spawns the charge bar UI near the human player's edge of the screen.
+///
+/// The bar is an outlined column whose fill grows from the bottom as the
+/// human player's shot charges up.
+fn spawn_charge_bar(mut commands: Commands, side: Res<PlayerSide>, theme: Res<Theme>) {
+    let (left, right) = match *side {
+        PlayerSide::Left => (Val::Px(20.0), Val::Auto),
+        PlayerSide::Right => (Val::Auto, Val::Px(20.0)),
+    };
+
+    commands
+        .spawn((
+            ChargeBar,
+            Node {
+                position_type: PositionType::Absolute,
+                left,
+                right,
+                top: Val::Percent(40.0),
+                width: Val::Px(12.0),
+                height: Val::Px(120.0),
+                border: UiRect::all(Val::Px(2.0)),
+                // Fill grows upward from the bottom
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::FlexEnd,
+                ..default()
+            },
+            BorderColor(theme.colors().text),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ChargeBarFill,
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(0.0),
+                    ..default()
+                },
+                BackgroundColor(RUST_ORANGE),
+            ));
+        });
+}
+
+/// Updates the charge bar fill to match the human player's charge.
+fn update_charge_bar(
+    paddle_query: Query<&Charge, LocalHumanPaddle>,
+    mut fill_query: Query<&mut Node, With<ChargeBarFill>>,
+) {
+    let Ok(charge) = paddle_query.get_single() else {
+        return;
+    };
+    for mut node in fill_query.iter_mut() {
+        node.height = Val::Percent(charge.level * 100.0);
+    }
+}
+
+/// Removes the charge bar UI when leaving gameplay.
+fn cleanup_charge_bar(mut commands: Commands, query: Query<Entity, With<ChargeBar>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Spawns a dash pip for each paddle.
+fn spawn_dash_pips(mut commands: Commands, paddle_query: Query<Entity, With<Dash>>) {
+    for paddle in paddle_query.iter() {
+        commands.spawn((
+            DashPip(paddle),
+            Sprite {
+                custom_size: Some(Vec2::splat(DASH_PIP_SIZE)),
+                ..default()
+            },
+            Transform::default(),
+        ));
+    }
+}
+
+/// Keeps each dash pip above its paddle, filling up and brightening as the
+/// cooldown runs out.
+fn update_dash_pips(
+    theme: Res<Theme>,
+    paddle_query: Query<(&Transform, &PaddleStats, &Dash), Without<DashPip>>,
+    mut pip_query: Query<(&DashPip, &mut Transform, &mut Sprite)>,
+) {
+    let color = theme.colors().paddle;
+    for (pip, mut transform, mut sprite) in pip_query.iter_mut() {
+        let Ok((paddle_transform, stats, dash)) = paddle_query.get(pip.0) else {
+            continue;
+        };
+
+        // Power-ups scale the paddle's height
+        let top = stats.height * paddle_transform.scale.y / 2.0;
+        transform.translation = Vec3::new(
+            paddle_transform.translation.x,
+            paddle_transform.translation.y + top + DASH_PIP_GAP,
+            1.0,
+        );
+
+        let progress = dash.cooldown.fraction();
+        sprite.custom_size = Some(Vec2::new(DASH_PIP_SIZE * progress, DASH_PIP_SIZE));
+        sprite.color = if dash.is_ready() {
+            color
+        } else {
+            color.with_alpha(DASH_PIP_COOLDOWN_ALPHA)
+        };
+    }
+}
+
+/// Removes the dash pips when leaving gameplay.
+fn cleanup_dash_pips(mut commands: Commands, query: Query<Entity, With<DashPip>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
 }
 
 /// Plugin that manages all player-related systems
@@ -599,20 +2580,270 @@ impl Plugin for PlayerPlugin {
         app
             // Initialize configuration resources
             .init_resource::<PaddleConfig>()
+            .init_resource::<RulesConfig>()
+            .init_resource::<Handicaps>()
             .init_resource::<AiConfig>()
-            // Add startup systems
-            .add_systems(Startup, spawn_players)
-            // Add gameplay systems that run during the Playing state
+            .init_resource::<RubberBandConfig>()
+            .init_resource::<AiAdaptation>()
+            .init_resource::<PaddleShapes>()
+            .add_event::<PaddleHit>()
+            .add_systems(Startup, setup_stun_material)
+            .init_resource::<PlayerSide>()
+            .init_resource::<PlayerRoles>()
+            .init_resource::<RemoteInput>()
+            // Paddles are spawned per game, once the mode has been chosen
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                spawn_players,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                spawn_players,
+            )
+            // A new match starts from the splash screen, the menu or a rematch
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                reset_adaptation,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                reset_adaptation,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                reset_adaptation,
+            )
+            // A restart starts a new match without leaving the Playing state
+            .add_systems(Update, reset_adaptation.run_if(on_event::<MatchRestarted>))
+            // The attract demo's paddles stay when the splash screen hands
+            // over to the menu
+            .add_systems(
+                OnEnter(GameState::Menu),
+                despawn_players.run_if(not(attract_running)),
+            )
+            // Add gameplay systems that run during the Playing state, and
+            // behind the splash screen and menu while the attract demo plays.
+            // Decisions and key presses are read every frame so none are missed
             .add_systems(
                 Update,
                 (
+                    reset_punch_on_serve,
+                    learn_from_returns,
                     ai_decision_making,
+                    ghost_decision_making,
+                    handle_paddle_swing,
+                    handle_dash_taps,
+                    update_stuns,
+                )
+                    .chain()
+                    .run_if(
+                        in_state(GameState::Playing)
+                            .or(attract_running)
+                            .and(not_replaying),
+                    ),
+            )
+            // Movement, hits and punch timing step with physics, so they
+            // feel the same at any frame rate
+            .add_systems(
+                FixedUpdate,
+                (
                     paddle_movement,
                     handle_paddle_collisions,
+                    trigger_paddle_punch,
                     update_paddle_punch,
                 )
                     .chain()
-                    .run_if(in_state(GameState::Playing)),
+                    .before(PhysicsSet::SyncBackend)
+                    .run_if(
+                        in_state(GameState::Playing)
+                            .or(attract_running)
+                            .and(not_replaying),
+                    ),
+            )
+            // Check the outcome of each step for balls that slipped through
+            .add_systems(
+                FixedUpdate,
+                recover_tunneled_balls
+                    .after(PhysicsSet::Writeback)
+                    .run_if(in_state(GameState::Playing).or(attract_running)),
+            )
+            .add_systems(Update, cycle_ai_aim)
+            // Difficulty only changes when a point is scored or the setting changes
+            .add_systems(
+                Update,
+                apply_rubber_band
+                    .run_if(resource_changed::<Score>.or(resource_changed::<RubberBandConfig>)),
+            )
+            // Charge bar UI for the human player
+            .add_systems(OnEnter(GameState::Playing), spawn_charge_bar)
+            .add_systems(
+                Update,
+                update_charge_bar.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_charge_bar)
+            // Dash cooldown pips above each paddle
+            .add_systems(OnEnter(GameState::Playing), spawn_dash_pips)
+            .add_systems(
+                Update,
+                update_dash_pips.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), cleanup_dash_pips);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// Games started when checking the paddle shape cache
+    const SPAWN_GAMES: usize = 10;
+
+    /// An app with what [`spawn_players`] reads, for a human against the AI
+    fn spawn_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<ColorMaterial>>()
+            .init_resource::<PaddleShapes>()
+            .init_resource::<BoardConfig>()
+            .init_resource::<PaddleConfig>()
+            .init_resource::<GameMode>()
+            .init_resource::<PlayerSide>()
+            .init_resource::<PlayerRoles>()
+            .init_resource::<Handicaps>()
+            .init_resource::<Theme>();
+        app
+    }
+
+    /// Spawns and despawns the paddles for [`SPAWN_GAMES`] games, emptying
+    /// the shape cache before each one unless `cached`. Returns the mesh of
+    /// every paddle spawned.
+    fn spawn_games(app: &mut App, cached: bool) -> Vec<Handle<Mesh>> {
+        let mut handles = Vec::new();
+        for _ in 0..SPAWN_GAMES {
+            if !cached {
+                app.world_mut().resource_mut::<PaddleShapes>().0.clear();
+            }
+            app.world_mut().run_system_once(spawn_players).unwrap();
+            handles.extend(
+                app.world_mut()
+                    .query_filtered::<&Mesh2d, With<Player>>()
+                    .iter(app.world())
+                    .map(|mesh| mesh.0.clone()),
             );
+            app.world_mut().run_system_once(despawn_players).unwrap();
+        }
+        handles
+    }
+
+    #[test]
+    fn cached_paddle_shapes_are_reused_between_games() {
+        let mut app = spawn_app();
+        let handles = spawn_games(&mut app, true);
+
+        // Both paddles of every game share the one mesh built for the first
+        assert_eq!(handles.len(), 2 * SPAWN_GAMES);
+        assert!(handles.iter().all(|handle| *handle == handles[0]));
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), 1);
+    }
+
+    #[test]
+    fn emptied_paddle_shapes_build_a_mesh_per_game() {
+        let mut app = spawn_app();
+        spawn_games(&mut app, false);
+        assert_eq!(app.world().resource::<Assets<Mesh>>().len(), SPAWN_GAMES);
+    }
+
+    /// Runs [`apply_rubber_band`] with adaptive difficulty on, once the
+    /// human P1 has `p1` points to the AI's `p2`, returning the AI's fast
+    /// serve weight
+    fn fast_serve_weight_at(app: &mut App, p1: u32, p2: u32) -> f32 {
+        {
+            let mut score = app.world_mut().resource_mut::<Score>();
+            score.p1 = p1;
+            score.p2 = p2;
+        }
+        app.update();
+        app.world().resource::<AiServeConfig>().fast_weight
+    }
+
+    #[test]
+    fn adaptive_difficulty_serves_fast_less_often_to_a_trailing_human() {
+        let mut app = App::new();
+        app.init_resource::<Score>()
+            .init_resource::<PlayerRoles>()
+            .init_resource::<AiConfig>()
+            .init_resource::<AiServeConfig>()
+            .insert_resource(RubberBandConfig {
+                enabled: true,
+                ..default()
+            })
+            .add_systems(Update, apply_rubber_band);
+        let base = AiServeConfig::default().fast_weight;
+
+        // Within the dead zone the AI serves as configured
+        assert_eq!(fast_serve_weight_at(&mut app, 0, 2), base);
+        // Further behind, fast serves get rarer
+        assert!(fast_serve_weight_at(&mut app, 0, 6) < base);
+        // Ahead, they get more common
+        assert!(fast_serve_weight_at(&mut app, 6, 0) > base);
+        // And the weight is restored once the gap closes
+        assert!((fast_serve_weight_at(&mut app, 6, 6) - base).abs() < 1e-6);
+    }
+
+    #[test]
+    fn paddle_limit_keeps_every_handicapped_paddle_off_the_walls() {
+        let board = BoardConfig::default();
+        let config = PaddleConfig::default();
+        for steps in 0..=10 {
+            let handicap = Handicap {
+                height: MIN_HANDICAP + steps as f32 * HANDICAP_STEP,
+                ..Default::default()
+            };
+            let height = PaddleStats::new(&config, handicap).height;
+            let limit = paddle_limit(&board, height);
+
+            // At the limit the paddle's end touches the wall's inside face
+            assert!((limit + height / 2.0 - board.inner_half_height()).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn paddle_limit_is_zero_for_a_paddle_taller_than_the_board() {
+        let board = BoardConfig::default();
+        assert_eq!(paddle_limit(&board, board.height * 2.0), 0.0);
+    }
+
+    #[test]
+    fn clamp_step_stops_at_either_wall() {
+        let limit = 3.0;
+
+        // Moves short of a wall are left alone
+        assert_eq!(clamp_step(0.0, 1.0, limit), 1.0);
+        assert_eq!(clamp_step(0.0, -1.0, limit), -1.0);
+
+        // Moves past a wall are shortened to reach it
+        assert_eq!(clamp_step(2.5, 1.0, limit), 0.5);
+        assert_eq!(clamp_step(-2.5, -1.0, limit), -0.5);
+
+        // A paddle at a wall can only move away from it
+        assert_eq!(clamp_step(limit, 1.0, limit), 0.0);
+        assert_eq!(clamp_step(-limit, -1.0, limit), 0.0);
+        assert_eq!(clamp_step(limit, -1.0, limit), -1.0);
     }
 }
@@ -0,0 +1,307 @@
+//! Power-up Module
+//!
+//! This module implements the arcade mode power-ups. Every so often a pickup
+//! token appears in the middle third of the board. When the ball passes
+//! through it, the player who last touched the ball is rewarded:
+//!
+//! - Grow tokens enlarge the owner's paddle
+//! - Shrink tokens shrink the opponent's paddle
+//!
+//! Effects are timed, visibly tint the affected paddle, and are cleared
+//! when a point ends or the game leaves the Playing state.
+
+use crate::ball::{Ball, BallOwner};
+use crate::board::{power_up_collision_groups, BoardConfig};
+use crate::mode::GameMode;
+use crate::palette::{RUST_ORANGE, VICTORY_GREEN};
+use crate::player::Player;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+/// Configuration for power-up spawning and effects
+#[derive(Debug, Resource)]
+pub struct PowerUpConfig {
+    /// Seconds between token spawn attempts
+    pub spawn_interval: f32,
+    /// Diameter of a token in world units
+    pub token_size: f32,
+    /// How long a paddle effect lasts in seconds
+    pub effect_duration: f32,
+    /// Paddle height multiplier for the grow reward
+    pub grow_factor: f32,
+    /// Paddle height multiplier for the shrink penalty
+    pub shrink_factor: f32,
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            spawn_interval: 8.0,
+            token_size: 0.5,
+            effect_duration: 10.0,
+            grow_factor: 1.25,
+            shrink_factor: 0.75,
+        }
+    }
+}
+
+/// The kind of effect a token grants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PowerUpKind {
+    Grow,   // Enlarges the ball owner's paddle
+    Shrink, // Shrinks the opponent's paddle
+}
+
+/// Component for a pickup token sitting on the board
+#[derive(Component, Debug)]
+struct PowerUpToken {
+    kind: PowerUpKind,
+}
+
+/// Component for a paddle currently affected by a power-up
+#[derive(Component, Debug)]
+struct PaddleEffect {
+    /// Time remaining on the effect
+    timer: Timer,
+    /// Material the paddle had before it was tinted
+    original_material: Handle<ColorMaterial>,
+}
+
+/// Resource holding the timer between token spawns
+#[derive(Resource)]
+struct PowerUpSpawner {
+    timer: Timer,
+}
+
+/// Shared assets for tokens and tinted paddles
+#[derive(Resource)]
+struct PowerUpAssets {
+    token_mesh: Handle<Mesh>,
+    grow_material: Handle<ColorMaterial>,
+    shrink_material: Handle<ColorMaterial>,
+}
+
+impl PowerUpAssets {
+    fn material(&self, kind: PowerUpKind) -> Handle<ColorMaterial> {
+        match kind {
+            PowerUpKind::Grow => self.grow_material.clone(),
+            PowerUpKind::Shrink => self.shrink_material.clone(),
+        }
+    }
+}
+
+/// Creates the spawn timer and shared power-up assets.
+fn setup_power_ups(
+    mut commands: Commands,
+    config: Res<PowerUpConfig>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(PowerUpSpawner {
+        timer: Timer::from_seconds(config.spawn_interval, TimerMode::Repeating),
+    });
+    commands.insert_resource(PowerUpAssets {
+        token_mesh: meshes.add(Circle::new(config.token_size / 2.0)),
+        // Green for rewards, orange for penalties
+        grow_material: materials.add(ColorMaterial::from(VICTORY_GREEN)),
+        shrink_material: materials.add(ColorMaterial::from(RUST_ORANGE)),
+    });
+}
+
+/// Spawns a token at a random position in the middle third of the board.
+///
+/// Only one token can be on the board at a time, and tokens only appear
+/// while a ball is in play.
+#[allow(clippy::too_many_arguments)]
+fn spawn_tokens(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<BoardConfig>,
+    config: Res<PowerUpConfig>,
+    assets: Res<PowerUpAssets>,
+    mut spawner: ResMut<PowerUpSpawner>,
+    ball_query: Query<(), With<Ball>>,
+    token_query: Query<(), With<PowerUpToken>>,
+) {
+    if !spawner.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    if ball_query.is_empty() || !token_query.is_empty() {
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let half_x = board.width / 6.0;
+    let half_y = (board.half_height() - config.token_size).max(0.0);
+    let position = Vec2::new(
+        rng.gen_range(-half_x..=half_x),
+        rng.gen_range(-half_y..=half_y),
+    );
+    let kind = if rng.gen_bool(0.5) {
+        PowerUpKind::Grow
+    } else {
+        PowerUpKind::Shrink
+    };
+
+    commands.spawn((
+        PowerUpToken { kind },
+        Mesh2d(assets.token_mesh.clone()),
+        MeshMaterial2d(assets.material(kind)),
+        // Drawn in front of the center line
+        Transform::from_xyz(position.x, position.y, 1.0),
+        // Sensors report overlaps without deflecting the ball
+        Collider::ball(config.token_size / 2.0),
+        Sensor,
+        power_up_collision_groups(),
+        ActiveEvents::COLLISION_EVENTS,
+    ));
+}
+
+/// Query data for paddles that can receive a power-up effect
+type PaddleEffectTarget<'a> = (
+    Entity,
+    &'a Player,
+    &'a mut Transform,
+    &'a mut MeshMaterial2d<ColorMaterial>,
+    Option<&'a mut PaddleEffect>,
+);
+
+/// Applies a token's effect when the ball passes through it.
+///
+/// The player who last touched the ball collects the token. A ball that
+/// nobody has returned yet passes through without effect.
+fn collect_tokens(
+    mut commands: Commands,
+    config: Res<PowerUpConfig>,
+    assets: Res<PowerUpAssets>,
+    mut collision_events: EventReader<CollisionEvent>,
+    ball_query: Query<&BallOwner, With<Ball>>,
+    token_query: Query<&PowerUpToken>,
+    mut paddle_query: Query<PaddleEffectTarget>,
+) {
+    let mut collected = Vec::new();
+
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = collision_event else {
+            continue;
+        };
+
+        // Find the ball and the token involved, in either order
+        let (owner, token_entity) = match (ball_query.get(*e1), ball_query.get(*e2)) {
+            (Ok(owner), _) => (owner, *e2),
+            (_, Ok(owner)) => (owner, *e1),
+            _ => continue,
+        };
+        let Ok(token) = token_query.get(token_entity) else {
+            continue;
+        };
+        let Some(owner) = owner.0 else {
+            continue;
+        };
+        if collected.contains(&token_entity) {
+            continue;
+        }
+        collected.push(token_entity);
+        commands.entity(token_entity).despawn();
+
+        let (target, factor) = match token.kind {
+            PowerUpKind::Grow => (owner, config.grow_factor),
+            PowerUpKind::Shrink => (owner.opponent(), config.shrink_factor),
+        };
+
+        for (entity, player, mut transform, mut material, effect) in paddle_query.iter_mut() {
+            if *player != target {
+                continue;
+            }
+
+            // Scaling the transform scales both the mesh and the collider
+            transform.scale.y = factor;
+
+            match effect {
+                // Refresh an existing effect, keeping the paddle's real material
+                Some(mut effect) => effect.timer.reset(),
+                None => {
+                    commands.entity(entity).insert(PaddleEffect {
+                        timer: Timer::from_seconds(config.effect_duration, TimerMode::Once),
+                        original_material: material.0.clone(),
+                    });
+                }
+            }
+            material.0 = assets.material(token.kind);
+        }
+    }
+}
+
+/// Counts down active paddle effects and restores paddles when they expire.
+fn update_paddle_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut PaddleEffect,
+        &mut Transform,
+        &mut MeshMaterial2d<ColorMaterial>,
+    )>,
+) {
+    for (entity, mut effect, mut transform, mut material) in query.iter_mut() {
+        if effect.timer.tick(time.delta()).finished() {
+            transform.scale.y = 1.0;
+            material.0 = effect.original_material.clone();
+            commands.entity(entity).remove::<PaddleEffect>();
+        }
+    }
+}
+
+/// Removes all tokens and paddle effects.
+///
+/// Runs when a point ends (no ball in play) and when leaving gameplay.
+fn clear_power_ups(
+    mut commands: Commands,
+    token_query: Query<Entity, With<PowerUpToken>>,
+    mut paddle_query: Query<(
+        Entity,
+        &PaddleEffect,
+        &mut Transform,
+        &mut MeshMaterial2d<ColorMaterial>,
+    )>,
+) {
+    for entity in token_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    for (entity, effect, mut transform, mut material) in paddle_query.iter_mut() {
+        transform.scale.y = 1.0;
+        material.0 = effect.original_material.clone();
+        commands.entity(entity).remove::<PaddleEffect>();
+    }
+}
+
+/// Plugin that manages arcade mode power-ups.
+///
+/// All power-up systems only run while playing in [`GameMode::Arcade`].
+pub struct PowerUpPlugin;
+
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PowerUpConfig>()
+            .add_systems(Startup, setup_power_ups)
+            // Spawn, collect and expire power-ups during arcade gameplay
+            .add_systems(
+                Update,
+                (spawn_tokens, collect_tokens, update_paddle_effects)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(resource_equals(GameMode::Arcade))),
+            )
+            // Effects only last for the point they were collected in
+            .add_systems(
+                Update,
+                clear_power_ups.run_if(
+                    in_state(GameState::Playing)
+                        .and(resource_equals(GameMode::Arcade))
+                        .and(not(any_with_component::<Ball>)),
+                ),
+            )
+            .add_systems(OnExit(GameState::Playing), clear_power_ups);
+    }
+}
@@ -0,0 +1,419 @@
+//! Profile Module
+//!
+//! This module keeps the player's lifetime statistics across runs, including:
+//! - Games played, won and lost
+//! - Points scored and conceded
+//! - The longest rally and the fastest ball ever played
+//! - The records broken during the current match, for the endgame screen
+//! - Resetting the profile from the main menu, after confirmation
+//!
+//! Only games with a single local human count toward the profile. It is
+//! saved through the storage module whenever play stops, so points from an
+//! abandoned match are kept too. A missing or unreadable profile starts
+//! afresh rather than stopping the game.
+//!
+//! The reset is deliberately hidden: Ctrl+Shift+Backspace in the menu
+//! asks for confirmation, and Y then clears the profile. Backspace
+//! keeps it, since N already toggles the sound effects.
+
+use crate::net::lobby_idle;
+use crate::options::settings_closed;
+use crate::player::{PaddleHit, PlayerRoles};
+use crate::score::{GameWon, PointScored, ServeStarted};
+use crate::storage;
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Key the profile is stored under
+const PROFILE_KEY: &str = "profile";
+
+/// Resource holding the player's lifetime statistics
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+// Fields added later are filled in, so older profiles still load
+#[serde(default)]
+pub struct Profile {
+    /// Matches finished
+    pub games_played: u32,
+    /// Matches won
+    pub games_won: u32,
+    /// Matches lost
+    pub games_lost: u32,
+    /// Points won
+    pub points_scored: u32,
+    /// Points conceded
+    pub points_conceded: u32,
+    /// Most paddle hits in a single rally
+    pub longest_rally: u32,
+    /// Fastest ball off a paddle, in world units per second
+    pub fastest_ball: f32,
+}
+
+impl Profile {
+    /// Loads the stored profile, or a fresh one if there is none or it
+    /// can't be read
+    fn load() -> Self {
+        storage::load(PROFILE_KEY).unwrap_or_default()
+    }
+
+    /// Compact summary for the menu
+    pub fn summary(&self) -> String {
+        format!(
+            "W {} – L {} · Best rally {}",
+            self.games_won, self.games_lost, self.longest_rally
+        )
+    }
+}
+
+/// Resource holding the lifetime records broken during the current match.
+///
+/// Records are only noted when they beat an earlier one, so a fresh profile
+/// doesn't celebrate every first rally.
+#[derive(Debug, Resource, Default)]
+pub struct NewRecords {
+    /// The new longest rally, if it was broken
+    pub longest_rally: Option<u32>,
+    /// The new fastest ball, if it was broken
+    pub fastest_ball: Option<f32>,
+}
+
+impl NewRecords {
+    /// Callout lines for the endgame screen, one per broken record
+    pub fn callouts(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(hits) = self.longest_rally {
+            lines.push(format!("New record! Longest rally: {}", hits));
+        }
+        if let Some(speed) = self.fastest_ball {
+            lines.push(format!("New record! Fastest ball: {:.1}", speed));
+        }
+        lines
+    }
+}
+
+/// Resource tracking whether the profile reset is waiting for confirmation
+#[derive(Debug, Resource, Default)]
+pub struct ProfileReset {
+    /// Whether the confirmation prompt is shown
+    confirming: bool,
+}
+
+/// Marker component for the reset confirmation prompt
+#[derive(Component)]
+struct ResetPrompt;
+
+/// Run condition that passes unless the profile reset is waiting for
+/// confirmation. The menu's own keys use this.
+pub fn reset_idle(reset: Res<ProfileReset>) -> bool {
+    !reset.confirming
+}
+
+/// Clears the current match's records when a match begins.
+fn start_records(mut records: ResMut<NewRecords>) {
+    *records = NewRecords::default();
+}
+
+/// Counts the human's points won and conceded.
+fn track_points(
+    roles: Res<PlayerRoles>,
+    mut profile: ResMut<Profile>,
+    mut points: EventReader<PointScored>,
+) {
+    let Some(human) = roles.sole_human() else {
+        points.clear();
+        return;
+    };
+
+    for point in points.read() {
+        if point.scorer == human {
+            profile.points_scored += 1;
+        } else {
+            profile.points_conceded += 1;
+        }
+    }
+}
+
+/// Measures rallies and hit speeds against the lifetime records.
+fn track_rallies(
+    roles: Res<PlayerRoles>,
+    mut profile: ResMut<Profile>,
+    mut records: ResMut<NewRecords>,
+    mut rally: Local<u32>,
+    mut serves: EventReader<ServeStarted>,
+    mut hits: EventReader<PaddleHit>,
+) {
+    if serves.read().count() > 0 {
+        *rally = 0;
+    }
+    if roles.sole_human().is_none() {
+        hits.clear();
+        return;
+    }
+
+    for hit in hits.read() {
+        *rally += 1;
+        if *rally > profile.longest_rally {
+            if profile.longest_rally > 0 {
+                records.longest_rally = Some(*rally);
+            }
+            profile.longest_rally = *rally;
+        }
+        if hit.ball_speed > profile.fastest_ball {
+            if profile.fastest_ball > 0.0 {
+                records.fastest_ball = Some(hit.ball_speed);
+            }
+            profile.fastest_ball = hit.ball_speed;
+        }
+    }
+}
+
+/// Counts the finished match and its result. Survival runs have no winner,
+/// so they only count as played.
+fn track_result(
+    roles: Res<PlayerRoles>,
+    mut profile: ResMut<Profile>,
+    mut wins: EventReader<GameWon>,
+) {
+    let Some(human) = roles.sole_human() else {
+        wins.clear();
+        return;
+    };
+
+    profile.games_played += 1;
+    if let Some(won) = wins.read().last() {
+        if won.winner == human {
+            profile.games_won += 1;
+        } else {
+            profile.games_lost += 1;
+        }
+    }
+}
+
+/// Saves the profile.
+fn save_profile(profile: Res<Profile>) {
+    storage::save(PROFILE_KEY, &*profile);
+}
+
+/// Opens the reset prompt on Ctrl+Shift+Backspace, then resets the profile
+/// on Y or keeps it on Backspace.
+fn handle_profile_reset(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut reset: ResMut<ProfileReset>,
+    mut profile: ResMut<Profile>,
+) {
+    if !reset.confirming {
+        let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        if ctrl && shift && keyboard.just_pressed(KeyCode::Backspace) {
+            reset.confirming = true;
+        }
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyY) {
+        *profile = Profile::default();
+        reset.confirming = false;
+    } else if keyboard.just_pressed(KeyCode::Backspace) {
+        reset.confirming = false;
+    }
+}
+
+/// Shows or hides the reset prompt to match the reset state.
+fn sync_reset_prompt(
+    mut commands: Commands,
+    reset: Res<ProfileReset>,
+    theme: Res<Theme>,
+    query: Query<Entity, With<ResetPrompt>>,
+) {
+    if !reset.confirming {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    if query.is_empty() {
+        commands.spawn((
+            ResetPrompt,
+            Themed::Text,
+            Text::new("Reset your lifetime stats? Y to reset, BACKSPACE to keep them"),
+            TextFont {
+                font_size: theme.font_size(30.0),
+                ..default()
+            },
+            TextColor(theme.colors().text),
+            TextLayout::new_with_justify(JustifyText::Center),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(20.0),
+                ..default()
+            },
+            // Drawn over the menu's background
+            GlobalZIndex(1),
+        ));
+    }
+}
+
+/// Closes the reset prompt when leaving the menu.
+fn cancel_profile_reset(mut reset: ResMut<ProfileReset>) {
+    reset.confirming = false;
+}
+
+/// Plugin that tracks and stores the player's lifetime statistics.
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        // Loaded up front so the menu can show it straight away
+        app.insert_resource(Profile::load())
+            .init_resource::<NewRecords>()
+            .init_resource::<ProfileReset>()
+            // A new match starts from the splash screen, the menu or a rematch
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                start_records,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                start_records,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                start_records,
+            )
+            .add_systems(
+                Update,
+                (track_points, track_rallies).run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnEnter(GameState::GameOver), track_result)
+            // Saved whenever play stops, rather than on every point
+            .add_systems(
+                Update,
+                save_profile
+                    .run_if(not(in_state(GameState::Playing)).and(resource_changed::<Profile>)),
+            )
+            .add_systems(
+                Update,
+                (
+                    handle_profile_reset.run_if(lobby_idle.and(settings_closed)),
+                    sync_reset_prompt.run_if(resource_changed::<ProfileReset>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(OnExit(GameState::Menu), cancel_profile_reset)
+            // The prompt may be closed after the menu's update stops
+            .add_systems(
+                Update,
+                sync_reset_prompt
+                    .run_if(not(in_state(GameState::Menu)).and(resource_changed::<ProfileReset>)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use bevy::ecs::system::RunSystemOnce;
+
+    /// An app that tracks a human P1 against the AI, as the profile plugin
+    /// does during play
+    fn profile_app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Profile>()
+            .init_resource::<NewRecords>()
+            .init_resource::<PlayerRoles>()
+            .add_event::<PointScored>()
+            .add_event::<ServeStarted>()
+            .add_event::<PaddleHit>()
+            .add_event::<GameWon>()
+            .add_systems(Update, (track_points, track_rallies));
+        app
+    }
+
+    /// A hit by `player` leaving at `ball_speed`
+    fn hit(player: Player, ball_speed: f32) -> PaddleHit {
+        PaddleHit {
+            player,
+            ball_speed,
+            charge: 0.0,
+            position: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn points_count_as_scored_or_conceded_by_the_human() {
+        let mut app = profile_app();
+        for scorer in [Player::P1, Player::P2, Player::P2] {
+            app.world_mut().send_event(PointScored {
+                scorer,
+                rally_over: true,
+            });
+        }
+        app.update();
+
+        let profile = app.world().resource::<Profile>();
+        assert_eq!(profile.points_scored, 1);
+        assert_eq!(profile.points_conceded, 2);
+    }
+
+    #[test]
+    fn hits_measure_the_rally_and_a_serve_starts_a_new_one() {
+        let mut app = profile_app();
+        for speed in [8.0, 12.0, 9.0] {
+            app.world_mut().send_event(hit(Player::P1, speed));
+        }
+        app.update();
+        app.world_mut()
+            .send_event(ServeStarted { server: Player::P2 });
+        app.world_mut().send_event(hit(Player::P2, 7.0));
+        app.update();
+
+        let profile = app.world().resource::<Profile>();
+        assert_eq!(profile.longest_rally, 3);
+        assert_eq!(profile.fastest_ball, 12.0);
+    }
+
+    #[test]
+    fn beating_a_record_is_noted_for_the_endgame_screen() {
+        let mut app = profile_app();
+        {
+            let mut profile = app.world_mut().resource_mut::<Profile>();
+            profile.longest_rally = 1;
+            profile.fastest_ball = 10.0;
+        }
+        app.world_mut().send_event(hit(Player::P1, 15.0));
+        app.world_mut().send_event(hit(Player::P2, 8.0));
+        app.update();
+
+        let records = app.world().resource::<NewRecords>();
+        assert_eq!(records.longest_rally, Some(2));
+        assert_eq!(records.fastest_ball, Some(15.0));
+    }
+
+    #[test]
+    fn game_won_counts_the_result() {
+        let mut app = profile_app();
+        app.world_mut().send_event(GameWon { winner: Player::P2 });
+        app.world_mut()
+            .run_system_once(track_result)
+            .expect("the result should be tracked");
+
+        let profile = app.world().resource::<Profile>();
+        assert_eq!(profile.games_played, 1);
+        assert_eq!(profile.games_won, 0);
+        assert_eq!(profile.games_lost, 1);
+    }
+}
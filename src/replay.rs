@@ -0,0 +1,759 @@
+//! Replay Recording Module
+//!
+//! This module records matches so they can be watched back later, including:
+//! - Per-tick snapshots of the ball and both paddles during play
+//! - Elapsed time for every snapshot, so playback can match the original pacing
+//! - The score after every point
+//! - Saving and loading recordings as versioned RON files
+//! - An optional slow-motion instant replay of each point after it is scored
+//!
+//! A recording starts fresh whenever a new match begins, and pausing simply
+//! stops adding frames. The finished match stays in the [`Recorder`] until the
+//! next one starts. Native builds save it to disk when the match ends and on
+//! F6, while on the web F6 downloads it instead.
+//!
+//! Every file starts with a [`ReplayHeader`] naming its format version, so
+//! files from an incompatible version are rejected with an error rather than
+//! misread. Watching a recording is handled by the playback module.
+//!
+//! The instant replay is off by default since it changes the pacing of a
+//! match, and F7 toggles it. While it is on, the last few seconds of fixed
+//! ticks are kept in the [`InstantReplay`] ring buffer. When a point is
+//! scored, live physics is frozen and the buffer is played back at reduced
+//! speed onto a ghost ball and the real paddles. Any key skips it, and the
+//! serve delay only starts counting once it has finished.
+
+use crate::ball::{Ball, BallAssets};
+use crate::player::Player;
+use crate::score::{MatchRestarted, PointScored, Score};
+use crate::theme::{Theme, Themed};
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// File recordings are saved to, and loaded from for playback
+pub const REPLAY_FILE: &str = "rusty_pong_replay.ron";
+
+/// Version of the replay file format.
+/// Bump whenever [`Recorder`] or anything it contains changes shape.
+pub const REPLAY_FORMAT: u32 = 2;
+
+/// Settings for the instant replay shown after each point
+#[derive(Debug, Resource)]
+pub struct InstantReplaySettings {
+    /// Whether points are replayed (toggled with F7)
+    pub enabled: bool,
+    /// Seconds of play kept for the replay
+    pub length: f32,
+    /// Playback speed relative to the original, 0.5 is half speed
+    pub speed: f32,
+}
+
+impl Default for InstantReplaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            length: 5.0,
+            speed: 0.5,
+        }
+    }
+}
+
+/// The ball's state on a single frame
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BallSnapshot {
+    /// Position in world units
+    pub position: [f32; 2],
+    /// Velocity in world units per second
+    pub velocity: [f32; 2],
+}
+
+/// Positions of the moving objects on a single frame
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Seconds of play since the recording started
+    pub time: f32,
+    /// Ball state, or `None` between points
+    pub ball: Option<BallSnapshot>,
+    /// Player 1's paddle position
+    pub p1: Option<[f32; 2]>,
+    /// Player 2's paddle position, `None` in survival mode
+    pub p2: Option<[f32; 2]>,
+}
+
+impl Snapshot {
+    /// Captures the current ball and paddle positions, with `time` left at zero
+    pub fn capture<'a>(
+        ball: Option<(&Transform, &Velocity)>,
+        paddles: impl IntoIterator<Item = (&'a Player, &'a Transform)>,
+    ) -> Self {
+        let mut frame = Snapshot {
+            ball: ball.map(|(transform, velocity)| BallSnapshot {
+                position: transform.translation.truncate().to_array(),
+                velocity: velocity.linvel.to_array(),
+            }),
+            ..default()
+        };
+
+        for (player, transform) in paddles {
+            let position = Some(transform.translation.truncate().to_array());
+            match player {
+                Player::P1 => frame.p1 = position,
+                Player::P2 => frame.p2 = position,
+            }
+        }
+        frame
+    }
+
+    /// Blends this frame toward `next` by `t`, from 0.0 (this frame) to
+    /// 1.0 (`next`). Objects missing from either frame are taken from
+    /// whichever frame is nearer.
+    pub fn lerp(&self, next: &Snapshot, t: f32) -> Snapshot {
+        let ball = match (self.ball, next.ball) {
+            (Some(a), Some(b)) => Some(BallSnapshot {
+                position: lerp_point(a.position, b.position, t),
+                velocity: lerp_point(a.velocity, b.velocity, t),
+            }),
+            _ if t < 0.5 => self.ball,
+            _ => next.ball,
+        };
+        let blend = |a: Option<[f32; 2]>, b: Option<[f32; 2]>| match (a, b) {
+            (Some(a), Some(b)) => Some(lerp_point(a, b, t)),
+            _ if t < 0.5 => a,
+            _ => b,
+        };
+
+        Snapshot {
+            time: self.time + (next.time - self.time) * t,
+            ball,
+            p1: blend(self.p1, next.p1),
+            p2: blend(self.p2, next.p2),
+        }
+    }
+}
+
+/// Linearly interpolates between two recorded points
+fn lerp_point(a: [f32; 2], b: [f32; 2], t: f32) -> [f32; 2] {
+    Vec2::from(a).lerp(Vec2::from(b), t).to_array()
+}
+
+/// The score after a point was played
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PointEvent {
+    /// Seconds of play since the recording started
+    pub time: f32,
+    /// Player 1's score
+    pub p1: u32,
+    /// Player 2's score
+    pub p2: u32,
+}
+
+/// Identifies the format and origin of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    /// File format version, see [`REPLAY_FORMAT`]
+    pub format: u32,
+    /// Version of the game that made the recording
+    pub crate_version: String,
+    /// Random seed drawn when the match started, identifying the match.
+    /// Missing from format 1 files, which are rejected by their format.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+impl ReplayHeader {
+    /// Creates a header for a recording made by this build
+    pub fn new(seed: u64) -> Self {
+        Self {
+            format: REPLAY_FORMAT,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            seed,
+        }
+    }
+}
+
+/// Just the header of a replay file, so the version can be checked before
+/// the rest of the file is parsed
+#[derive(Deserialize)]
+struct HeaderOnly {
+    header: ReplayHeader,
+}
+
+/// Errors from loading a replay file
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The file couldn't be read
+    Io(io::Error),
+    /// The file isn't a valid recording
+    Parse(ron::error::SpannedError),
+    /// The file was made by an incompatible version of the game
+    UnsupportedFormat(ReplayHeader),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "{}", error),
+            ReplayError::Parse(error) => write!(f, "invalid replay file: {}", error),
+            ReplayError::UnsupportedFormat(header) => write!(
+                f,
+                "replay format {} from version {} is not supported (expected format {})",
+                header.format, header.crate_version, REPLAY_FORMAT
+            ),
+        }
+    }
+}
+
+/// Resource holding the current match's recording.
+///
+/// Frames are captured every fixed tick, and only while playing, so paused
+/// time doesn't appear in the recording and `time` advances by the played
+/// ticks alone.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct Recorder {
+    /// Format and origin of the recording
+    pub header: ReplayHeader,
+    /// Recorded frames, oldest first
+    pub frames: Vec<Snapshot>,
+    /// The score after each point, oldest first
+    pub points: Vec<PointEvent>,
+    /// Seconds of play recorded so far
+    #[serde(skip)]
+    elapsed: f32,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self {
+            header: ReplayHeader::new(0),
+            frames: Vec::new(),
+            points: Vec::new(),
+            elapsed: 0.0,
+        }
+    }
+}
+
+impl Recorder {
+    /// Discards the recording, ready for a new match identified by `seed`
+    pub fn restart(&mut self, seed: u64) {
+        *self = Self {
+            header: ReplayHeader::new(seed),
+            ..default()
+        };
+    }
+
+    /// Appends a frame that took `delta` seconds
+    pub fn record(&mut self, delta: f32, frame: Snapshot) {
+        self.elapsed += delta;
+        self.frames.push(Snapshot {
+            time: self.elapsed,
+            ..frame
+        });
+    }
+
+    /// Appends a frame streamed with its own timestamp, dropping frames
+    /// more than `length` seconds older than it
+    pub fn push_frame(&mut self, frame: Snapshot, length: f32) {
+        self.elapsed = frame.time;
+        self.frames.push(frame);
+        let stale = self
+            .frames
+            .partition_point(|old| old.time < frame.time - length);
+        self.frames.drain(..stale);
+    }
+
+    /// Notes the score, if it has changed since it was last noted
+    pub fn record_point(&mut self, p1: u32, p2: u32) {
+        let (last_p1, last_p2) = self.points.last().map_or((0, 0), |last| (last.p1, last.p2));
+        if (p1, p2) != (last_p1, last_p2) {
+            self.points.push(PointEvent {
+                time: self.elapsed,
+                p1,
+                p2,
+            });
+        }
+    }
+
+    /// Seconds of play in the recording
+    pub fn duration(&self) -> f32 {
+        self.frames.last().map_or(0.0, |frame| frame.time)
+    }
+
+    /// The score at `time` seconds into the recording
+    pub fn score_at(&self, time: f32) -> (u32, u32) {
+        self.points
+            .iter()
+            .take_while(|point| point.time <= time)
+            .last()
+            .map_or((0, 0), |point| (point.p1, point.p2))
+    }
+
+    /// The frame shown at `time` seconds into the recording: the first one
+    /// at or after it, or the last frame once the recording has ended
+    pub fn frame_at(&self, time: f32) -> Option<Snapshot> {
+        let index = self.frames.partition_point(|frame| frame.time < time);
+        self.frames
+            .get(index.min(self.frames.len().saturating_sub(1)))
+            .copied()
+    }
+
+    /// The frame at `time` seconds into the recording, blended between the
+    /// recorded frames either side of it
+    pub fn interpolated_frame_at(&self, time: f32) -> Option<Snapshot> {
+        let index = self.frames.partition_point(|frame| frame.time < time);
+        let before = index
+            .checked_sub(1)
+            .and_then(|index| self.frames.get(index));
+        match (before, self.frames.get(index)) {
+            (Some(before), Some(after)) => {
+                let span = after.time - before.time;
+                let t = if span > 0.0 {
+                    (time - before.time) / span
+                } else {
+                    1.0
+                };
+                Some(before.lerp(after, t))
+            }
+            (before, after) => after.or(before).copied(),
+        }
+    }
+
+    /// Serializes the recording as RON
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::to_string(self)
+    }
+
+    /// Parses a recording from RON, rejecting incompatible versions
+    pub fn from_ron(contents: &str) -> Result<Self, ReplayError> {
+        let HeaderOnly { header } = ron::from_str(contents).map_err(ReplayError::Parse)?;
+        if header.format != REPLAY_FORMAT {
+            return Err(ReplayError::UnsupportedFormat(header));
+        }
+        ron::from_str(contents).map_err(ReplayError::Parse)
+    }
+
+    /// Writes the recording to a RON file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let contents = self.to_ron().map_err(io::Error::other)?;
+        std::fs::write(path, contents)
+    }
+
+    /// Reads a recording from a RON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let contents = std::fs::read_to_string(path).map_err(ReplayError::Io)?;
+        Self::from_ron(&contents)
+    }
+}
+
+/// A replay being played back
+#[derive(Debug)]
+struct Playback {
+    /// Frames being replayed, oldest first
+    frames: Vec<Snapshot>,
+    /// Recorded time currently shown
+    position: f32,
+}
+
+/// Resource holding the recent frames for the instant replay, and the
+/// replay itself while one is playing.
+///
+/// Frames are captured every fixed tick, so the buffer has the same
+/// resolution as the physics.
+#[derive(Debug, Resource, Default)]
+pub struct InstantReplay {
+    /// Most recent frames, oldest first
+    buffer: VecDeque<Snapshot>,
+    /// Seconds of play recorded since the buffer was last cleared
+    elapsed: f32,
+    /// The replay in progress, if any
+    playback: Option<Playback>,
+}
+
+impl InstantReplay {
+    /// Appends a frame that took `delta` seconds, dropping frames older
+    /// than `length` seconds
+    pub fn record(&mut self, delta: f32, length: f32, frame: Snapshot) {
+        self.elapsed += delta;
+        self.buffer.push_back(Snapshot {
+            time: self.elapsed,
+            ..frame
+        });
+        while self
+            .buffer
+            .front()
+            .is_some_and(|frame| frame.time < self.elapsed - length)
+        {
+            self.buffer.pop_front();
+        }
+    }
+
+    /// Starts replaying the buffered frames, which are then cleared so the
+    /// next replay only shows the next point
+    pub fn start(&mut self) {
+        let frames: Vec<Snapshot> = self.buffer.drain(..).collect();
+        self.elapsed = 0.0;
+        if let Some(first) = frames.first() {
+            self.playback = Some(Playback {
+                position: first.time,
+                frames,
+            });
+        }
+    }
+
+    /// Ends the replay in progress
+    pub fn stop(&mut self) {
+        self.playback = None;
+    }
+
+    /// Discards the buffered frames and any replay in progress
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.elapsed = 0.0;
+        self.playback = None;
+    }
+
+    /// Whether a replay is being played back
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+}
+
+/// Run condition that passes while an instant replay is playing
+pub fn replaying(replay: Res<InstantReplay>) -> bool {
+    replay.is_playing()
+}
+
+/// Run condition that passes while no instant replay is playing.
+/// Live gameplay systems use this to stand still during a replay.
+pub fn not_replaying(replay: Res<InstantReplay>) -> bool {
+    !replay.is_playing()
+}
+
+/// Marker component for the ghost ball shown during a replay
+#[derive(Component)]
+struct ReplayBall;
+
+/// Marker component for everything spawned for a replay
+#[derive(Component)]
+struct ReplayEntity;
+
+/// Starts a new recording when a match begins, including when it is
+/// started over with the restart key.
+fn start_recording(mut recorder: ResMut<Recorder>) {
+    recorder.restart(rand::random());
+}
+
+/// Captures the ball and paddle positions after each physics step, for both
+/// the match recording and the instant replay.
+fn record_tick(
+    time: Res<Time>,
+    settings: Res<InstantReplaySettings>,
+    mut recorder: ResMut<Recorder>,
+    mut replay: ResMut<InstantReplay>,
+    ball_query: Query<(&Transform, &Velocity), With<Ball>>,
+    paddle_query: Query<(&Player, &Transform)>,
+) {
+    let frame = Snapshot::capture(ball_query.iter().next(), paddle_query.iter());
+
+    recorder.record(time.delta_secs(), frame);
+    replay.record(time.delta_secs(), settings.length, frame);
+}
+
+/// Notes the score in the recording after each point.
+fn record_points(
+    score: Res<Score>,
+    mut recorder: ResMut<Recorder>,
+    mut points: EventReader<PointScored>,
+) {
+    for _ in points.read() {
+        recorder.record_point(score.p1, score.p2);
+    }
+}
+
+/// Starts the instant replay after each point, if enabled.
+fn start_instant_replay(
+    settings: Res<InstantReplaySettings>,
+    mut replay: ResMut<InstantReplay>,
+    mut points: EventReader<PointScored>,
+) {
+    if points.read().count() > 0 && settings.enabled {
+        replay.start();
+    }
+}
+
+/// Toggles the instant replay with F7.
+fn toggle_instant_replay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<InstantReplaySettings>,
+) {
+    if keyboard.just_pressed(KeyCode::F7) {
+        settings.enabled = !settings.enabled;
+    }
+}
+
+/// Freezes live physics and spawns the ghost ball and label when a replay
+/// starts.
+fn begin_instant_replay(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    ball_assets: Res<BallAssets>,
+    mut rapier_query: Query<&mut RapierConfiguration>,
+    replay_query: Query<(), With<ReplayEntity>>,
+) {
+    if !replay_query.is_empty() {
+        return;
+    }
+
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = false;
+    }
+
+    // Hidden until the first frame with a ball is shown
+    commands.spawn((
+        ReplayEntity,
+        ReplayBall,
+        Mesh2d(ball_assets.mesh.clone()),
+        MeshMaterial2d(ball_assets.material.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+    ));
+
+    commands.spawn((
+        ReplayEntity,
+        Themed::Text,
+        Text::new("REPLAY - press any key to skip"),
+        TextFont {
+            font_size: theme.font_size(20.0),
+            ..default()
+        },
+        TextColor(theme.colors().text),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(20.0),
+            ..default()
+        },
+    ));
+}
+
+/// Advances the replay and moves the ghost ball and paddles to match.
+///
+/// The replay ends once the last frame has been shown, or as soon as any
+/// key is pressed.
+fn play_instant_replay(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<InstantReplaySettings>,
+    mut replay: ResMut<InstantReplay>,
+    mut ball_query: Query<(&mut Transform, &mut Visibility), With<ReplayBall>>,
+    mut paddle_query: Query<(&Player, &mut Transform), Without<ReplayBall>>,
+) {
+    if keyboard.get_just_pressed().next().is_some() {
+        replay.stop();
+        return;
+    }
+
+    let Some(playback) = replay.playback.as_mut() else {
+        return;
+    };
+    playback.position += time.delta_secs() * settings.speed;
+
+    // The first frame at or after the current position
+    let index = playback
+        .frames
+        .partition_point(|frame| frame.time < playback.position);
+    let finished = index >= playback.frames.len();
+    let frame = playback.frames[index.min(playback.frames.len() - 1)];
+
+    for (mut transform, mut visibility) in ball_query.iter_mut() {
+        match frame.ball {
+            Some(ball) => {
+                transform.translation.x = ball.position[0];
+                transform.translation.y = ball.position[1];
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    // Only heights are replayed, so a paddle caught mid-punch isn't left
+    // away from where its punch will return it
+    for (player, mut transform) in paddle_query.iter_mut() {
+        let position = match player {
+            Player::P1 => frame.p1,
+            Player::P2 => frame.p2,
+        };
+        if let Some([_, y]) = position {
+            transform.translation.y = y;
+        }
+    }
+
+    if finished {
+        replay.stop();
+    }
+}
+
+/// Removes the ghost ball and label and unfreezes live physics once a
+/// replay has ended.
+fn end_instant_replay(
+    mut commands: Commands,
+    mut rapier_query: Query<&mut RapierConfiguration>,
+    replay_query: Query<Entity, With<ReplayEntity>>,
+) {
+    if replay_query.is_empty() {
+        return;
+    }
+
+    for entity in replay_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    for mut config in rapier_query.iter_mut() {
+        config.physics_pipeline_active = true;
+    }
+}
+
+/// Discards the replay buffer, and any replay in progress, when entering or
+/// leaving gameplay.
+fn clear_instant_replay(mut replay: ResMut<InstantReplay>) {
+    replay.clear();
+}
+
+/// Writes the recording to [`REPLAY_FILE`].
+#[cfg(not(target_arch = "wasm32"))]
+fn export_recording(recorder: &Recorder) {
+    match recorder.save(REPLAY_FILE) {
+        Ok(()) => info!(
+            "Saved {} replay frames to {}",
+            recorder.frames.len(),
+            REPLAY_FILE
+        ),
+        Err(error) => warn!("Failed to save replay to {}: {}", REPLAY_FILE, error),
+    }
+}
+
+/// Offers the recording as a [`REPLAY_FILE`] download, since browsers have
+/// no file system to write to.
+#[cfg(target_arch = "wasm32")]
+fn export_recording(recorder: &Recorder) {
+    let result = recorder
+        .to_ron()
+        .map_err(|error| error.to_string())
+        .and_then(|contents| download(&contents).map_err(|error| format!("{:?}", error)));
+    if let Err(error) = result {
+        warn!("Failed to download replay: {}", error);
+    }
+}
+
+/// Downloads `contents` as a file by clicking a temporary link to a blob.
+#[cfg(target_arch = "wasm32")]
+fn download(contents: &str) -> Result<(), wasm_bindgen::JsValue> {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let blob = web_sys::Blob::new_with_str_sequence(&parts)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let link: web_sys::HtmlAnchorElement = document
+        .create_element("a")?
+        .dyn_into()
+        .map_err(JsValue::from)?;
+    link.set_href(&url);
+    link.set_download(REPLAY_FILE);
+    link.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// Saves the recording when F6 is pressed.
+fn save_recording(keyboard: Res<ButtonInput<KeyCode>>, recorder: Res<Recorder>) {
+    if keyboard.just_pressed(KeyCode::F6) {
+        export_recording(&recorder);
+    }
+}
+
+/// Saves the finished match to disk. Native only, as a download the player
+/// didn't ask for would be unwelcome in a browser.
+#[cfg(not(target_arch = "wasm32"))]
+fn autosave_recording(recorder: Res<Recorder>) {
+    export_recording(&recorder);
+}
+
+/// Plugin that records matches for later playback, and replays each point
+/// when the instant replay is enabled.
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recorder>()
+            .init_resource::<InstantReplaySettings>()
+            .init_resource::<InstantReplay>()
+            // A new match starts from the splash screen, the menu or a rematch
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                start_recording,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                start_recording,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                start_recording,
+            )
+            // Or from the restart key, once the frame's restart is done
+            .add_systems(
+                PostUpdate,
+                start_recording.run_if(on_event::<MatchRestarted>),
+            )
+            // Live frames are captured after each physics step
+            .add_systems(
+                FixedUpdate,
+                record_tick
+                    .after(PhysicsSet::Writeback)
+                    .run_if(in_state(GameState::Playing).and(not_replaying)),
+            )
+            .add_systems(Update, record_points.run_if(in_state(GameState::Playing)))
+            .add_systems(Update, save_recording);
+
+        app.add_systems(Update, toggle_instant_replay)
+            .add_systems(
+                Update,
+                (
+                    start_instant_replay,
+                    (begin_instant_replay, play_instant_replay)
+                        .chain()
+                        .run_if(replaying),
+                    end_instant_replay.run_if(not_replaying),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // Leaving gameplay ends any replay, so the cleanup runs here too
+            .add_systems(
+                OnExit(GameState::Playing),
+                (clear_instant_replay, end_instant_replay).chain(),
+            )
+            .add_systems(OnEnter(GameState::Playing), clear_instant_replay);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(OnEnter(GameState::GameOver), autosave_recording);
+    }
+}
@@ -2,21 +2,202 @@
 //!
 //! Handles scoring mechanics and display for a table tennis-style game. Features include:
 //! - Score tracking and persistence across game states
-//! - Traditional table tennis scoring rules (first to 11, win by 2)
+//! - Traditional table tennis scoring rules (first to 11, win by 2), with
+//!   quick 5-point and old-school 21-point games selectable
+//! - Optional sudden-death overtime instead of win-by-2 deuce
 //! - Alternating serve patterns with deuce handling
 //! - Score display UI with automatic updates
+//! - Optional momentum bar showing who is ahead
+//! - A fading "P1 SCORES!" announcement after each point
 //! - Victory condition checking
-//! - Ball spawning and serve mechanics
+//! - Optional slow motion while a match point is played
+//! - Ball spawning and serve mechanics, including held serves under the
+//!   manual serve rule
+//! - A short "READY… GO!" sequence before a new match's first serve
+//! - A restart key that starts the match over without leaving play
+//! - Match timer tracking active play time
+//!
+//! Other modules learn about the match through the [`PointScored`],
+//! [`ServeStarted`] and [`GameWon`] events rather than by watching the
+//! [`Score`] or parsing collisions themselves. The scoring systems follow
+//! the same flow: goals are detected from collisions and sent as
+//! [`PointScored`], which is what updates the [`Score`] and arms the next
+//! serve, and [`GameWon`] is only decided in response to a point.
 
-use crate::ball::{create_ball, Ball};
-use crate::board::Wall;
+use crate::ball::{create_ball, despawn_balls, Ball, BallAssets, BallConfig, DespawnBall};
+use crate::board::{BoardConfig, PhysicsConfig, Wall};
+use crate::mode::GameMode;
+use crate::palette::RUST_ORANGE;
+use crate::player::{PaddleConfig, Player, PlayerRoles, PlayerSide};
+use crate::replay::not_replaying;
+use crate::serve::{
+    ai_serve_velocity, hold_ball, serve_position, serves_automatically, AiServeConfig, AiServePlan,
+    PaddlePositions, ServeConfig,
+};
+use crate::survival::SurvivalStats;
+use crate::theme::Theme;
 use crate::GameState;
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
 
 // ----- Resources -----
 
+/// How a game tied near the end is decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Tiebreak {
+    /// Play on until one player leads by 2 points
+    #[default]
+    WinByTwo,
+    /// Once both players are one point from the target, the next point wins
+    SuddenDeath,
+}
+
+impl Tiebreak {
+    /// Returns the other tiebreak
+    pub fn toggle(&self) -> Self {
+        match self {
+            Tiebreak::WinByTwo => Tiebreak::SuddenDeath,
+            Tiebreak::SuddenDeath => Tiebreak::WinByTwo,
+        }
+    }
+
+    /// Returns the tiebreak's display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            Tiebreak::WinByTwo => "win by 2",
+            Tiebreak::SuddenDeath => "sudden death",
+        }
+    }
+}
+
+/// Target scores selectable from the menu: quick, classic and
+/// old-school games
+const TARGETS: [u32; 3] = [5, 11, 21];
+
+/// Resource holding the rules used to decide a game
+#[derive(Debug, Resource)]
+pub struct ScoreRules {
+    /// Points needed to win
+    pub target: u32,
+    /// How a game that reaches deuce is decided
+    pub tiebreak: Tiebreak,
+    /// Delay between a point ending and the next serve in seconds
+    pub serve_delay: f32,
+    /// Length of the "READY… GO!" sequence before a new match's first
+    /// serve in seconds, or 0.0 to serve straight away
+    pub ready_duration: f32,
+    /// Who serves first: `Some(true)` for `Player::P1`, `Some(false)` for
+    /// `Player::P2`, or `None` for a coin flip
+    pub first_server: Option<bool>,
+    /// Most points a match can last, counting both players', or `None` for
+    /// no limit. A match still undecided when it is reached goes to the
+    /// player in the lead, or is drawn if the scores are level.
+    pub point_cap: Option<u32>,
+}
+
+impl Default for ScoreRules {
+    fn default() -> Self {
+        Self {
+            target: 11,
+            tiebreak: Tiebreak::WinByTwo,
+            serve_delay: 0.75,
+            // Long enough to get a hand off the start key
+            ready_duration: 1.0,
+            // Tournaments and tests can pick the first server instead
+            first_server: None,
+            // Matches are played until someone wins
+            point_cap: None,
+        }
+    }
+}
+
+impl ScoreRules {
+    /// Selects the next larger target score, wrapping around
+    pub fn next_target(&mut self) {
+        let index = TARGETS.iter().position(|&t| t == self.target).unwrap_or(0);
+        self.target = TARGETS[(index + 1) % TARGETS.len()];
+    }
+
+    /// Selects the next smaller target score, wrapping around
+    pub fn previous_target(&mut self) {
+        let index = TARGETS.iter().position(|&t| t == self.target).unwrap_or(0);
+        self.target = TARGETS[(index + TARGETS.len() - 1) % TARGETS.len()];
+    }
+
+    /// Describes the rules, such as "First to 11, win by 2"
+    pub fn label(&self) -> String {
+        format!("First to {}, {}", self.target, self.tiebreak.label())
+    }
+
+    /// Decides whether `Player::P1` serves first, flipping a coin unless
+    /// the first server is set
+    pub fn first_server_is_p1(&self) -> bool {
+        self.first_server
+            .unwrap_or_else(|| rand::thread_rng().gen_bool(0.5))
+    }
+
+    /// Returns true once both players are one point away from the target
+    pub fn in_deuce(&self, score: &Score) -> bool {
+        let deuce = self.target.saturating_sub(1);
+        score.p1 >= deuce && score.p2 >= deuce
+    }
+
+    /// Returns true while the next point decides the game outright
+    pub fn in_sudden_death(&self, score: &Score) -> bool {
+        self.tiebreak == Tiebreak::SuddenDeath && self.in_deuce(score)
+    }
+
+    /// Returns true once `points` points have been played in a capped match
+    fn cap_reached(&self, points: u32) -> bool {
+        self.point_cap.is_some_and(|cap| points >= cap)
+    }
+}
+
+/// How a finished match turned out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    /// `Player::P1` won
+    P1Win,
+    /// `Player::P2` won
+    P2Win,
+    /// The match hit its point cap with the scores level
+    Draw,
+}
+
+impl GameResult {
+    /// Reads the result off the final score: the player with more points
+    /// won, and level scores are a draw
+    pub fn from_score(score: &Score) -> Self {
+        match score.p1.cmp(&score.p2) {
+            std::cmp::Ordering::Greater => GameResult::P1Win,
+            std::cmp::Ordering::Less => GameResult::P2Win,
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+
+    /// Returns the winning player, or `None` for a draw
+    pub fn winner(&self) -> Option<Player> {
+        match self {
+            GameResult::P1Win => Some(Player::P1),
+            GameResult::P2Win => Some(Player::P2),
+            GameResult::Draw => None,
+        }
+    }
+}
+
+impl From<Player> for GameResult {
+    fn from(winner: Player) -> Self {
+        match winner {
+            Player::P1 => GameResult::P1Win,
+            Player::P2 => GameResult::P2Win,
+        }
+    }
+}
+
 /// Resource that tracks game scoring state and serve mechanics.
 /// This persists across state changes to maintain game progress.
 #[derive(Resource)]
@@ -35,29 +216,58 @@ pub struct Score {
     pub should_serve: bool,
 }
 
-impl Score {
-    /// Creates a new scoring state with initial values.
-    /// Server is randomly chosen at start.
-    fn new() -> Self {
+impl Default for Score {
+    /// Creates a scoring state for a match that hasn't started yet.
+    ///
+    /// The first server is only picked when a match starts, see
+    /// [`Score::pick_first_server`], and the serve delay follows the rules
+    /// once a serve is pending.
+    fn default() -> Self {
         Self {
             p1: 0,
             p2: 0,
-            server_is_p1: rand::thread_rng().gen_bool(0.5),
+            server_is_p1: true,
             serve_count: 0,
-            serve_timer: Timer::from_seconds(0.75, TimerMode::Once),
+            serve_timer: Timer::from_seconds(ScoreRules::default().serve_delay, TimerMode::Once),
             should_serve: false,
         }
     }
+}
+
+impl Score {
+    /// Assigns the first server of a new match, as set by the rules or by
+    /// a coin flip
+    pub fn pick_first_server(&mut self, rules: &ScoreRules) {
+        self.server_is_p1 = rules.first_server_is_p1();
+        self.serve_count = 0;
+    }
+
+    /// Returns how many points the current server has served since the
+    /// server last changed
+    pub fn serve_count(&self) -> u32 {
+        self.serve_count
+    }
+
+    /// Returns the timer for the delay before a pending serve
+    pub fn serve_timer(&self) -> &Timer {
+        &self.serve_timer
+    }
 
     /// Awards a point and handles serve rotation logic.
     ///
     /// Implements official table tennis serve rules:
     /// - Server changes every 2 points in normal play
-    /// - Server changes every point during deuce (10-10 or higher)
+    /// - Server changes every point during deuce, once both players are one
+    ///   point from the target (10-10 in an 11-point game)
+    ///
+    /// Sudden death starts at the same score as deuce, so the server also
+    /// changes every point there and the deciding point is served by
+    /// whoever didn't serve the last one.
     ///
     /// # Arguments
     /// * `p1_scored` - true if point goes to Player 1, false for Player 2
-    fn add_point(&mut self, p1_scored: bool) {
+    /// * `rules` - Rules deciding when deuce starts
+    pub fn add_point(&mut self, p1_scored: bool, rules: &ScoreRules) {
         // Update appropriate player's score
         if p1_scored {
             self.p1 += 1;
@@ -67,8 +277,8 @@ impl Score {
 
         self.serve_count += 1;
 
-        // Check for deuce conditions (both players at 10+)
-        let in_deuce = self.p1 >= 10 && self.p2 >= 10;
+        // Check for deuce conditions (both players one point from the target)
+        let in_deuce = rules.in_deuce(self);
         let switch_threshold = if in_deuce { 1 } else { 2 };
 
         // Switch server if we've hit the threshold
@@ -78,23 +288,50 @@ impl Score {
         }
     }
 
+    /// Returns the player currently serving
+    pub fn server(&self) -> Player {
+        if self.server_is_p1 {
+            Player::P1
+        } else {
+            Player::P2
+        }
+    }
+
     /// Checks if either player has won the game.
     ///
     /// Victory conditions (official table tennis rules):
-    /// 1. Score must be 11 or higher
+    /// 1. Score must reach the target, 11 by default
     /// 2. Must have a 2-point lead
     ///
+    /// With [`Tiebreak::SuddenDeath`] the 2-point lead is not required:
+    /// reaching the target score wins outright.
+    ///
+    /// A match that reaches [`ScoreRules::point_cap`] is over too, even if
+    /// nobody has won it. See [`GameResult`] for how it turned out.
+    ///
     /// # Returns
-    /// * `true` if either player has won
+    /// * `true` if either player has won or the match hit its point cap
     /// * `false` if game should continue
-    pub fn check_victory(&self) -> bool {
-        if self.p1 >= 11 && self.p1 >= self.p2 + 2 {
-            return true;
-        }
-        if self.p2 >= 11 && self.p2 >= self.p1 + 2 {
-            return true;
-        }
-        false
+    pub fn check_victory(&self, rules: &ScoreRules) -> bool {
+        Self::is_winning(self.p1, self.p2, rules)
+            || Self::is_winning(self.p2, self.p1, rules)
+            || rules.cap_reached(self.p1 + self.p2)
+    }
+
+    /// Returns true if the next point could end the game
+    pub fn is_match_point(&self, rules: &ScoreRules) -> bool {
+        Self::is_winning(self.p1 + 1, self.p2, rules)
+            || Self::is_winning(self.p2 + 1, self.p1, rules)
+            || rules.cap_reached(self.p1 + self.p2 + 1)
+    }
+
+    /// Returns true if a player with `points` has beaten one with `other`
+    fn is_winning(points: u32, other: u32, rules: &ScoreRules) -> bool {
+        let margin = match rules.tiebreak {
+            Tiebreak::WinByTwo => 2,
+            Tiebreak::SuddenDeath => 1,
+        };
+        points >= rules.target && points >= other + margin
     }
 
     /// Resets scoring state for a new game.
@@ -102,18 +339,146 @@ impl Score {
     /// This resets:
     /// - Both players' scores to 0
     /// - Serve count to 0
-    /// - Randomly assigns initial server
     /// - Clears any pending serve state
-    pub fn reset(&mut self) {
+    /// - Applies the configured serve delay
+    ///
+    /// The next match's first server is picked when it starts.
+    pub fn reset(&mut self, rules: &ScoreRules) {
         self.p1 = 0;
         self.p2 = 0;
-        self.server_is_p1 = rand::thread_rng().gen_bool(0.5);
         self.serve_count = 0;
-        self.serve_timer.reset();
+        self.serve_timer = Timer::from_seconds(rules.serve_delay, TimerMode::Once);
         self.should_serve = false;
     }
 }
 
+/// Resource timing the "READY… GO!" sequence before a new match's first
+/// serve, or `None` once the ball has been served.
+///
+/// Pausing stops the countdown and resuming picks it up again, while
+/// resuming a match already underway serves as usual.
+#[derive(Resource, Debug, Default)]
+pub struct ReadyCountdown(pub Option<Timer>);
+
+/// Fraction of the ready sequence at the end that shows "GO!"
+const GO_FRACTION: f32 = 0.3;
+
+/// Configuration for slowing the game down while a match point is played.
+///
+/// Only the physics step is shortened, so the ball travels slower while
+/// paddles keep their normal speed, giving both players extra time to reach
+/// the deciding ball. Speed limits are on velocity, so they still hold.
+#[derive(Debug, Resource)]
+pub struct SlowMotionConfig {
+    /// Whether match points are played in slow motion
+    pub match_point: bool,
+    /// Speed of the physics during slow motion, 0.5 is half speed
+    pub time_scale: f32,
+}
+
+impl Default for SlowMotionConfig {
+    fn default() -> Self {
+        Self {
+            // Off by default since it changes the feel of the deciding rally
+            match_point: false,
+            time_scale: 0.5,
+        }
+    }
+}
+
+/// Configuration for optional score display elements
+#[derive(Debug, Resource)]
+pub struct ScoreDisplayConfig {
+    /// Whether the momentum bar is shown above the scores.
+    /// Disable for a cleaner look.
+    pub momentum_bar: bool,
+    /// How long the "P1 SCORES!" announcement stays on screen in seconds
+    pub announcement_duration: f32,
+}
+
+impl Default for ScoreDisplayConfig {
+    fn default() -> Self {
+        Self {
+            momentum_bar: true,
+            announcement_duration: 1.0,
+        }
+    }
+}
+
+/// Configuration for restarting a match from within play
+#[derive(Debug, Resource)]
+pub struct RestartConfig {
+    /// Key that starts the match over from 0-0, or `None` to disable it
+    pub key: Option<KeyCode>,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            // Not used by either player's controls
+            key: Some(KeyCode::KeyR),
+        }
+    }
+}
+
+/// Resource that tracks how long the current match has been played.
+///
+/// Only time spent in the Playing state counts, so pausing stops the clock.
+#[derive(Resource, Default)]
+pub struct MatchTimer {
+    /// Total active play time in the current match
+    pub elapsed: Duration,
+}
+
+/// Formats a duration as MM:SS
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl MatchTimer {
+    /// Formats the elapsed time as MM:SS
+    pub fn formatted(&self) -> String {
+        format_duration(self.elapsed)
+    }
+
+    /// Resets the timer for a new match
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+}
+
+// ----- Events -----
+
+/// Sent when a point is awarded, after the [`Score`] has been updated
+#[derive(Event, Debug, Clone, Copy)]
+pub struct PointScored {
+    /// The player who won the point
+    pub scorer: Player,
+    /// Whether the ball was the last one in play, ending the rally
+    pub rally_over: bool,
+}
+
+/// Sent when a ball is put into play
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ServeStarted {
+    /// The player serving the ball
+    pub server: Player,
+}
+
+/// Sent when the match is started over with the restart key, after the
+/// [`Score`] has been reset
+#[derive(Event, Debug, Clone, Copy)]
+pub struct MatchRestarted;
+
+/// Sent when a player reaches a winning score, or leads when the match
+/// hits its point cap. Drawn matches end without one.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct GameWon {
+    /// The player who won the match
+    pub winner: Player,
+}
+
 // ----- Components -----
 
 /// Component to identify and differentiate score display UI elements.
@@ -124,9 +489,27 @@ struct ScoreText {
 
 /// Types of score display UI elements.
 enum ScoreKind {
-    P1,   // Player 1's score display
-    P2,   // Player 2's score display
-    Root, // Container element
+    P1,      // Player 1's score display
+    P2,      // Player 2's score display
+    Timer,   // Match timer display
+    Returns, // Survival mode return count
+    Status,  // Match status line, e.g. sudden death
+    Root,    // Container element
+}
+
+/// Marker component for the momentum bar's fill
+#[derive(Component)]
+struct MomentumFill;
+
+/// Marker component for the "READY… GO!" text
+#[derive(Component)]
+struct ReadyText;
+
+/// Component for the text announcing a point, which fades out and is
+/// despawned when its timer finishes
+#[derive(Component)]
+struct GoalAnnouncement {
+    timer: Timer,
 }
 
 // ----- UI Creation and Management Systems -----
@@ -135,13 +518,45 @@ enum ScoreKind {
 ///
 /// Layout structure:
 /// - Root container (centered, fixed width)
-///   - Player 1 score (left side)
-///   - Player 2 score (right side)
+///   - Left player's score (left side)
+///   - Right player's score (right side)
+/// - Match timer (centered below the scores)
+/// - Status line (below the timer, empty unless something notable is happening)
+///
+/// Each score is shown on the side of the board its player defends.
+///
+/// In survival mode the root shows the elapsed time and the number of
+/// returns instead of the two player scores.
 ///
 /// # Arguments
 /// * `commands` - Command buffer for entity creation
+/// * `mode` - Selected game mode
+/// * `side` - Side of the board the human player defends
 /// * `score` - Current score resource for initial values
-fn setup_score_ui(mut commands: Commands, score: Res<Score>) {
+/// * `rules` - Scoring rules for the initial status line
+/// * `timer` - Current match timer for the initial elapsed time
+/// * `survival` - Survival run statistics for the initial return count
+/// * `theme` - Color theme and font scale for the text
+#[allow(clippy::too_many_arguments)]
+fn setup_score_ui(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    side: Res<PlayerSide>,
+    score: Res<Score>,
+    rules: Res<ScoreRules>,
+    timer: Res<MatchTimer>,
+    survival: Res<SurvivalStats>,
+    theme: Res<Theme>,
+) {
+    let survival_mode = *mode == GameMode::Survival;
+
+    // Order the scores to match the paddles on screen
+    let (left, right) = if side.is_left(Player::P1) {
+        ((score.p1, ScoreKind::P1), (score.p2, ScoreKind::P2))
+    } else {
+        ((score.p2, ScoreKind::P2), (score.p1, ScoreKind::P1))
+    };
+
     commands
         .spawn((
             Node {
@@ -158,13 +573,82 @@ fn setup_score_ui(mut commands: Commands, score: Res<Score>) {
             },
         ))
         .with_children(|parent| {
-            spawn_player_score(
-                parent,
-                score.p1,
-                ScoreKind::P1,
-                UiRect::right(Val::Px(20.0)),
-            );
-            spawn_player_score(parent, score.p2, ScoreKind::P2, UiRect::left(Val::Px(20.0)));
+            if survival_mode {
+                spawn_player_score(
+                    parent,
+                    timer.formatted(),
+                    ScoreKind::Timer,
+                    UiRect::right(Val::Px(20.0)),
+                    &theme,
+                );
+                spawn_player_score(
+                    parent,
+                    returns_text(&survival),
+                    ScoreKind::Returns,
+                    UiRect::left(Val::Px(20.0)),
+                    &theme,
+                );
+            } else {
+                spawn_player_score(
+                    parent,
+                    left.0.to_string(),
+                    left.1,
+                    UiRect::right(Val::Px(20.0)),
+                    &theme,
+                );
+                spawn_player_score(
+                    parent,
+                    right.0.to_string(),
+                    right.1,
+                    UiRect::left(Val::Px(20.0)),
+                    &theme,
+                );
+            }
+        });
+
+    // The timer is already the headline in survival mode
+    if survival_mode {
+        return;
+    }
+
+    // Match timer and status line below the scores
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(80.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ScoreText {
+                kind: ScoreKind::Root,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(timer.formatted()),
+                TextFont {
+                    font_size: theme.font_size(24.0),
+                    ..default()
+                },
+                TextColor(theme.colors().text),
+                ScoreText {
+                    kind: ScoreKind::Timer,
+                },
+            ));
+            parent.spawn((
+                Text::new(status_text(&score, &rules)),
+                TextFont {
+                    font_size: theme.font_size(24.0),
+                    ..default()
+                },
+                TextColor(RUST_ORANGE),
+                ScoreText {
+                    kind: ScoreKind::Status,
+                },
+            ));
         });
 }
 
@@ -172,17 +656,24 @@ fn setup_score_ui(mut commands: Commands, score: Res<Score>) {
 ///
 /// # Arguments
 /// * `parent` - Parent UI node to attach to
-/// * `score` - Initial score value to display
-/// * `kind` - Which player's score this represents
+/// * `text` - Initial text to display
+/// * `kind` - Which score element this represents
 /// * `margin` - Margin settings for positioning
-fn spawn_player_score(parent: &mut ChildBuilder, score: u32, kind: ScoreKind, margin: UiRect) {
+/// * `theme` - Color theme and font scale for the text
+fn spawn_player_score(
+    parent: &mut ChildBuilder,
+    text: String,
+    kind: ScoreKind,
+    margin: UiRect,
+    theme: &Theme,
+) {
     parent.spawn((
-        Text::new(score.to_string()),
+        Text::new(text),
         TextFont {
-            font_size: 48.0,
+            font_size: theme.font_size(48.0),
             ..default()
         },
-        TextColor(Color::WHITE),
+        TextColor(theme.colors().text),
         Node {
             margin,
             ..default()
@@ -191,21 +682,144 @@ fn spawn_player_score(parent: &mut ChildBuilder, score: u32, kind: ScoreKind, ma
     ));
 }
 
+/// Spawns the momentum bar at the top of the screen.
+///
+/// The bar's fill grows from the center toward the side of whichever
+/// player is ahead, reaching the end when the lead equals the target score.
+/// It is skipped in survival mode, where there is no opponent.
+fn spawn_momentum_bar(
+    mut commands: Commands,
+    mode: Res<GameMode>,
+    config: Res<ScoreDisplayConfig>,
+    theme: Res<Theme>,
+) {
+    if !config.momentum_bar || *mode == GameMode::Survival {
+        return;
+    }
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                top: Val::Px(6.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ScoreText {
+                kind: ScoreKind::Root,
+            },
+        ))
+        .with_children(|parent| {
+            // Outlined track
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(240.0),
+                        height: Val::Px(8.0),
+                        border: UiRect::all(Val::Px(1.0)),
+                        ..default()
+                    },
+                    BorderColor(theme.colors().text),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        MomentumFill,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Percent(50.0),
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(RUST_ORANGE),
+                    ));
+                });
+        });
+}
+
+/// Calculates the momentum bar fill as (left, width) percentages of the
+/// track, extending from the center toward the leading player's side.
+fn momentum_fill(score: &Score, rules: &ScoreRules, side: &PlayerSide) -> (f32, f32) {
+    let lead = score.p1 as f32 - score.p2 as f32;
+    let share = (lead / rules.target.max(1) as f32).clamp(-1.0, 1.0);
+    // Positive means the bar grows to the right
+    let toward_right = if side.is_left(Player::P1) {
+        -share
+    } else {
+        share
+    };
+    let width = toward_right.abs() * 50.0;
+    let left = if toward_right < 0.0 {
+        50.0 - width
+    } else {
+        50.0
+    };
+    (left, width)
+}
+
+/// Formats the survival mode return count.
+fn returns_text(survival: &SurvivalStats) -> String {
+    format!("Returns: {}", survival.returns)
+}
+
+/// Formats the match status line, empty when there's nothing to report.
+fn status_text(score: &Score, rules: &ScoreRules) -> String {
+    if rules.in_sudden_death(score) {
+        "SUDDEN DEATH".to_string()
+    } else {
+        String::new()
+    }
+}
+
 /// Updates score display text to match current game state.
 ///
 /// This system:
 /// - Runs continuously during gameplay
-/// - Updates only when text doesn't match current score
+/// - Updates only when text doesn't match current score or time
+/// - Moves the momentum bar's fill toward the leading player
 /// - Ensures consistency after state transitions
-fn update_score_display(score: Res<Score>, mut query: Query<(&mut Text, &ScoreText)>) {
+fn update_score_display(
+    score: Res<Score>,
+    rules: Res<ScoreRules>,
+    side: Res<PlayerSide>,
+    mut query: Query<(&mut Text, &ScoreText)>,
+    mut momentum_query: Query<&mut Node, With<MomentumFill>>,
+) {
+    let (left, width) = momentum_fill(&score, &rules, &side);
+    for mut node in momentum_query.iter_mut() {
+        node.left = Val::Percent(left);
+        node.width = Val::Percent(width);
+    }
+
+    for (mut text, score_text) in query.iter_mut() {
+        let score_text = match score_text.kind {
+            ScoreKind::P1 => score.p1.to_string(),
+            ScoreKind::P2 => score.p2.to_string(),
+            ScoreKind::Status => status_text(&score, &rules),
+            _ => continue,
+        };
+
+        if **text != score_text {
+            **text = score_text;
+        }
+    }
+}
+
+/// Updates the match timer and survival return count, which change
+/// without a point being scored.
+fn update_timer_display(
+    timer: Res<MatchTimer>,
+    survival: Res<SurvivalStats>,
+    mut query: Query<(&mut Text, &ScoreText)>,
+) {
     for (mut text, score_text) in query.iter_mut() {
-        let current_score = match score_text.kind {
-            ScoreKind::P1 => score.p1,
-            ScoreKind::P2 => score.p2,
-            ScoreKind::Root => continue,
+        let score_text = match score_text.kind {
+            ScoreKind::Timer => timer.formatted(),
+            ScoreKind::Returns => returns_text(&survival),
+            _ => continue,
         };
 
-        let score_text = current_score.to_string();
         if **text != score_text {
             **text = score_text;
         }
@@ -219,33 +833,340 @@ fn cleanup_score_ui(mut commands: Commands, query: Query<Entity, With<ScoreText>
     }
 }
 
+/// Spawns the centered announcement of who scored.
+fn spawn_goal_announcement(
+    commands: &mut Commands,
+    scorer: Player,
+    theme: &Theme,
+    config: &ScoreDisplayConfig,
+) {
+    let message = match scorer {
+        Player::P1 => "P1 SCORES!",
+        Player::P2 => "P2 SCORES!",
+    };
+
+    commands.spawn((
+        GoalAnnouncement {
+            timer: Timer::from_seconds(config.announcement_duration, TimerMode::Once),
+        },
+        Text::new(message),
+        TextFont {
+            font_size: theme.font_size(60.0),
+            ..default()
+        },
+        TextColor(theme.colors().text),
+        TextLayout::new_with_justify(JustifyText::Center),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(40.0),
+            ..default()
+        },
+    ));
+}
+
+/// Announces the scorer of each point.
+fn announce_goals(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    display: Res<ScoreDisplayConfig>,
+    mut events: EventReader<PointScored>,
+) {
+    for event in events.read() {
+        spawn_goal_announcement(&mut commands, event.scorer, &theme, &display);
+    }
+}
+
+/// Fades goal announcements out over their lifetime, then removes them.
+fn fade_goal_announcements(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut GoalAnnouncement, &mut TextColor)>,
+) {
+    for (entity, mut announcement, mut color) in query.iter_mut() {
+        announcement.timer.tick(time.delta());
+        if announcement.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            color.0 = color.0.with_alpha(1.0 - announcement.timer.fraction());
+        }
+    }
+}
+
+/// Removes any announcement still showing when leaving gameplay state.
+fn cleanup_goal_announcements(
+    mut commands: Commands,
+    query: Query<Entity, With<GoalAnnouncement>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 // ----- Gameplay Systems -----
 
-/// Creates initial Score resource.
-fn init_score(mut commands: Commands) {
-    commands.insert_resource(Score::new());
+/// Advances the match timer by the frame's delta.
+///
+/// Only runs during the Playing state, so paused time is excluded.
+fn tick_match_timer(time: Res<Time>, mut timer: ResMut<MatchTimer>) {
+    timer.elapsed += time.delta();
+}
+
+/// The resources deciding how and where a ball is served
+type ServeRules<'w, 's> = (
+    Res<'w, ServeConfig>,
+    Res<'w, AiServeConfig>,
+    ResMut<'w, AiServePlan>,
+    Res<'w, BoardConfig>,
+    Res<'w, PaddleConfig>,
+    PaddlePositions<'w, 's>,
+);
+
+/// Puts a ball into play for `server`, and sends [`ServeStarted`].
+///
+/// The ball starts in front of the server's paddle, see [`serve_position`].
+/// Under the manual serve rule the ball is held on the server's paddle.
+/// Otherwise a computer-controlled server launches it with its planned
+/// serve, and anyone else straight across.
+#[allow(clippy::too_many_arguments)]
+fn serve_ball(
+    commands: &mut Commands,
+    ball_assets: &BallAssets,
+    ball_config: &BallConfig,
+    (serve_config, ai_serve_config, plan, board, paddle_config, paddles): &mut ServeRules,
+    roles: &PlayerRoles,
+    side: &PlayerSide,
+    server: Player,
+    serves: &mut EventWriter<ServeStarted>,
+) {
+    let served_by_left = side.is_left(server);
+    let position = serve_position(server, paddles, side, paddle_config, serve_config, board);
+    let ball = create_ball(commands, ball_assets, ball_config, served_by_left, position);
+    if serve_config.manual {
+        hold_ball(&mut commands.entity(ball), server, roles, serve_config);
+    } else if serves_automatically(roles.controller(server)) {
+        let velocity = ai_serve_velocity(plan, ai_serve_config, ball_config, served_by_left);
+        commands.entity(ball).insert(Velocity::linear(velocity));
+    }
+    serves.send(ServeStarted { server });
+}
+
+/// The player serving a ball put into play outside the serve delay.
+///
+/// In survival mode the ball is always served away from the player.
+fn opening_server(mode: &GameMode, score: &Score) -> Player {
+    if *mode == GameMode::Survival {
+        Player::P1
+    } else {
+        score.server()
+    }
 }
 
 /// Manages ball spawning for various game situations.
 ///
 /// Spawns ball:
-/// - At start of new game
-/// - After resuming from pause
+/// - On entering gameplay with no ball in play and none on its way
 /// - After each point (with serve delay)
+///
+/// A new game's first ball waits for [`run_ready_countdown`] instead.
+///
+/// Pausing keeps the ball in play, so resuming normally serves nothing. If
+/// a point was scored before pausing, `should_serve` is still set on
+/// resume, so this system leaves serving to [`handle_serve_delay`], whose
+/// timer continues from where it stopped rather than a second ball being
+/// served here.
+#[allow(clippy::too_many_arguments)]
 fn on_resume(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    mut serve_rules: ServeRules,
+    mode: Res<GameMode>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
     score: Res<Score>,
+    countdown: Res<ReadyCountdown>,
+    ball_query: Query<Entity, With<Ball>>,
+    mut serves: EventWriter<ServeStarted>,
+) {
+    if ball_query.is_empty() && !score.should_serve && countdown.0.is_none() {
+        let server = opening_server(&mode, &score);
+        serve_ball(
+            &mut commands,
+            &ball_assets,
+            &ball_config,
+            &mut serve_rules,
+            &roles,
+            &side,
+            server,
+            &mut serves,
+        );
+    }
+}
+
+/// Timer for a new match's "READY… GO!" sequence, or `None` if the rules
+/// skip it
+fn ready_timer(rules: &ScoreRules) -> Option<Timer> {
+    (rules.ready_duration > 0.0).then(|| Timer::from_seconds(rules.ready_duration, TimerMode::Once))
+}
+
+/// Picks the first server and arms the "READY… GO!" sequence when a new
+/// match begins.
+fn start_new_match(
+    rules: Res<ScoreRules>,
+    mut score: ResMut<Score>,
+    mut countdown: ResMut<ReadyCountdown>,
+) {
+    score.pick_first_server(&rules);
+    countdown.0 = ready_timer(&rules);
+}
+
+/// The resources a restart clears for the new match
+type MatchProgress<'a> = (
+    ResMut<'a, Score>,
+    ResMut<'a, MatchTimer>,
+    ResMut<'a, SurvivalStats>,
+    ResMut<'a, ReadyCountdown>,
+);
+
+/// Starts the match over when the restart key is pressed, without leaving
+/// the Playing state.
+///
+/// The balls in play are removed and the score, match time and survival
+/// run are cleared, as when leaving the endgame screen. The new match then
+/// opens like any other, with the "READY… GO!" sequence serving its first
+/// ball, or with an immediate serve if the rules skip the sequence. Any
+/// serve the old match had pending is dropped with its score, so exactly
+/// one ball comes into play. The match recording and the ghost capture
+/// start over on the [`MatchRestarted`] event sent here.
+#[allow(clippy::too_many_arguments)]
+fn restart_match(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: Res<RestartConfig>,
+    rules: Res<ScoreRules>,
+    (mut score, mut timer, mut survival, mut countdown): MatchProgress,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    mut serve_rules: ServeRules,
+    mode: Res<GameMode>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
     ball_query: Query<Entity, With<Ball>>,
+    mut despawns: EventWriter<DespawnBall>,
+    mut serves: EventWriter<ServeStarted>,
+    mut restarts: EventWriter<MatchRestarted>,
+) {
+    if !config.key.is_some_and(|key| keyboard.just_pressed(key)) {
+        return;
+    }
+
+    // Only the old balls, not the one served below
+    for entity in ball_query.iter() {
+        despawns.send(DespawnBall::One(entity));
+    }
+    score.reset(&rules);
+    score.pick_first_server(&rules);
+    timer.reset();
+    survival.reset_run();
+    countdown.0 = ready_timer(&rules);
+    restarts.send(MatchRestarted);
+
+    if countdown.0.is_none() {
+        let server = opening_server(&mode, &score);
+        serve_ball(
+            &mut commands,
+            &ball_assets,
+            &ball_config,
+            &mut serve_rules,
+            &roles,
+            &side,
+            server,
+            &mut serves,
+        );
+    }
+}
+
+/// Shows "READY…" and then "GO!" while the ready sequence runs, and serves
+/// the match's first ball when it ends.
+///
+/// Paddles can already move, so players can get into position.
+#[allow(clippy::too_many_arguments)]
+fn run_ready_countdown(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<Theme>,
+    mut countdown: ResMut<ReadyCountdown>,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    mut serve_rules: ServeRules,
+    mode: Res<GameMode>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
+    score: Res<Score>,
+    mut text_query: Query<(Entity, &mut Text), With<ReadyText>>,
+    mut serves: EventWriter<ServeStarted>,
 ) {
-    if ball_query.is_empty() && !score.should_serve {
-        create_ball(
+    let Some(timer) = countdown.0.as_mut() else {
+        return;
+    };
+
+    if timer.tick(time.delta()).finished() {
+        countdown.0 = None;
+        for (entity, _) in text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        serve_ball(
             &mut commands,
-            &mut meshes,
-            &mut materials,
-            score.server_is_p1,
+            &ball_assets,
+            &ball_config,
+            &mut serve_rules,
+            &roles,
+            &side,
+            opening_server(&mode, &score),
+            &mut serves,
         );
+        return;
+    }
+
+    let message = if timer.fraction() >= 1.0 - GO_FRACTION {
+        "GO!"
+    } else {
+        "READY…"
+    };
+    match text_query.get_single_mut() {
+        Ok((_, mut text)) => {
+            if text.0 != message {
+                text.0 = message.to_string();
+            }
+        }
+        // Spawned here rather than on entering the state, so it also comes
+        // back after a pause
+        Err(_) => {
+            commands.spawn((
+                ReadyText,
+                Text::new(message),
+                TextFont {
+                    font_size: theme.font_size(60.0),
+                    ..default()
+                },
+                TextColor(theme.colors().text),
+                TextLayout::new_with_justify(JustifyText::Center),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    top: Val::Percent(40.0),
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+/// Removes the ready text when leaving gameplay state.
+fn cleanup_ready_text(mut commands: Commands, query: Query<Entity, With<ReadyText>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
 }
 
@@ -255,42 +1176,131 @@ fn on_resume(
 /// - Visual pause between points
 /// - Time for players to prepare
 /// - Consistent serve timing
+///
+/// The delay follows [`ScoreRules::serve_delay`], so changes to the rules
+/// apply from the next serve.
+///
+/// The timer only ticks while playing, so a pause during the delay simply
+/// stops it, and it waits for an instant replay to finish before starting.
+/// A pending serve never coexists with a ball in play; debug builds assert
+/// this, and release builds skip the serve rather than add a second ball.
+#[allow(clippy::too_many_arguments)]
 fn handle_serve_delay(
     time: Res<Time>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
+    rules: Res<ScoreRules>,
     mut score: ResMut<Score>,
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    ball_assets: Res<BallAssets>,
+    ball_config: Res<BallConfig>,
+    mut serve_rules: ServeRules,
+    ball_query: Query<(), With<Ball>>,
+    mut serves: EventWriter<ServeStarted>,
 ) {
     if score.should_serve {
+        let serve_delay = Duration::from_secs_f32(rules.serve_delay);
+        if score.serve_timer.duration() != serve_delay {
+            score.serve_timer.set_duration(serve_delay);
+        }
         score.serve_timer.tick(time.delta());
 
         if score.serve_timer.just_finished() {
-            create_ball(
-                &mut commands,
-                &mut meshes,
-                &mut materials,
-                score.server_is_p1,
+            debug_assert!(
+                ball_query.is_empty(),
+                "serve delay finished with a ball already in play"
             );
+
+            // The server on the left serves to the right
+            if ball_query.is_empty() {
+                serve_ball(
+                    &mut commands,
+                    &ball_assets,
+                    &ball_config,
+                    &mut serve_rules,
+                    &roles,
+                    &side,
+                    score.server(),
+                    &mut serves,
+                );
+            }
             score.should_serve = false;
             score.serve_timer.reset();
         }
     }
 }
 
-/// Processes ball-wall collisions for scoring.
+/// Slows the physics down while a match point rally is underway, and
+/// restores normal speed once the point resolves.
+fn apply_match_point_slow_motion(
+    slow_motion: Res<SlowMotionConfig>,
+    physics: Res<PhysicsConfig>,
+    mode: Res<GameMode>,
+    rules: Res<ScoreRules>,
+    score: Res<Score>,
+    mut timestep: ResMut<TimestepMode>,
+    ball_query: Query<(), With<Ball>>,
+) {
+    let rally_underway = !ball_query.is_empty() && !score.should_serve;
+    let slowed = slow_motion.match_point
+        && *mode != GameMode::Survival
+        && rally_underway
+        && score.is_match_point(&rules);
+
+    let dt = if slowed {
+        physics.timestep * slow_motion.time_scale
+    } else {
+        physics.timestep
+    };
+    set_physics_dt(&mut timestep, dt);
+}
+
+/// Restores the normal physics speed when leaving gameplay.
+fn reset_slow_motion(physics: Res<PhysicsConfig>, mut timestep: ResMut<TimestepMode>) {
+    set_physics_dt(&mut timestep, physics.timestep);
+}
+
+/// Sets the length of each physics step, leaving the resource unchanged if
+/// it already matches.
+fn set_physics_dt(timestep: &mut ResMut<TimestepMode>, dt: f32) {
+    if let TimestepMode::Fixed { substeps, .. } = **timestep {
+        timestep.set_if_neq(TimestepMode::Fixed { dt, substeps });
+    }
+}
+
+/// Detects goals from ball-wall collisions.
 ///
 /// When ball hits scoring wall:
-/// 1. Awards point to appropriate player
-/// 2. Removes the ball
-/// 3. Initiates serve sequence
-fn handle_scoring(
-    mut commands: Commands,
-    mut score: ResMut<Score>,
+/// 1. Sends [`PointScored`] for the opponent of the wall's defender
+/// 2. Asks for the ball to be removed with [`DespawnBall`]
+///
+/// The point itself is awarded by [`award_points`]. With several balls in
+/// play, as in multi-ball mode, each one scores, but only the last one to
+/// leave ends the rally.
+///
+/// In survival mode the far wall just bounces the ball, and reaching
+/// the wall behind the player ends the run.
+///
+/// A ball clipping a corner can report several wall contacts in the same
+/// frame, so each ball scores at most once before it is despawned.
+/// Walls are only in collision groups with balls, so paddles never report
+/// wall contacts here.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn handle_scoring(
+    mode: Res<GameMode>,
+    side: Res<PlayerSide>,
+    mut next_state: ResMut<NextState<GameState>>,
     mut collision_events: EventReader<CollisionEvent>,
+    mut points: EventWriter<PointScored>,
+    mut despawns: EventWriter<DespawnBall>,
     ball_query: Query<Entity, With<Ball>>,
     wall_query: Query<(Entity, &Wall)>,
 ) {
+    // Balls that have already scored in this run of the system
+    let mut scored = HashSet::new();
+    // Balls still in play, counting down as they score
+    let mut live = ball_query.iter().count();
+
     for collision_event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = collision_event {
             // Find colliding entities
@@ -301,41 +1311,78 @@ fn handle_scoring(
                 .map(|(_, w)| w);
 
             if let (Some(ball_entity), Some(wall)) = (ball_entity, wall) {
-                match wall {
-                    Wall::Left => {
-                        score.add_point(false); // P2 scores
-                        commands.entity(ball_entity).despawn();
-                        score.should_serve = true;
-                    }
-                    Wall::Right => {
-                        score.add_point(true); // P1 scores
-                        commands.entity(ball_entity).despawn();
-                        score.should_serve = true;
+                // Top/Bottom walls don't affect score
+                let Some(defender) = side.defender(wall) else {
+                    continue;
+                };
+
+                // Ignore further contacts from a ball that has already scored
+                if !scored.insert(ball_entity) {
+                    continue;
+                }
+
+                if *mode == GameMode::Survival {
+                    if defender == Player::P1 {
+                        despawns.send(DespawnBall::One(ball_entity));
+                        next_state.set(GameState::GameOver);
                     }
-                    _ => {} // Top/Bottom walls don't affect score
+                    continue;
                 }
+
+                // The defender conceded, so the other player scores
+                live = live.saturating_sub(1);
+                points.send(PointScored {
+                    scorer: defender.opponent(),
+                    rally_over: live == 0,
+                });
+                despawns.send(DespawnBall::One(ball_entity));
             }
         }
     }
 }
 
-/// Monitors for victory conditions during gameplay.
+/// Awards each point scored, and starts the serve sequence for the next
+/// rally once the last ball has left play.
+fn award_points(
+    rules: Res<ScoreRules>,
+    mut score: ResMut<Score>,
+    mut points: EventReader<PointScored>,
+) {
+    for point in points.read() {
+        score.add_point(point.scorer == Player::P1, &rules);
+        if point.rally_over {
+            score.should_serve = true;
+        }
+    }
+}
+
+/// Checks for victory after each point.
 ///
 /// When victory detected:
-/// 1. Removes the ball to prevent further scoring
-/// 2. Transitions to game over state
+/// 1. Asks for every ball to be removed, to prevent further scoring
+/// 2. Sends [`GameWon`], unless the match was drawn
+/// 3. Cancels the serve the final point armed, so it can't fire later
+/// 4. Transitions to game over state
 fn check_victory(
-    score: Res<Score>,
-    mut commands: Commands,
+    mut score: ResMut<Score>,
+    rules: Res<ScoreRules>,
     mut next_state: ResMut<NextState<GameState>>,
-    ball_query: Query<Entity, With<Ball>>,
+    mut points: EventReader<PointScored>,
+    mut wins: EventWriter<GameWon>,
+    mut despawns: EventWriter<DespawnBall>,
 ) {
-    if score.check_victory() {
-        for entity in ball_query.iter() {
-            commands.entity(entity).despawn();
-        }
-        next_state.set(GameState::GameOver);
+    // Only a point can decide the match, and only once
+    if points.read().count() == 0 || !score.check_victory(&rules) {
+        return;
+    }
+
+    despawns.send(DespawnBall::All);
+    if let Some(winner) = GameResult::from_score(&score).winner() {
+        wins.send(GameWon { winner });
     }
+    score.should_serve = false;
+    score.serve_timer.reset();
+    next_state.set(GameState::GameOver);
 }
 
 // ----- Plugin Setup -----
@@ -347,24 +1394,180 @@ impl Plugin for ScorePlugin {
     fn build(&self, app: &mut App) {
         app
             // Resource initialization
-            .add_systems(Startup, init_score)
+            .init_resource::<Score>()
+            .init_resource::<ScoreRules>()
+            .init_resource::<MatchTimer>()
+            .init_resource::<ScoreDisplayConfig>()
+            .init_resource::<SlowMotionConfig>()
+            .init_resource::<ReadyCountdown>()
+            .init_resource::<RestartConfig>()
+            .add_event::<PointScored>()
+            .add_event::<MatchRestarted>()
+            .add_event::<ServeStarted>()
+            .add_event::<GameWon>()
             // UI management
             .add_systems(
                 OnEnter(GameState::Playing),
-                (setup_score_ui, update_score_display),
+                (setup_score_ui, spawn_momentum_bar, update_score_display),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                (
+                    cleanup_score_ui,
+                    cleanup_goal_announcements,
+                    cleanup_ready_text,
+                    reset_slow_motion,
+                ),
+            )
+            // A new match starts from the splash screen, the menu or a
+            // rematch, and transitions run before entering the state, so
+            // the first serve waits for the countdown
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                start_new_match,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                start_new_match,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                start_new_match,
             )
-            .add_systems(OnExit(GameState::Playing), cleanup_score_ui)
             .add_systems(OnEnter(GameState::Playing), on_resume)
-            // Score display updates
+            // Score display updates that don't depend on a point
+            .add_systems(
+                Update,
+                (
+                    announce_goals,
+                    update_timer_display,
+                    fade_goal_announcements,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            )
+            // Gameplay systems, chained so a point is awarded and shown in
+            // the frame it is scored, and the match decided, before the
+            // scored ball is removed. Only then does the serve delay run,
+            // so a serve never overlaps the ball it replaces or outlives
+            // the match.
+            // Instant replays aren't counted as match time.
             .add_systems(
                 Update,
-                update_score_display.run_if(in_state(GameState::Playing)),
+                (
+                    tick_match_timer.run_if(not_replaying),
+                    handle_scoring,
+                    award_points,
+                    // A restart in the same frame wipes out the point
+                    restart_match.run_if(not_replaying),
+                    // The scores are only redrawn when they change
+                    update_score_display
+                        .run_if(on_event::<PointScored>.or(on_event::<MatchRestarted>)),
+                    check_victory,
+                )
+                    .chain()
+                    .before(despawn_balls)
+                    .run_if(in_state(GameState::Playing)),
             )
-            // Gameplay systems
             .add_systems(
                 Update,
-                (handle_scoring, handle_serve_delay, check_victory)
+                (
+                    handle_serve_delay.run_if(not_replaying),
+                    run_ready_countdown,
+                    apply_match_point_slow_motion,
+                )
+                    .chain()
+                    .after(despawn_balls)
                     .run_if(in_state(GameState::Playing)),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    /// An app that only awards points and decides the match, as the score
+    /// plugin does
+    fn scoring_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin)
+            .init_state::<GameState>()
+            .init_resource::<Score>()
+            .init_resource::<ScoreRules>()
+            .add_event::<PointScored>()
+            .add_event::<GameWon>()
+            .add_event::<DespawnBall>()
+            .add_systems(Update, (award_points, check_victory).chain());
+        app
+    }
+
+    /// The match winners announced so far
+    fn winners(app: &App) -> Vec<Player> {
+        let events = app.world().resource::<Events<GameWon>>();
+        events
+            .get_cursor()
+            .read(events)
+            .map(|won| won.winner)
+            .collect()
+    }
+
+    #[test]
+    fn point_scored_awards_the_point_and_arms_the_serve() {
+        let mut app = scoring_app();
+        app.world_mut().send_event(PointScored {
+            scorer: Player::P2,
+            rally_over: true,
+        });
+        app.update();
+
+        let score = app.world().resource::<Score>();
+        assert_eq!((score.p1, score.p2), (0, 1));
+        assert!(score.should_serve);
+        assert!(winners(&app).is_empty());
+    }
+
+    #[test]
+    fn point_with_balls_still_in_play_leaves_the_serve() {
+        let mut app = scoring_app();
+        app.world_mut().send_event(PointScored {
+            scorer: Player::P1,
+            rally_over: false,
+        });
+        app.update();
+
+        let score = app.world().resource::<Score>();
+        assert_eq!((score.p1, score.p2), (1, 0));
+        assert!(!score.should_serve);
+    }
+
+    #[test]
+    fn winning_point_sends_game_won_and_ends_the_match() {
+        let mut app = scoring_app();
+        let target = app.world().resource::<ScoreRules>().target;
+        app.world_mut().resource_mut::<Score>().p1 = target - 1;
+        app.world_mut().send_event(PointScored {
+            scorer: Player::P1,
+            rally_over: true,
+        });
+        app.update();
+
+        assert_eq!(winners(&app), vec![Player::P1]);
+        let score = app.world().resource::<Score>();
+        assert_eq!(score.p1, target);
+        assert!(!score.should_serve);
+        assert!(matches!(
+            app.world().resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::GameOver)
+        ));
+    }
+}
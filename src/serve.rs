@@ -0,0 +1,622 @@
+//! Serve Module
+//!
+//! This module implements how balls are served, including:
+//! - Varied serves by computer-controlled servers
+//! - Serving from in front of the server's paddle, where the next ball
+//!   waits during the serve delay
+//! - A faint dotted preview of the next serve's direction during the serve
+//!   delay, so the receiver can anticipate it
+//! - Holding a newly served ball in front of the server's paddle under the
+//!   optional manual serve rule
+//! - An aim indicator sweeping up and down while the ball is held
+//! - Launching the ball along the indicator when the server serves
+//! - A short, randomized think time before other servers serve
+//! - Serving automatically after a timeout, so the game can't be stalled
+//!
+//! A computer-controlled server plans its serve as soon as the serve is
+//! due: fast and flat, or slow and angled up or down, weighted by
+//! [`AiServeConfig`]. Its paddle lines up for the serve during the serve
+//! delay, and the ball leaves along the planned angle with some jitter, so
+//! no two serves are quite alike.
+//!
+//! With the manual serve rule off, which is the default, balls launch as
+//! soon as they are served. Under the rule, the human serves with the swing
+//! keys, since Space pauses the game, or by tapping the far half of a touch
+//! screen. A held ball is moved with the paddle rather than by physics, and
+//! its collider is disabled so nothing can knock it loose. The timeout only
+//! runs while playing, so a pause stops it.
+
+use crate::ball::{Ball, BallAssets, BallConfig};
+use crate::board::BoardConfig;
+use crate::player::{Controller, PaddleConfig, Player, PlayerRoles, PlayerSide};
+use crate::replay::{not_replaying, InstantReplay};
+use crate::score::Score;
+use crate::theme::Theme;
+use crate::touch::TouchServe;
+use crate::GameState;
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use std::f32::consts::FRAC_PI_4;
+
+/// Configuration for the manual serve rule
+#[derive(Debug, Resource)]
+pub struct ServeConfig {
+    /// Whether served balls wait on the server's paddle until they serve.
+    /// Turned on and off from the settings screen.
+    pub manual: bool,
+    /// Steepest launch angle from straight across, in radians
+    pub max_angle: f32,
+    /// Time for the aim indicator to sweep from bottom to top in seconds
+    pub sweep_time: f32,
+    /// Distance of a served or held ball in front of the paddle's resting
+    /// position. Kept beyond the reach of a swing.
+    pub hold_distance: f32,
+    /// Shortest think time before a computer or online server serves in seconds
+    pub min_think_time: f32,
+    /// Longest think time before a computer or online server serves in seconds
+    pub max_think_time: f32,
+    /// Time after which a held ball is served automatically in seconds
+    pub timeout: f32,
+    /// Length of the aim indicator in world units
+    pub indicator_length: f32,
+    /// Whether the direction of the next automatic serve is previewed
+    /// during the serve delay
+    pub preview: bool,
+    /// Number of dots in the serve preview
+    pub preview_dots: usize,
+    /// Distance between the serve preview's dots in world units
+    pub preview_spacing: f32,
+    /// Opacity of the serve preview's dots
+    pub preview_alpha: f32,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            // Classic automatic serves
+            manual: false,
+            // 45 degrees either way
+            max_angle: FRAC_PI_4,
+            // Slow enough to time the release
+            sweep_time: 1.2,
+            // Clear of the paddle's curve and swing
+            hold_distance: 0.7,
+            // A moment to line up, like a human would
+            min_think_time: 0.4,
+            max_think_time: 1.2,
+            // Long enough to aim, short enough to keep the game moving
+            timeout: 5.0,
+            indicator_length: 1.0,
+            // A hint for the receiver, faint enough not to distract
+            preview: true,
+            preview_dots: 8,
+            preview_spacing: 0.5,
+            preview_alpha: 0.3,
+        }
+    }
+}
+
+/// Size of a serve preview dot in world units
+const PREVIEW_DOT_SIZE: f32 = 0.08;
+
+/// Configuration for serves by computer-controlled servers.
+///
+/// The weights are relative chances of each kind of serve. Raising
+/// `fast_weight` makes for a tougher opponent, and the adaptive difficulty
+/// does so with the score gap, see
+/// [`RubberBandConfig`](crate::player::RubberBandConfig).
+#[derive(Debug, Resource)]
+pub struct AiServeConfig {
+    /// Relative chance of a fast, flat serve
+    pub fast_weight: f32,
+    /// Relative chance of a slow serve angled upward
+    pub up_weight: f32,
+    /// Relative chance of a slow serve angled downward
+    pub down_weight: f32,
+    /// Speed of a fast serve as a multiple of the minimum ball speed
+    pub fast_speed: f32,
+    /// Launch angle of an angled serve from straight across, in radians
+    pub angle: f32,
+    /// Largest random change to any serve's angle, in radians
+    pub angle_jitter: f32,
+    /// Distance from the center the paddle waits at before an angled serve
+    pub ready_offset: f32,
+}
+
+impl Default for AiServeConfig {
+    fn default() -> Self {
+        Self {
+            // Even odds of each serve
+            fast_weight: 1.0,
+            up_weight: 1.0,
+            down_weight: 1.0,
+            // Noticeably quicker than a normal serve, without being unfair
+            fast_speed: 1.5,
+            // About 30 degrees, enough to reach a wall before the receiver
+            angle: 0.5,
+            // About 6 degrees either way
+            angle_jitter: 0.1,
+            // Waits on the opposite side of the center from where it aims
+            ready_offset: 1.5,
+        }
+    }
+}
+
+/// The kinds of serve a computer-controlled server chooses from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AiServeKind {
+    FastFlat,   // Fast and straight across
+    AngledUp,   // Slow, angled toward the top wall
+    AngledDown, // Slow, angled toward the bottom wall
+}
+
+/// A computer-controlled server's next serve
+#[derive(Debug, Clone, Copy)]
+pub struct AiServe {
+    /// Launch angle from straight across, in radians, positive upward
+    angle: f32,
+    /// Speed as a multiple of the minimum ball speed
+    speed: f32,
+    /// Height the server's paddle waits at
+    ready_y: f32,
+}
+
+impl AiServe {
+    /// Picks a serve at random, weighted by the config
+    fn choose(config: &AiServeConfig) -> Self {
+        let mut rng = rand::thread_rng();
+        let total = config.fast_weight + config.up_weight + config.down_weight;
+        let roll = rng.gen_range(0.0..total.max(f32::EPSILON));
+        let kind = if roll < config.fast_weight {
+            AiServeKind::FastFlat
+        } else if roll < config.fast_weight + config.up_weight {
+            AiServeKind::AngledUp
+        } else {
+            AiServeKind::AngledDown
+        };
+
+        let (angle, speed, ready_y) = match kind {
+            AiServeKind::FastFlat => (0.0, config.fast_speed, 0.0),
+            AiServeKind::AngledUp => (config.angle, 1.0, -config.ready_offset),
+            AiServeKind::AngledDown => (-config.angle, 1.0, config.ready_offset),
+        };
+        let jitter = rng.gen_range(-config.angle_jitter..=config.angle_jitter);
+        Self {
+            angle: angle + jitter,
+            speed,
+            ready_y,
+        }
+    }
+
+    /// Launch direction toward the receiver, `facing` being 1.0 for a
+    /// serve to the right and -1.0 for one to the left
+    fn direction(&self, facing: f32) -> Vec2 {
+        Vec2::new(facing * self.angle.cos(), self.angle.sin())
+    }
+
+    /// Launch velocity toward the receiver, see [`AiServe::direction`]
+    fn velocity(&self, facing: f32, ball_config: &BallConfig) -> Vec2 {
+        self.direction(facing) * ball_config.min_velocity * self.speed
+    }
+}
+
+/// Resource holding the serve a computer-controlled server is about to
+/// make, once it is due
+#[derive(Debug, Resource, Default)]
+pub struct AiServePlan(Option<AiServe>);
+
+impl AiServePlan {
+    /// Height the server's paddle should wait at, if a serve is planned
+    pub fn ready_y(&self) -> Option<f32> {
+        self.0.map(|serve| serve.ready_y)
+    }
+
+    /// Takes the planned serve, choosing one on the spot if none was
+    /// planned, such as for the first serve of a match
+    pub fn take(&mut self, config: &AiServeConfig) -> AiServe {
+        self.0.take().unwrap_or_else(|| AiServe::choose(config))
+    }
+}
+
+/// Whether the given controller serves by itself rather than by a player's
+/// input
+pub fn serves_automatically(controller: Controller) -> bool {
+    matches!(controller, Controller::Ai | Controller::Ghost)
+}
+
+/// Launch velocity for a ball served automatically by a computer-controlled
+/// server, following its planned serve
+pub fn ai_serve_velocity(
+    plan: &mut AiServePlan,
+    config: &AiServeConfig,
+    ball_config: &BallConfig,
+    served_by_left: bool,
+) -> Vec2 {
+    let facing = if served_by_left { 1.0 } else { -1.0 };
+    plan.take(config).velocity(facing, ball_config)
+}
+
+/// Plans a computer-controlled server's serve as soon as it is due, so its
+/// paddle can line up during the serve delay. Held serves are aimed when
+/// they are released instead.
+fn plan_ai_serve(
+    serve_config: Res<ServeConfig>,
+    config: Res<AiServeConfig>,
+    roles: Res<PlayerRoles>,
+    score: Res<Score>,
+    mut plan: ResMut<AiServePlan>,
+) {
+    if !serve_config.manual
+        && score.should_serve
+        && plan.0.is_none()
+        && serves_automatically(roles.controller(score.server()))
+    {
+        plan.0 = Some(AiServe::choose(&config));
+    }
+}
+
+/// Component marking a ball held by its server under the manual serve rule
+#[derive(Component, Debug)]
+pub struct BallHeld {
+    /// The player holding the ball
+    pub server: Player,
+    /// Position of the aim indicator, from -1.0 (steepest down) to 1.0
+    /// (steepest up)
+    aim: f32,
+    /// Whether the indicator is sweeping upward
+    rising: bool,
+    /// Time left before the ball is served automatically
+    timeout: Timer,
+    /// Time left before a server not at this keyboard serves, or `None` for
+    /// the local human
+    think: Option<Timer>,
+}
+
+impl BallHeld {
+    /// Whether the ball waits on the human at this keyboard or screen to
+    /// serve it
+    pub fn awaits_local_serve(&self) -> bool {
+        self.think.is_none()
+    }
+}
+
+/// Query for the paddles' positions, to serve in front of
+pub type PaddlePositions<'w, 's> =
+    Query<'w, 's, (&'static Player, &'static Transform), Without<Ball>>;
+
+/// Where a ball served by `server` is put into play: in front of the
+/// paddle's resting position, so a swing doesn't move it, and level with
+/// the paddle.
+pub fn serve_position(
+    server: Player,
+    paddles: &PaddlePositions,
+    side: &PlayerSide,
+    paddle_config: &PaddleConfig,
+    serve_config: &ServeConfig,
+    board: &BoardConfig,
+) -> Vec2 {
+    let facing = if side.is_left(server) { 1.0 } else { -1.0 };
+    let y = paddles
+        .iter()
+        .find(|(player, _)| **player == server)
+        .map_or(0.0, |(_, transform)| transform.translation.y);
+    Vec2::new(
+        side.paddle_x(server, paddle_config, board) + facing * serve_config.hold_distance,
+        y,
+    )
+}
+
+/// Makes a newly served ball wait on the server's paddle.
+///
+/// The ball is switched to a kinematic body with its collider disabled and
+/// stays put until [`release_held_balls`] launches it.
+pub fn hold_ball(
+    ball: &mut EntityCommands,
+    server: Player,
+    roles: &PlayerRoles,
+    config: &ServeConfig,
+) {
+    let think = (roles.controller(server) != Controller::Human).then(|| {
+        let seconds = rand::thread_rng().gen_range(config.min_think_time..=config.max_think_time);
+        Timer::from_seconds(seconds, TimerMode::Once)
+    });
+
+    ball.insert((
+        BallHeld {
+            server,
+            aim: 0.0,
+            rising: true,
+            timeout: Timer::from_seconds(config.timeout, TimerMode::Once),
+            think,
+        },
+        RigidBody::KinematicPositionBased,
+        ColliderDisabled,
+        Velocity::zero(),
+    ));
+}
+
+/// Direction a held ball would be launched in, toward the receiver
+fn launch_direction(held: &BallHeld, side: &PlayerSide, config: &ServeConfig) -> Vec2 {
+    let facing = if side.is_left(held.server) { 1.0 } else { -1.0 };
+    let angle = held.aim * config.max_angle;
+    Vec2::new(facing * angle.cos(), angle.sin())
+}
+
+/// Keeps held balls in front of their server's paddle.
+fn follow_server(
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    serve_config: Res<ServeConfig>,
+    side: Res<PlayerSide>,
+    paddle_query: PaddlePositions,
+    mut ball_query: Query<(&BallHeld, &mut Transform), With<Ball>>,
+) {
+    for (held, mut transform) in ball_query.iter_mut() {
+        let position = serve_position(
+            held.server,
+            &paddle_query,
+            &side,
+            &paddle_config,
+            &serve_config,
+            &board,
+        );
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// Sweeps the aim indicators up and down.
+fn sweep_aim(time: Res<Time>, config: Res<ServeConfig>, mut query: Query<&mut BallHeld>) {
+    let step = 2.0 * time.delta_secs() / config.sweep_time;
+    for mut held in query.iter_mut() {
+        let aim = held.aim + if held.rising { step } else { -step };
+        if aim >= 1.0 {
+            held.rising = false;
+        } else if aim <= -1.0 {
+            held.rising = true;
+        }
+        held.aim = aim.clamp(-1.0, 1.0);
+    }
+}
+
+/// Launches held balls once their server serves, their think time is up or
+/// they have been held too long.
+///
+/// The ball leaves at the minimum speed along the aim indicator, with its
+/// physics and collider restored.
+#[allow(clippy::too_many_arguments)]
+fn release_held_balls(
+    mut commands: Commands,
+    time: Res<Time>,
+    input: Res<ButtonInput<KeyCode>>,
+    serve_config: Res<ServeConfig>,
+    ball_config: Res<BallConfig>,
+    side: Res<PlayerSide>,
+    mut touch_serves: EventReader<TouchServe>,
+    mut query: Query<(Entity, &mut BallHeld, &mut Velocity)>,
+) {
+    let touched = touch_serves.read().count() > 0;
+    let serve_pressed = touched || input.any_just_pressed([KeyCode::KeyE, KeyCode::ControlRight]);
+
+    for (entity, mut held, mut velocity) in query.iter_mut() {
+        let timed_out = held.timeout.tick(time.delta()).finished();
+        let served = match held.think.as_mut() {
+            Some(think) => think.tick(time.delta()).finished(),
+            None => serve_pressed,
+        };
+        if !served && !timed_out {
+            continue;
+        }
+
+        velocity.linvel = launch_direction(&held, &side, &serve_config) * ball_config.min_velocity;
+        commands
+            .entity(entity)
+            .remove::<(BallHeld, ColliderDisabled)>()
+            .insert(RigidBody::Dynamic);
+    }
+}
+
+/// Component for a dot of the serve preview, numbered from the serve's
+/// starting point outward
+#[derive(Component)]
+struct ServePreviewDot(usize);
+
+/// Shows a dotted line along the next serve's direction while the serve
+/// delay runs, and removes it once the ball is served.
+///
+/// The dots start from where the ball will be served, following the
+/// server's paddle. A computer-controlled server's dots follow its planned
+/// serve, and everyone else's go straight across. Held serves have their
+/// own aim indicator, so they aren't previewed, and neither is anything
+/// during an instant replay.
+#[allow(clippy::too_many_arguments)]
+fn update_serve_preview(
+    mut commands: Commands,
+    config: Res<ServeConfig>,
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    roles: Res<PlayerRoles>,
+    side: Res<PlayerSide>,
+    score: Res<Score>,
+    plan: Res<AiServePlan>,
+    theme: Res<Theme>,
+    replay: Res<InstantReplay>,
+    paddle_query: PaddlePositions,
+    mut dot_query: Query<(Entity, &ServePreviewDot, &mut Transform), Without<Player>>,
+) {
+    let shown = config.preview && !config.manual && score.should_serve && !replay.is_playing();
+    if !shown {
+        for (entity, _, _) in dot_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let server = score.server();
+    let facing = if side.is_left(server) { 1.0 } else { -1.0 };
+    let direction = if serves_automatically(roles.controller(server)) {
+        plan.0.map(|serve| serve.direction(facing))
+    } else {
+        None
+    }
+    .unwrap_or(Vec2::new(facing, 0.0));
+    let origin = serve_position(
+        server,
+        &paddle_query,
+        &side,
+        &paddle_config,
+        &config,
+        &board,
+    );
+    // Dots start a step out so the first isn't hidden by the ball
+    let dot_position =
+        |index: usize| origin + direction * config.preview_spacing * (index + 1) as f32;
+
+    if dot_query.is_empty() {
+        let color = theme.colors().ball.with_alpha(config.preview_alpha);
+        for index in 0..config.preview_dots {
+            commands.spawn((
+                ServePreviewDot(index),
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(PREVIEW_DOT_SIZE)),
+                    ..default()
+                },
+                // Drawn above the center line
+                Transform::from_translation(dot_position(index).extend(0.5)),
+            ));
+        }
+        return;
+    }
+
+    // A computer's serve may be planned after the dots appear, and the
+    // server's paddle moves during the delay
+    for (_, dot, mut transform) in dot_query.iter_mut() {
+        let position = dot_position(dot.0);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+}
+
+/// Component for the stand-in shown where the next ball will be served
+/// during the serve delay
+#[derive(Component)]
+struct WaitingBall;
+
+/// Filter for the waiting ball, kept apart from the paddles it follows
+type WaitingBallOnly = (With<WaitingBall>, Without<Player>);
+
+/// Shows the next ball waiting in front of the server's paddle while the
+/// serve delay runs, and removes it once the real ball is served there.
+///
+/// The stand-in follows the paddle but has no physics, so nothing can hit
+/// it and a pending serve still never coexists with a ball in play. It is
+/// hidden during an instant replay.
+#[allow(clippy::too_many_arguments)]
+fn show_waiting_ball(
+    mut commands: Commands,
+    board: Res<BoardConfig>,
+    paddle_config: Res<PaddleConfig>,
+    serve_config: Res<ServeConfig>,
+    side: Res<PlayerSide>,
+    score: Res<Score>,
+    replay: Res<InstantReplay>,
+    ball_assets: Res<BallAssets>,
+    paddle_query: PaddlePositions,
+    mut waiting_query: Query<(Entity, &mut Transform), WaitingBallOnly>,
+) {
+    if !score.should_serve || replay.is_playing() {
+        for (entity, _) in waiting_query.iter() {
+            commands.entity(entity).despawn();
+        }
+        return;
+    }
+
+    let position = serve_position(
+        score.server(),
+        &paddle_query,
+        &side,
+        &paddle_config,
+        &serve_config,
+        &board,
+    );
+    match waiting_query.get_single_mut() {
+        Ok((_, mut transform)) => {
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+        }
+        Err(_) => {
+            commands.spawn((
+                WaitingBall,
+                Mesh2d(ball_assets.mesh.clone()),
+                MeshMaterial2d(ball_assets.material.clone()),
+                Transform::from_translation(position.extend(0.0)),
+            ));
+        }
+    }
+}
+
+/// Removes the serve preview when leaving gameplay.
+fn cleanup_serve_preview(mut commands: Commands, query: Query<Entity, With<ServePreviewDot>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Removes the waiting ball when leaving gameplay.
+fn cleanup_waiting_ball(mut commands: Commands, query: Query<Entity, With<WaitingBall>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Draws the aim indicator of each held ball.
+fn draw_aim_indicator(
+    mut gizmos: Gizmos,
+    config: Res<ServeConfig>,
+    side: Res<PlayerSide>,
+    theme: Res<Theme>,
+    query: Query<(&BallHeld, &Transform)>,
+) {
+    let color = theme.colors().ball;
+    for (held, transform) in query.iter() {
+        let start = transform.translation.truncate();
+        let end = start + launch_direction(held, &side, &config) * config.indicator_length;
+        gizmos.line_2d(start, end, color);
+    }
+}
+
+/// Plugin that manages the manual serve rule.
+pub struct ServePlugin;
+
+impl Plugin for ServePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ServeConfig>()
+            .init_resource::<AiServeConfig>()
+            .init_resource::<AiServePlan>()
+            .add_systems(
+                Update,
+                (
+                    plan_ai_serve,
+                    follow_server,
+                    sweep_aim,
+                    release_held_balls,
+                    draw_aim_indicator,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(not_replaying)),
+            )
+            // Also runs during instant replays, to hide the preview
+            .add_systems(
+                Update,
+                (update_serve_preview.after(plan_ai_serve), show_waiting_ball)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                (cleanup_serve_preview, cleanup_waiting_ball),
+            );
+    }
+}
@@ -0,0 +1,269 @@
+//! Settings Module
+//!
+//! This module keeps the player's chosen settings between runs, including:
+//! - The match setup picked in the menu: mode, side, target score,
+//!   tiebreak, court, difficulty and handicaps
+//! - The match rules picked on the settings screen: aim assist, dramatic
+//!   saves and manual serves
+//! - The color theme
+//! - Music and sound effects
+//! - The graphics toggles, including the hit sparks, the speedometer and
+//!   the wall flashes
+//!
+//! The settings are loaded when the app is built and inserted as the
+//! resources they belong to, before the other plugins initialize theirs, so
+//! the game starts with them already in place. They are saved through the
+//! storage module whenever one of them changes, held off while the settings
+//! screen is open so a run of changes there is saved once it closes, or
+//! when the game quits with it still open. A file saved by an older
+//! version, missing some settings, keeps the defaults for those, and a
+//! missing or unreadable one starts from the defaults.
+//!
+//! The opponent isn't kept, since the player's ghost may not be recorded
+//! the next time.
+
+use crate::assist::AssistSettings;
+use crate::audio::AudioSettings;
+use crate::board::{BoardConfig, CourtPreset};
+use crate::camera::GraphicsSettings;
+use crate::clutch::ClutchConfig;
+use crate::mode::GameMode;
+use crate::options::settings_closed;
+use crate::player::{Handicaps, PlayerSide, RubberBandConfig};
+use crate::score::{ScoreRules, Tiebreak};
+use crate::serve::ServeConfig;
+use crate::speedometer::SpeedometerConfig;
+use crate::storage;
+use crate::theme::Theme;
+use crate::wallflash::WallFlashConfig;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Key the settings are stored under
+const SETTINGS_KEY: &str = "settings";
+
+/// The player's chosen settings, as they are stored between runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+// Settings added later are filled in, so older files still load
+#[serde(default)]
+pub struct Settings {
+    /// Selected game mode
+    pub mode: GameMode,
+    /// Side the human defends
+    pub side: PlayerSide,
+    /// Points needed to win
+    pub target: u32,
+    /// How a game that reaches deuce is decided
+    pub tiebreak: Tiebreak,
+    /// Board size
+    pub court: CourtPreset,
+    /// Whether the AI adapts to the score gap
+    pub adaptive_ai: bool,
+    /// Whether human players get the aim assist marker
+    pub aim_assist: bool,
+    /// Whether dramatic saves are on
+    pub dramatic_saves: bool,
+    /// Whether served balls wait on the server's paddle
+    pub manual_serve: bool,
+    /// Both players' handicaps
+    pub handicaps: Handicaps,
+    /// Color theme
+    pub theme: Theme,
+    /// Whether background music plays
+    pub music: bool,
+    /// Whether sound effects play
+    pub sound_effects: bool,
+    /// Whether the CRT effect is drawn
+    pub crt: bool,
+    /// Whether the camera zooms toward the action
+    pub zoom: bool,
+    /// Whether the center line pulses
+    pub center_line_pulse: bool,
+    /// Whether the background grid is drawn
+    pub background_grid: bool,
+    /// Whether sparks fly off paddle hits
+    pub hit_sparks: bool,
+    /// Whether the speedometer is shown
+    pub speedometer: bool,
+    /// Whether the walls flash on goals and bounces
+    pub wall_flash: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        let rules = ScoreRules::default();
+        let graphics = GraphicsSettings::default();
+        let audio = AudioSettings::default();
+        Self {
+            mode: GameMode::default(),
+            side: PlayerSide::default(),
+            target: rules.target,
+            tiebreak: rules.tiebreak,
+            court: CourtPreset::default(),
+            adaptive_ai: RubberBandConfig::default().enabled,
+            aim_assist: AssistSettings::default().aim_assist,
+            dramatic_saves: ClutchConfig::default().enabled,
+            manual_serve: ServeConfig::default().manual,
+            handicaps: Handicaps::default(),
+            theme: Theme::default(),
+            music: audio.music_enabled,
+            sound_effects: audio.sfx_enabled,
+            crt: graphics.crt_enabled,
+            zoom: graphics.zoom_enabled,
+            center_line_pulse: graphics.center_line_pulse,
+            background_grid: graphics.background_grid,
+            hit_sparks: graphics.hit_sparks,
+            speedometer: SpeedometerConfig::default().enabled,
+            wall_flash: WallFlashConfig::default().enabled,
+        }
+    }
+}
+
+/// The resources the settings are kept in
+type SettingsResources<'w> = (
+    (
+        Res<'w, GameMode>,
+        Res<'w, PlayerSide>,
+        Res<'w, ScoreRules>,
+        Res<'w, BoardConfig>,
+        Res<'w, RubberBandConfig>,
+        Res<'w, AssistSettings>,
+        Res<'w, ClutchConfig>,
+        Res<'w, ServeConfig>,
+        Res<'w, Handicaps>,
+    ),
+    (
+        Res<'w, Theme>,
+        Res<'w, AudioSettings>,
+        Res<'w, GraphicsSettings>,
+        Res<'w, SpeedometerConfig>,
+        Res<'w, WallFlashConfig>,
+    ),
+);
+
+impl Settings {
+    /// Loads the stored settings, or the defaults if there are none or they
+    /// can't be read
+    fn load() -> Self {
+        storage::load(SETTINGS_KEY).unwrap_or_default()
+    }
+
+    /// The settings as they currently are in their resources
+    fn capture(
+        (
+            (mode, side, rules, board, rubber_band, assist, clutch, serve, handicaps),
+            (theme, audio, graphics, speedometer, wall_flash),
+        ): &SettingsResources,
+    ) -> Self {
+        Self {
+            mode: **mode,
+            side: **side,
+            target: rules.target,
+            tiebreak: rules.tiebreak,
+            court: CourtPreset::of(board).unwrap_or_default(),
+            adaptive_ai: rubber_band.enabled,
+            aim_assist: assist.aim_assist,
+            dramatic_saves: clutch.enabled,
+            manual_serve: serve.manual,
+            handicaps: **handicaps,
+            theme: **theme,
+            music: audio.music_enabled,
+            sound_effects: audio.sfx_enabled,
+            crt: graphics.crt_enabled,
+            zoom: graphics.zoom_enabled,
+            center_line_pulse: graphics.center_line_pulse,
+            background_grid: graphics.background_grid,
+            hit_sparks: graphics.hit_sparks,
+            speedometer: speedometer.enabled,
+            wall_flash: wall_flash.enabled,
+        }
+    }
+
+    /// Inserts the resources the settings are kept in, with everything the
+    /// settings don't cover at its default
+    fn insert_resources(&self, app: &mut App) {
+        let mut board = BoardConfig::default();
+        self.court.apply(&mut board);
+
+        app.insert_resource(self.mode)
+            .insert_resource(self.side)
+            .insert_resource(ScoreRules {
+                target: self.target,
+                tiebreak: self.tiebreak,
+                ..default()
+            })
+            .insert_resource(board)
+            .insert_resource(RubberBandConfig {
+                enabled: self.adaptive_ai,
+                ..default()
+            })
+            .insert_resource(AssistSettings {
+                aim_assist: self.aim_assist,
+                ..default()
+            })
+            .insert_resource(ClutchConfig {
+                enabled: self.dramatic_saves,
+                ..default()
+            })
+            .insert_resource(ServeConfig {
+                manual: self.manual_serve,
+                ..default()
+            })
+            .insert_resource(self.handicaps)
+            .insert_resource(self.theme)
+            .insert_resource(AudioSettings {
+                music_enabled: self.music,
+                sfx_enabled: self.sound_effects,
+            })
+            .insert_resource(GraphicsSettings {
+                crt_enabled: self.crt,
+                zoom_enabled: self.zoom,
+                center_line_pulse: self.center_line_pulse,
+                background_grid: self.background_grid,
+                hit_sparks: self.hit_sparks,
+                ..default()
+            })
+            .insert_resource(SpeedometerConfig {
+                enabled: self.speedometer,
+                ..default()
+            })
+            .insert_resource(WallFlashConfig {
+                enabled: self.wall_flash,
+                ..default()
+            });
+    }
+}
+
+/// Saves the settings whenever one of them has changed.
+///
+/// Runs while the settings screen is closed, and on the frame the game
+/// quits, so changes made just before quitting aren't lost.
+///
+/// The first run only notes the settings the game started with, so nothing
+/// is written until the player changes something.
+fn save_settings(resources: SettingsResources, mut saved: Local<Option<Settings>>) {
+    let settings = Settings::capture(&resources);
+    if saved.as_ref() == Some(&settings) {
+        return;
+    }
+    if saved.is_some() {
+        storage::save(SETTINGS_KEY, &settings);
+    }
+    *saved = Some(settings);
+}
+
+/// Plugin that loads the player's settings and saves their changes.
+///
+/// Must be added before the plugins owning the settings' resources, which
+/// only initialize them when they aren't there already.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        Settings::load().insert_resources(app);
+        app.add_systems(
+            Last,
+            save_settings.run_if(settings_closed.or(on_event::<AppExit>)),
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Hit Sparks Module
+//!
+//! This module throws a small burst of sparks off the paddle whenever it
+//! hits the ball, including:
+//! - Spawning the sparks at the point of contact
+//! - Sending each one outward at its own speed and angle
+//! - Fading and despawning each one on its own timer
+//! - Clearing any sparks still flying when play stops
+//!
+//! Bursts are kept to a handful of plain sprites, so they stay cheap on the
+//! web. The sparks are turned off in [`GraphicsSettings`].
+
+use crate::camera::GraphicsSettings;
+use crate::player::PaddleHit;
+use crate::theme::Theme;
+use crate::GameState;
+use bevy::prelude::*;
+use rand::Rng;
+use std::f32::consts::TAU;
+
+/// Configuration for the hit sparks
+#[derive(Debug, Resource)]
+pub struct SparkConfig {
+    /// Number of sparks in each burst
+    pub count: usize,
+    /// Slowest a spark sets off, in world units per second
+    pub min_speed: f32,
+    /// Fastest a spark sets off, in world units per second
+    pub max_speed: f32,
+    /// Shortest a spark lasts, in seconds
+    pub min_lifetime: f32,
+    /// Longest a spark lasts, in seconds
+    pub max_lifetime: f32,
+    /// Size of each spark in world units
+    pub size: f32,
+}
+
+impl Default for SparkConfig {
+    fn default() -> Self {
+        Self {
+            count: 8,          // A burst, not a fountain
+            min_speed: 2.0,    // Enough to clear the ball
+            max_speed: 5.0,    // Still close by when it fades
+            min_lifetime: 0.2, // Gone before the ball is far away
+            max_lifetime: 0.4,
+            size: 0.06, // Smaller than the ball
+        }
+    }
+}
+
+/// Component for a spark flying off a hit
+#[derive(Component, Debug)]
+struct Spark {
+    /// Velocity in world units per second
+    velocity: Vec2,
+    /// Time left before the spark despawns
+    timer: Timer,
+}
+
+/// Plugin that throws sparks off paddle hits.
+pub struct SparksPlugin;
+
+impl Plugin for SparksPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SparkConfig>()
+            .init_resource::<GraphicsSettings>()
+            .add_systems(
+                Update,
+                (spawn_hit_sparks, update_sparks)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), despawn_sparks);
+    }
+}
+
+/// Spawns a burst of sparks where each paddle hit happened.
+fn spawn_hit_sparks(
+    mut commands: Commands,
+    config: Res<SparkConfig>,
+    settings: Res<GraphicsSettings>,
+    theme: Res<Theme>,
+    mut hits: EventReader<PaddleHit>,
+) {
+    if !settings.hit_sparks {
+        hits.clear();
+        return;
+    }
+
+    let mut rng = rand::thread_rng();
+    let color = theme.colors().ball;
+    for hit in hits.read() {
+        for _ in 0..config.count {
+            let direction = Vec2::from_angle(rng.gen_range(0.0..TAU));
+            let speed = rng.gen_range(config.min_speed..=config.max_speed);
+            let lifetime = rng.gen_range(config.min_lifetime..=config.max_lifetime);
+            commands.spawn((
+                Spark {
+                    velocity: direction * speed,
+                    timer: Timer::from_seconds(lifetime, TimerMode::Once),
+                },
+                Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(config.size)),
+                    ..default()
+                },
+                // In front of the paddles and the ball
+                Transform::from_translation(hit.position.extend(2.0)),
+            ));
+        }
+    }
+}
+
+/// Moves each spark along, fading it out over its lifetime, and despawns it
+/// once its time is up.
+fn update_sparks(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Spark, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut spark, mut transform, mut sprite) in query.iter_mut() {
+        if spark.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation += (spark.velocity * time.delta_secs()).extend(0.0);
+        sprite.color.set_alpha(spark.timer.fraction_remaining());
+    }
+}
+
+/// Removes any sparks still flying when play stops.
+fn despawn_sparks(mut commands: Commands, query: Query<Entity, With<Spark>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,302 @@
+//! Speedometer Module
+//!
+//! This module tracks how fast the ball travels and shows it on an optional
+//! speedometer, including:
+//! - Measuring the ball's speed every physics step
+//! - The top speed of each rally and of the whole match
+//! - A small readout in the bottom right corner, toggled with F2
+//! - Flashing the readout when a rally beats the match's top speed
+//!
+//! Speeds can be shown in world units per second, or converted to "km/h"
+//! by an arbitrary factor for fun. The readout is only rewritten when the
+//! shown value changes by a visible amount, so it doesn't flicker through
+//! every small correction of the ball's speed.
+
+use crate::ball::{maintain_ball_velocity, Ball};
+use crate::palette::VICTORY_GREEN;
+use crate::replay::not_replaying;
+use crate::score::{MatchRestarted, ServeStarted};
+use crate::theme::Theme;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Color the readout flashes when a rally beats the match's top speed
+const FLASH_COLOR: Color = VICTORY_GREEN;
+
+/// Units the speedometer shows speeds in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpeedUnit {
+    /// World units per second, as the physics measures them
+    #[default]
+    WorldUnits,
+    /// World units per second scaled by [`SpeedometerConfig::kmh_scale`]
+    Kmh,
+}
+
+impl SpeedUnit {
+    /// Abbreviation shown after the number
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpeedUnit::WorldUnits => "u/s",
+            SpeedUnit::Kmh => "km/h",
+        }
+    }
+}
+
+/// Configuration for the speedometer
+#[derive(Debug, Resource)]
+pub struct SpeedometerConfig {
+    /// Whether the speedometer is shown
+    pub enabled: bool,
+    /// Units speeds are shown in
+    pub unit: SpeedUnit,
+    /// "km/h" shown per world unit per second
+    pub kmh_scale: f32,
+    /// Smallest change in the shown value, in the configured units, that
+    /// rewrites the readout
+    pub step: f32,
+    /// How long the readout flashes on a new match top speed, in seconds
+    pub flash_duration: f32,
+}
+
+impl Default for SpeedometerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,              // Toggled with F2
+            unit: SpeedUnit::WorldUnits, // What the physics measures
+            kmh_scale: 6.0,              // A fast rally reads like a hard smash
+            step: 0.5,                   // Ignores the speed clamp's small corrections
+            flash_duration: 0.8,         // Long enough to catch the eye mid-rally
+        }
+    }
+}
+
+impl SpeedometerConfig {
+    /// Converts a speed in world units per second to the configured units
+    pub fn convert(&self, speed: f32) -> f32 {
+        match self.unit {
+            SpeedUnit::WorldUnits => speed,
+            SpeedUnit::Kmh => speed * self.kmh_scale,
+        }
+    }
+}
+
+/// Resource tracking the ball's speed over the current match, in world
+/// units per second
+#[derive(Debug, Resource, Default)]
+pub struct RallyStats {
+    /// Speed of the fastest ball in play at the last physics step
+    pub speed: f32,
+    /// Top speed reached since the ball was last served
+    pub rally_top_speed: f32,
+    /// Top speed reached in the match's earlier rallies
+    earlier_top_speed: f32,
+    /// Whether this rally has already beaten the earlier rallies
+    beaten: bool,
+}
+
+impl RallyStats {
+    /// Top speed reached so far in the match
+    pub fn match_top_speed(&self) -> f32 {
+        self.earlier_top_speed.max(self.rally_top_speed)
+    }
+}
+
+/// Sent when a rally beats the top speed of the match's earlier rallies.
+/// The match's first rally sets the bar rather than beating it.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct NewTopSpeed {
+    /// The new top speed in world units per second
+    pub speed: f32,
+}
+
+/// Component for the speedometer readout
+#[derive(Component)]
+struct Speedometer {
+    /// Value the readout shows, in the configured units, or `None` before
+    /// it has been written
+    shown: Option<f32>,
+    /// Seconds left of the new top speed flash
+    flash: f32,
+}
+
+/// Plugin that measures the ball's speed and shows the speedometer.
+pub struct SpeedometerPlugin;
+
+impl Plugin for SpeedometerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpeedometerConfig>()
+            .init_resource::<RallyStats>()
+            .add_event::<NewTopSpeed>()
+            // A new match starts from the splash screen, the menu or a rematch
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Splash,
+                    entered: GameState::Playing,
+                },
+                reset_rally_stats,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                reset_rally_stats,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::GameOver,
+                    entered: GameState::Playing,
+                },
+                reset_rally_stats,
+            )
+            // After the speed clamp, so the speed measured is the one the
+            // ball actually travels at
+            .add_systems(
+                FixedUpdate,
+                record_ball_speed
+                    .after(maintain_ball_velocity)
+                    .run_if(in_state(GameState::Playing).and(not_replaying)),
+            )
+            .add_systems(
+                Update,
+                (
+                    start_rally,
+                    toggle_speedometer,
+                    sync_speedometer,
+                    update_speedometer,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), despawn_speedometer);
+    }
+}
+
+/// Clears the speeds when a new match begins.
+fn reset_rally_stats(mut stats: ResMut<RallyStats>) {
+    *stats = RallyStats::default();
+}
+
+/// Folds the finished rally into the match's top speed when the next ball
+/// is served, and starts the match over on a restart.
+fn start_rally(
+    mut stats: ResMut<RallyStats>,
+    mut serves: EventReader<ServeStarted>,
+    mut restarts: EventReader<MatchRestarted>,
+) {
+    if restarts.read().count() > 0 {
+        *stats = RallyStats::default();
+    }
+    if serves.read().count() > 0 {
+        stats.earlier_top_speed = stats.match_top_speed();
+        stats.rally_top_speed = 0.0;
+        stats.beaten = false;
+    }
+}
+
+/// Measures the fastest ball in play, and sends [`NewTopSpeed`] the first
+/// time each rally beats the earlier rallies.
+fn record_ball_speed(
+    mut stats: ResMut<RallyStats>,
+    mut new_tops: EventWriter<NewTopSpeed>,
+    ball_query: Query<&Velocity, With<Ball>>,
+) {
+    stats.speed = ball_query
+        .iter()
+        .map(|velocity| velocity.linvel.length())
+        .fold(0.0, f32::max);
+    stats.rally_top_speed = stats.rally_top_speed.max(stats.speed);
+
+    if !stats.beaten && stats.earlier_top_speed > 0.0 && stats.speed > stats.earlier_top_speed {
+        stats.beaten = true;
+        new_tops.send(NewTopSpeed { speed: stats.speed });
+    }
+}
+
+/// Toggles the speedometer with F2.
+fn toggle_speedometer(keyboard: Res<ButtonInput<KeyCode>>, mut config: ResMut<SpeedometerConfig>) {
+    if keyboard.just_pressed(KeyCode::F2) {
+        config.enabled = !config.enabled;
+    }
+}
+
+/// Spawns or despawns the readout to match the setting.
+fn sync_speedometer(
+    mut commands: Commands,
+    config: Res<SpeedometerConfig>,
+    theme: Res<Theme>,
+    query: Query<Entity, With<Speedometer>>,
+) {
+    if !config.enabled {
+        for entity in query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    if query.is_empty() {
+        commands.spawn((
+            Speedometer {
+                shown: None,
+                flash: 0.0,
+            },
+            Text::default(),
+            TextFont {
+                font_size: theme.font_size(20.0),
+                ..default()
+            },
+            TextColor(theme.colors().text),
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                bottom: Val::Px(10.0),
+                ..default()
+            },
+        ));
+    }
+}
+
+/// Rewrites the readout when the speed has visibly changed, and flashes it
+/// on a new match top speed.
+fn update_speedometer(
+    time: Res<Time>,
+    config: Res<SpeedometerConfig>,
+    stats: Res<RallyStats>,
+    theme: Res<Theme>,
+    mut new_tops: EventReader<NewTopSpeed>,
+    mut query: Query<(&mut Speedometer, &mut Text, &mut TextColor)>,
+) {
+    let new_top = new_tops.read().count() > 0;
+    let speed = config.convert(stats.speed);
+
+    for (mut speedometer, mut text, mut color) in query.iter_mut() {
+        // A change of units rewrites the readout straight away
+        let stale = config.is_changed()
+            || speedometer
+                .shown
+                .is_none_or(|shown| (shown - speed).abs() >= config.step);
+        if stale {
+            speedometer.shown = Some(speed);
+            **text = format!("{:.1} {}", speed, config.unit.label());
+        }
+
+        if new_top {
+            speedometer.flash = config.flash_duration;
+        }
+        speedometer.flash = (speedometer.flash - time.delta_secs()).max(0.0);
+        color.0 = if speedometer.flash > 0.0 {
+            FLASH_COLOR
+        } else {
+            theme.colors().text
+        };
+    }
+}
+
+/// Removes the readout when play stops.
+fn despawn_speedometer(mut commands: Commands, query: Query<Entity, With<Speedometer>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -3,34 +3,53 @@
 //! This module handles the game's splash screen, including:
 //! - Initial screen display and layout
 //! - Title and prompt rendering
-//! - Input handling for game start
-//! - Transition to gameplay
+//! - Letting the attract demo show through
+//! - Moving on to the main menu after a moment, or on any other key or a
+//!   click
+//! - Transition straight to gameplay with space
 //!
-//! The splash screen serves as the initial game state and
-//! provides a clean entry point to the game.
+//! The splash screen serves as the initial game state and provides a clean
+//! entry point to the game. Players who don't care about the options start
+//! playing with space, and everyone else lands in the main menu.
 
+use crate::attract::AttractConfig;
+use crate::palette::SPLASH_OVERLAY_ALPHA;
+use crate::theme::{Theme, Themed};
 use crate::GameState;
 use bevy::prelude::*;
 
+/// How long the splash screen stays up before the menu, in seconds
+const SPLASH_DURATION: f32 = 3.0;
+
 /// Plugin that manages the splash screen functionality.
 ///
 /// This plugin coordinates:
 /// - Splash screen creation on game start
-/// - Input handling for transitioning to gameplay
-/// - Cleanup when transitioning to game
+/// - Input handling for transitioning to the menu or gameplay
+/// - Cleanup when leaving the splash screen
 pub struct SplashPlugin;
 
+/// Resource timing how long the splash screen has been up
+#[derive(Resource, Debug)]
+struct SplashTimer(Timer);
+
+impl Default for SplashTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(SPLASH_DURATION, TimerMode::Once))
+    }
+}
+
 /// Marker component for identifying splash screen UI elements.
-/// Used for querying and cleanup when transitioning to gameplay.
+/// Used for querying and cleanup when leaving the splash screen.
 #[derive(Component)]
 struct SplashScreen;
 
 impl Plugin for SplashPlugin {
     fn build(&self, app: &mut App) {
-        app
+        app.init_resource::<SplashTimer>()
             // Create splash screen when entering Splash state
             .add_systems(OnEnter(GameState::Splash), spawn_splash_screen)
-            // Handle space bar input while in Splash state
+            // Handle input while in Splash state
             .add_systems(
                 Update,
                 handle_splash_input.run_if(in_state(GameState::Splash)),
@@ -45,12 +64,28 @@ impl Plugin for SplashPlugin {
 /// Creates a full-screen layout containing:
 /// - Game title ("Rusty Pong")
 /// - Start prompt ("Press SPACE to start")
+/// - A hint that any other key opens the menu
 ///
 /// The layout uses flexbox for:
 /// - Vertical stacking of elements
 /// - Center alignment both horizontally and vertically
-/// - Full screen coverage with black background
-fn spawn_splash_screen(mut commands: Commands) {
+/// - Full screen coverage with the theme background, see-through over the
+///   attract demo
+fn spawn_splash_screen(
+    mut commands: Commands,
+    theme: Res<Theme>,
+    attract: Res<AttractConfig>,
+    mut timer: ResMut<SplashTimer>,
+) {
+    timer.0.reset();
+    let colors = theme.colors();
+    // The attract demo shows through when it plays
+    let background = if attract.enabled {
+        colors.background.with_alpha(SPLASH_OVERLAY_ALPHA)
+    } else {
+        colors.background
+    };
+
     // Create root container node
     commands
         .spawn((
@@ -69,8 +104,9 @@ fn spawn_splash_screen(mut commands: Commands) {
                 height: Val::Percent(100.0),
                 ..default()
             },
-            // Black background
-            BackgroundColor(Color::BLACK),
+            // Theme background
+            BackgroundColor(background),
+            Themed::Background,
             Visibility::default(),
         ))
         .with_children(|parent| {
@@ -78,10 +114,11 @@ fn spawn_splash_screen(mut commands: Commands) {
             parent.spawn((
                 Text::new("RUSTY PONG"),
                 TextFont {
-                    font_size: 80.0, // Large, prominent title
+                    font_size: theme.font_size(80.0), // Large, prominent title
                     ..default()
                 },
-                TextColor(Color::WHITE),
+                TextColor(colors.text),
+                Themed::Text,
                 Node {
                     // Add space below title
                     margin: UiRect::bottom(Val::Px(20.0)),
@@ -93,29 +130,56 @@ fn spawn_splash_screen(mut commands: Commands) {
             parent.spawn((
                 Text::new("Press SPACE to start"),
                 TextFont {
-                    font_size: 40.0, // Smaller than title
+                    font_size: theme.font_size(40.0), // Smaller than title
+                    ..default()
+                },
+                TextColor(colors.text),
+                Themed::Text,
+                Node {
+                    // Add space below the prompt
+                    margin: UiRect::bottom(Val::Px(20.0)),
                     ..default()
                 },
-                TextColor(Color::WHITE),
-                Node::default(),
+            ));
+
+            // Menu hint
+            parent.spawn((
+                Text::new("or any other key for the menu"),
+                TextFont {
+                    font_size: theme.font_size(24.0), // Small, secondary text
+                    ..default()
+                },
+                TextColor(colors.get(Themed::DimText)),
+                Themed::DimText,
             ));
         });
 }
 
-/// Handles keyboard input on the splash screen.
+/// Handles input on the splash screen.
 ///
-/// Watches for space bar press and transitions to
-/// the Playing state when detected.
+/// Space transitions straight to the Playing state with the settings as
+/// they are. Any other key or a click moves on to the menu, as does
+/// leaving the splash screen up for a few seconds.
 fn handle_splash_input(
-    keyboard: Res<ButtonInput<KeyCode>>, // Keyboard input resource
+    keyboard: Res<ButtonInput<KeyCode>>,  // Keyboard input resource
+    mouse: Res<ButtonInput<MouseButton>>, // Mouse input resource
+    time: Res<Time>,                      // For the splash screen's timer
+    mut timer: ResMut<SplashTimer>,       // Time the splash screen has been up
     mut next_state: ResMut<NextState<GameState>>, // For state transitions
 ) {
     if keyboard.just_pressed(KeyCode::Space) {
         next_state.set(GameState::Playing); // Start the game
+        return;
+    }
+
+    let skipped =
+        keyboard.get_just_pressed().next().is_some() || mouse.get_just_pressed().next().is_some();
+    if timer.0.tick(time.delta()).finished() || skipped {
+        next_state.set(GameState::Menu);
     }
 }
 
-/// Cleans up splash screen entities when transitioning to gameplay.
+/// Cleans up splash screen entities when leaving the splash screen.
 ///
 /// Queries for all entities marked with the SplashScreen component
 /// and recursively despawns them and their children.
@@ -0,0 +1,93 @@
+//! Storage Module
+//!
+//! This module keeps small pieces of data between runs of the game, such as
+//! the player's profile and settings. Each entry is saved under a key: as a
+//! RON file in the working directory on native builds, like saved replays,
+//! and as a `localStorage` item in the browser.
+//!
+//! A missing entry isn't an error. Entries that can't be read or parsed are
+//! logged and treated as missing, and failed saves are logged rather than
+//! interrupting the game.
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Prefix shared by the names of all stored entries
+const PREFIX: &str = "rusty_pong_";
+
+/// File an entry is stored in
+#[cfg(not(target_arch = "wasm32"))]
+fn path(key: &str) -> String {
+    format!("{}{}.ron", PREFIX, key)
+}
+
+/// Returns the text stored under `key`, if any
+#[cfg(not(target_arch = "wasm32"))]
+fn load_text(key: &str) -> Option<String> {
+    match std::fs::read_to_string(path(key)) {
+        Ok(contents) => Some(contents),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+        Err(error) => {
+            warn!("Can't read {}: {}", path(key), error);
+            None
+        }
+    }
+}
+
+/// Stores `contents` under `key`
+#[cfg(not(target_arch = "wasm32"))]
+fn save_text(key: &str, contents: &str) {
+    if let Err(error) = std::fs::write(path(key), contents) {
+        warn!("Can't write {}: {}", path(key), error);
+    }
+}
+
+/// The browser's local storage, if the page is allowed to use it
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// Returns the text stored under `key`, if any
+#[cfg(target_arch = "wasm32")]
+fn load_text(key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("{}{}", PREFIX, key))
+        .ok()
+        .flatten()
+}
+
+/// Stores `contents` under `key`
+#[cfg(target_arch = "wasm32")]
+fn save_text(key: &str, contents: &str) {
+    let saved = local_storage().is_some_and(|storage| {
+        storage
+            .set_item(&format!("{}{}", PREFIX, key), contents)
+            .is_ok()
+    });
+    if !saved {
+        warn!("Can't save {} to local storage", key);
+    }
+}
+
+/// Returns the value stored under `key`, or `None` if there is none or it
+/// can't be parsed
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let contents = load_text(key)?;
+    match ron::from_str(&contents) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            warn!("Ignoring unreadable {}: {}", key, error);
+            None
+        }
+    }
+}
+
+/// Stores `value` under `key`
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    match ron::to_string(value) {
+        Ok(contents) => save_text(key, &contents),
+        Err(error) => warn!("Can't encode {}: {}", key, error),
+    }
+}
@@ -0,0 +1,132 @@
+//! Survival Mode Module
+//!
+//! This module implements the survival game mode, where the player defends
+//! alone against a ball that gets faster with every bounce:
+//!
+//! - The AI paddle is never spawned and the right wall simply bounces
+//! - Every bounce speeds the ball up by a configurable factor
+//! - The run ends the first time the ball reaches the left wall
+//! - Returns and the session's best survival time are tracked
+//!
+//! Elapsed time comes from the shared match timer, so pausing stops the
+//! clock just like in a regular match.
+
+use crate::ball::{Ball, SpeedCap};
+use crate::mode::GameMode;
+use crate::player::PaddleHit;
+use crate::score::MatchTimer;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::time::Duration;
+
+/// Configuration for survival mode
+#[derive(Debug, Resource)]
+pub struct SurvivalConfig {
+    /// Speed multiplier applied to the ball on every bounce
+    pub speed_up: f32,
+    /// Maximum ball speed in survival mode, above the normal cap
+    pub max_speed: f32,
+}
+
+impl Default for SurvivalConfig {
+    fn default() -> Self {
+        Self {
+            speed_up: 1.05,
+            max_speed: 35.0,
+        }
+    }
+}
+
+/// Resource tracking the current survival run and the session's record.
+#[derive(Debug, Resource, Default)]
+pub struct SurvivalStats {
+    /// Number of times the player returned the ball this run
+    pub returns: u32,
+    /// Longest survival time this session
+    pub best: Duration,
+}
+
+impl SurvivalStats {
+    /// Clears the current run, keeping the session record
+    pub fn reset_run(&mut self) {
+        self.returns = 0;
+    }
+}
+
+/// Lifts the speed cap on newly served balls so they can keep accelerating.
+fn uncap_new_balls(
+    mut commands: Commands,
+    config: Res<SurvivalConfig>,
+    query: Query<Entity, Added<Ball>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(SpeedCap(config.max_speed));
+    }
+}
+
+/// Speeds the ball up on every bounce.
+fn handle_survival_bounces(
+    config: Res<SurvivalConfig>,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut ball_query: Query<&mut Velocity, With<Ball>>,
+) {
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, flags) = collision_event else {
+            continue;
+        };
+        // Passing through sensors isn't a bounce
+        if flags.contains(CollisionEventFlags::SENSOR) {
+            continue;
+        }
+
+        let mut velocity = match ball_query.get_mut(*e1) {
+            Ok(velocity) => velocity,
+            Err(_) => match ball_query.get_mut(*e2) {
+                Ok(velocity) => velocity,
+                Err(_) => continue,
+            },
+        };
+
+        velocity.linvel *= config.speed_up;
+    }
+}
+
+/// Counts the player's returns.
+fn count_survival_returns(mut stats: ResMut<SurvivalStats>, mut hits: EventReader<PaddleHit>) {
+    stats.returns += hits.read().count() as u32;
+}
+
+/// Records the session best when a survival run ends.
+fn record_survival_best(timer: Res<MatchTimer>, mut stats: ResMut<SurvivalStats>) {
+    stats.best = stats.best.max(timer.elapsed);
+}
+
+/// Plugin that manages survival mode.
+///
+/// Scoring, UI and endgame differences are handled by the score and endgame
+/// modules based on [`GameMode::Survival`]; this plugin adds the accelerating
+/// ball and run statistics.
+pub struct SurvivalPlugin;
+
+impl Plugin for SurvivalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SurvivalConfig>()
+            .init_resource::<SurvivalStats>()
+            .add_systems(
+                Update,
+                uncap_new_balls
+                    .run_if(in_state(GameState::Playing).and(resource_equals(GameMode::Survival))),
+            )
+            // The speed ramp steps with physics, alongside the ball's speed clamp
+            .add_systems(
+                FixedUpdate,
+                (handle_survival_bounces, count_survival_returns)
+                    .run_if(in_state(GameState::Playing).and(resource_equals(GameMode::Survival))),
+            )
+            .add_systems(
+                OnEnter(GameState::GameOver),
+                record_survival_best.run_if(resource_equals(GameMode::Survival)),
+            );
+    }
+}
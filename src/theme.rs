@@ -0,0 +1,221 @@
+//! Theme Module
+//!
+//! This module implements selectable color themes, including:
+//! - The selected theme resource and its named variants
+//! - Per-theme colors for paddles, ball, walls, center line, background and UI text
+//! - Larger UI fonts for the high contrast theme
+//! - Retroactively recoloring existing entities when the theme changes
+//!
+//! Entities opt in to theming with the [`Themed`] component, which names the
+//! role they play. Colors are applied at spawn time by reading the theme, and
+//! the systems here keep themed entities in sync whenever the theme changes.
+
+use crate::palette::{BACKGROUND, FOREGROUND};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Resource selecting the game's color theme
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Classic, // White on black, like the original
+    Neon,         // Glowing colors on a dark purple board
+    Solarized,    // Solarized dark palette
+    HighContrast, // Maximum contrast and larger text for accessibility
+}
+
+/// The colors a theme assigns to each part of the game
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeColors {
+    pub paddle: Color,
+    pub ball: Color,
+    pub wall: Color,
+    pub center_line: Color,
+    pub background: Color,
+    pub text: Color,
+}
+
+impl ThemeColors {
+    /// Returns the color for a themed role
+    pub fn get(&self, role: Themed) -> Color {
+        match role {
+            Themed::Paddle => self.paddle,
+            Themed::Ball => self.ball,
+            Themed::Wall => self.wall,
+            Themed::CenterLine => self.center_line,
+            Themed::Background => self.background,
+            Themed::Text => self.text,
+            Themed::DimText => self.text.with_alpha(DIM_TEXT_ALPHA),
+            Themed::Grid => self.center_line.with_alpha(GRID_ALPHA),
+        }
+    }
+}
+
+impl Theme {
+    /// All themes in selection order
+    const ALL: [Theme; 4] = [
+        Theme::Classic,
+        Theme::Neon,
+        Theme::Solarized,
+        Theme::HighContrast,
+    ];
+
+    /// Text shown for the theme in the menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            Theme::Classic => "Classic",
+            Theme::Neon => "Neon",
+            Theme::Solarized => "Solarized",
+            Theme::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Returns the next theme, wrapping around
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Returns the previous theme, wrapping around
+    pub fn previous(&self) -> Self {
+        let index = Self::ALL.iter().position(|t| t == self).unwrap_or(0);
+        Self::ALL[(index + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Returns the theme's colors
+    pub fn colors(&self) -> ThemeColors {
+        match self {
+            Theme::Classic => ThemeColors {
+                paddle: FOREGROUND,
+                ball: FOREGROUND,
+                wall: FOREGROUND,
+                center_line: FOREGROUND,
+                background: BACKGROUND,
+                text: FOREGROUND,
+            },
+            Theme::Neon => ThemeColors {
+                paddle: Color::srgb(0.0, 1.0, 0.95),      // Cyan
+                ball: Color::srgb(1.0, 0.2, 0.8),         // Magenta
+                wall: Color::srgb(0.6, 0.2, 1.0),         // Violet
+                center_line: Color::srgb(0.35, 0.1, 0.6), // Dim violet
+                background: Color::srgb(0.04, 0.0, 0.08), // Near-black purple
+                text: Color::srgb(0.0, 1.0, 0.95),        // Cyan
+            },
+            Theme::Solarized => ThemeColors {
+                paddle: Color::srgb(0.149, 0.545, 0.824),      // blue
+                ball: Color::srgb(0.71, 0.537, 0.0),           // yellow
+                wall: Color::srgb(0.514, 0.58, 0.588),         // base0
+                center_line: Color::srgb(0.345, 0.431, 0.459), // base01
+                background: Color::srgb(0.0, 0.169, 0.212),    // base03
+                text: Color::srgb(0.576, 0.631, 0.631),        // base1
+            },
+            Theme::HighContrast => ThemeColors {
+                paddle: FOREGROUND,
+                ball: Color::srgb(1.0, 1.0, 0.0), // Yellow stands out from the white paddles
+                wall: FOREGROUND,
+                center_line: FOREGROUND,
+                background: BACKGROUND,
+                text: FOREGROUND,
+            },
+        }
+    }
+
+    /// Multiplier applied to UI font sizes
+    pub fn font_scale(&self) -> f32 {
+        match self {
+            Theme::HighContrast => 1.2,
+            _ => 1.0,
+        }
+    }
+
+    /// Scales a UI font size for the theme
+    pub fn font_size(&self, size: f32) -> f32 {
+        size * self.font_scale()
+    }
+}
+
+/// Opacity of text for options that can't be picked yet
+const DIM_TEXT_ALPHA: f32 = 0.4;
+
+/// Alpha of the background grid, just visible against the board
+const GRID_ALPHA: f32 = 0.06;
+
+/// Component naming the role an entity plays in the theme, so its color can
+/// be updated when the theme changes
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Themed {
+    Paddle,     // Mesh material
+    Ball,       // Mesh material
+    Wall,       // Sprite
+    CenterLine, // Sprite
+    Background, // UI background color
+    Text,       // UI text color and font size
+    DimText,    // UI text for unavailable options, greyed out
+    Grid,       // Sprite, faint lines behind the board
+}
+
+/// Recolors the background, sprites and mesh materials of themed entities.
+fn apply_theme_colors(
+    theme: Res<Theme>,
+    mut clear_color: ResMut<ClearColor>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut sprite_query: Query<(&Themed, &mut Sprite)>,
+    mesh_query: Query<(&Themed, &MeshMaterial2d<ColorMaterial>)>,
+) {
+    let colors = theme.colors();
+    clear_color.0 = colors.background;
+
+    for (role, mut sprite) in sprite_query.iter_mut() {
+        sprite.color = colors.get(*role);
+    }
+    for (role, material) in mesh_query.iter() {
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = colors.get(*role);
+        }
+    }
+}
+
+/// Recolors and resizes themed UI elements.
+///
+/// Font sizes are rescaled relative to the previously applied theme, so
+/// switching in and out of the high contrast theme restores the originals.
+/// Text is already spawned at the current theme's size, so nothing is
+/// rescaled the first time this runs.
+fn apply_theme_ui(
+    theme: Res<Theme>,
+    mut previous: Local<Option<Theme>>,
+    mut text_query: Query<(&Themed, &mut TextColor, &mut TextFont)>,
+    mut background_query: Query<(&Themed, &mut BackgroundColor)>,
+) {
+    let colors = theme.colors();
+    let rescale = previous.map_or(1.0, |previous| theme.font_scale() / previous.font_scale());
+    *previous = Some(*theme);
+
+    for (role, mut color, mut font) in text_query.iter_mut() {
+        color.0 = colors.get(*role);
+        font.font_size *= rescale;
+    }
+    // Backgrounds keep their own alpha, such as the menu's over the
+    // attract demo
+    for (role, mut background) in background_query.iter_mut() {
+        background.0 = colors.get(*role).with_alpha(background.0.alpha());
+    }
+}
+
+/// Plugin that manages the color theme.
+///
+/// The theme's background replaces the clear color, and themed entities are
+/// updated whenever the [`Theme`] resource changes.
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Theme>()
+            // Set background color
+            .insert_resource(ClearColor(Theme::default().colors().background))
+            .add_systems(
+                Update,
+                (apply_theme_colors, apply_theme_ui).run_if(resource_changed::<Theme>),
+            );
+    }
+}
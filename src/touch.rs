@@ -0,0 +1,126 @@
+//! Touch Controls Module
+//!
+//! This module lets the game be played on touch screens, mostly phones and
+//! tablets in the browser, including:
+//! - Steering the human's paddle toward a finger on their half of the board
+//! - Tapping the other half to serve a held ball, or to pause otherwise
+//! - Tapping anywhere on the pause menu to resume
+//!
+//! Only the first finger on the screen counts, so a second finger resting
+//! on the glass doesn't pull the paddle around. Touch works alongside the
+//! keyboard rather than replacing it, and the paddle still stops at the
+//! walls however far the finger goes.
+
+use crate::camera::MainCamera;
+use crate::player::{Player, PlayerSide};
+use crate::serve::BallHeld;
+use crate::GameState;
+use bevy::input::touch::Touch;
+use bevy::prelude::*;
+
+/// Resource holding the height the human's paddle is steered toward, in
+/// world units, while a finger is down on their half of the board
+#[derive(Resource, Debug, Default)]
+pub struct TouchTarget(pub Option<f32>);
+
+/// Event sent when a tap asks to serve the human's held ball
+#[derive(Event, Debug)]
+pub struct TouchServe;
+
+/// Query for the camera showing the board, used to map touches to the world
+type BoardCamera<'w, 's> =
+    Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>;
+
+/// Plugin that handles touch input.
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchTarget>()
+            .add_event::<TouchServe>()
+            .add_systems(
+                Update,
+                steer_with_touch.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), release_touch_target)
+            .add_systems(
+                Update,
+                handle_touch_taps
+                    .run_if(in_state(GameState::Playing).or(in_state(GameState::Paused))),
+            );
+    }
+}
+
+/// The first finger on the screen, if any
+fn first_touch<'a>(touches: impl Iterator<Item = &'a Touch>) -> Option<&'a Touch> {
+    touches.min_by_key(|touch| touch.id())
+}
+
+/// Maps a touch to world coordinates through the board's camera
+fn world_position(touch: &Touch, camera_query: &BoardCamera) -> Option<Vec2> {
+    let (camera, transform) = camera_query.get_single().ok()?;
+    camera
+        .viewport_to_world_2d(transform, touch.position())
+        .ok()
+}
+
+/// Whether a world position is on the human's half of the board
+fn on_own_half(side: &PlayerSide, position: Vec2) -> bool {
+    side.is_left(Player::P1) == (position.x < 0.0)
+}
+
+/// Steers the human's paddle toward the first finger, while it is on their
+/// half of the board.
+fn steer_with_touch(
+    touches: Res<Touches>,
+    side: Res<PlayerSide>,
+    camera_query: BoardCamera,
+    mut target: ResMut<TouchTarget>,
+) {
+    let position =
+        first_touch(touches.iter()).and_then(|touch| world_position(touch, &camera_query));
+    target.0 = position
+        .filter(|position| on_own_half(&side, *position))
+        .map(|position| position.y);
+}
+
+/// Stops steering when play stops, so the paddle doesn't chase a finger
+/// lifted during the pause.
+fn release_touch_target(mut target: ResMut<TouchTarget>) {
+    target.0 = None;
+}
+
+/// Serves or pauses on a tap away from the human's half, and resumes on any
+/// tap while paused.
+fn handle_touch_taps(
+    touches: Res<Touches>,
+    side: Res<PlayerSide>,
+    state: Res<State<GameState>>,
+    camera_query: BoardCamera,
+    held_query: Query<&BallHeld>,
+    mut serves: EventWriter<TouchServe>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Some(touch) = first_touch(touches.iter_just_pressed()) else {
+        return;
+    };
+
+    if *state.get() == GameState::Paused {
+        next_state.set(GameState::Playing);
+        return;
+    }
+
+    let Some(position) = world_position(touch, &camera_query) else {
+        return;
+    };
+    if on_own_half(&side, position) {
+        return;
+    }
+
+    // A ball waiting on the human's serve takes the tap, otherwise it pauses
+    if held_query.iter().any(BallHeld::awaits_local_serve) {
+        serves.send(TouchServe);
+    } else {
+        next_state.set(GameState::Paused);
+    }
+}
@@ -0,0 +1,137 @@
+//! Trajectory Module
+//!
+//! This module predicts where the ball will cross a vertical line on the
+//! board, such as a paddle's. The AI plans its moves with it, and the aim
+//! assist marks the same spot for human players, so both read the ball the
+//! same way.
+//!
+//! Predictions follow the ball in a straight line, folding the path back
+//! at the top and bottom walls. Paddles, obstacles and the net aren't
+//! taken into account.
+
+use crate::board::BoardConfig;
+use bevy::prelude::*;
+
+/// Furthest the center of a ball `ball_size` across can get above or below
+/// the center of `board`
+pub fn ball_height_limit(board: &BoardConfig, ball_size: f32) -> f32 {
+    (board.inner_half_height() - ball_size / 2.0).max(0.0)
+}
+
+/// Folds `y` back into `-limit..=limit`, as if it had bounced off walls at
+/// either end of that range
+pub fn reflect_within(y: f32, limit: f32) -> f32 {
+    if limit <= 0.0 {
+        return 0.0;
+    }
+
+    // One full period goes up to the top wall, down to the bottom and back
+    let period = 4.0 * limit;
+    let shifted = (y + limit).rem_euclid(period);
+    if shifted <= 2.0 * limit {
+        shifted - limit
+    } else {
+        3.0 * limit - shifted
+    }
+}
+
+/// Predicts where and when the ball will intersect with a paddle's x-position
+///
+/// Bounces off the top and bottom walls are accounted for by reflecting the
+/// straight-line path within `y_limit`, the furthest the ball's center can
+/// get from the middle of the board.
+///
+/// Returns the intersection's y-coordinate and the time until it happens,
+/// or `None` if the ball is moving away from the paddle.
+pub fn predict_intersection(
+    ball_pos: Vec2,
+    ball_vel: Vec2,
+    paddle_x: f32,
+    y_limit: f32,
+) -> Option<(f32, f32)> {
+    // Check if ball is moving toward paddle
+    let moving_toward =
+        (paddle_x > ball_pos.x && ball_vel.x > 0.0) || (paddle_x < ball_pos.x && ball_vel.x < 0.0);
+
+    if moving_toward {
+        // Calculate intersection time and position
+        let time = (paddle_x - ball_pos.x) / ball_vel.x;
+        let y = reflect_within(ball_pos.y + (ball_vel.y * time), y_limit);
+        Some((y, time))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walls 4 units above and below the center
+    const LIMIT: f32 = 4.0;
+
+    #[test]
+    fn reflect_within_leaves_heights_inside_the_range() {
+        assert_eq!(reflect_within(0.0, LIMIT), 0.0);
+        assert_eq!(reflect_within(3.0, LIMIT), 3.0);
+        assert_eq!(reflect_within(-LIMIT, LIMIT), -LIMIT);
+    }
+
+    #[test]
+    fn reflect_within_folds_back_at_one_wall() {
+        assert_eq!(reflect_within(6.0, LIMIT), 2.0);
+        assert_eq!(reflect_within(-6.0, LIMIT), -2.0);
+    }
+
+    #[test]
+    fn reflect_within_folds_back_at_several_walls() {
+        // Up to the top, down to the bottom, then 1 back up
+        assert_eq!(reflect_within(13.0, LIMIT), -3.0);
+        // A full period lands where it started
+        assert_eq!(reflect_within(1.0 + 4.0 * LIMIT, LIMIT), 1.0);
+        assert_eq!(reflect_within(-1.0 - 4.0 * LIMIT, LIMIT), -1.0);
+    }
+
+    #[test]
+    fn prediction_without_a_bounce() {
+        let prediction = predict_intersection(Vec2::ZERO, Vec2::new(4.0, 1.0), 8.0, LIMIT);
+        assert_eq!(prediction, Some((2.0, 2.0)));
+    }
+
+    #[test]
+    fn prediction_with_one_bounce() {
+        let prediction = predict_intersection(Vec2::ZERO, Vec2::new(4.0, 3.0), 8.0, LIMIT);
+        assert_eq!(prediction, Some((2.0, 2.0)));
+
+        // Toward a paddle on the left, off the bottom wall
+        let prediction = predict_intersection(Vec2::ZERO, Vec2::new(-4.0, -3.0), -8.0, LIMIT);
+        assert_eq!(prediction, Some((-2.0, 2.0)));
+    }
+
+    #[test]
+    fn prediction_with_several_bounces() {
+        // 4 up to the top, 8 down to the bottom and 4 back up to the middle
+        let prediction = predict_intersection(Vec2::ZERO, Vec2::new(4.0, 8.0), 8.0, LIMIT);
+        assert_eq!(prediction, Some((0.0, 2.0)));
+    }
+
+    #[test]
+    fn no_prediction_for_a_ball_moving_away() {
+        assert_eq!(
+            predict_intersection(Vec2::ZERO, Vec2::new(-4.0, 1.0), 8.0, LIMIT),
+            None
+        );
+        assert_eq!(
+            predict_intersection(Vec2::ZERO, Vec2::new(4.0, 1.0), -8.0, LIMIT),
+            None
+        );
+        // Already past the paddle, heading further away
+        let prediction = predict_intersection(Vec2::new(9.0, 0.0), Vec2::new(4.0, 0.0), 8.0, LIMIT);
+        assert_eq!(prediction, None);
+        // Moving straight up or down never reaches it
+        assert_eq!(
+            predict_intersection(Vec2::ZERO, Vec2::new(0.0, 5.0), 8.0, LIMIT),
+            None
+        );
+    }
+}
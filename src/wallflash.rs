@@ -0,0 +1,208 @@
+//! Wall Flash Module
+//!
+//! This module lights up the walls when the ball reaches them, including:
+//! - Flashing a scoring wall in the scorer's color when a point is conceded
+//! - Drawing the scoring wall thicker for the length of the flash
+//! - A much subtler tick on the top and bottom walls when the ball bounces
+//!
+//! Each flash is a [`WallFlash`] on the wall with its own timer. A new flash
+//! on a wall that is still flashing simply starts over, so rapid points
+//! never leave a wall stuck partway. Once a flash ends, the wall goes back
+//! to exactly its theme color and size. Only the sprite changes; the wall's
+//! collider is left alone.
+
+use crate::ball::Ball;
+use crate::board::{BoardConfig, Wall};
+use crate::palette::{RUST_ORANGE, VICTORY_GREEN};
+use crate::player::{Player, PlayerSide};
+use crate::score::{handle_scoring, PointScored};
+use crate::theme::Theme;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::f32::consts::PI;
+
+/// Color the top and bottom walls tick toward on a bounce
+const BOUNCE_COLOR: Color = RUST_ORANGE;
+
+/// Configuration for the wall flashes
+#[derive(Debug, Resource)]
+pub struct WallFlashConfig {
+    /// Whether the walls flash at all
+    pub enabled: bool,
+    /// Length of a scoring wall's flash in seconds
+    pub goal_duration: f32,
+    /// Thickness of a scoring wall at the height of its flash, as a multiple
+    /// of its normal thickness
+    pub goal_thickening: f32,
+    /// Length of a top or bottom wall's tick in seconds
+    pub bounce_duration: f32,
+    /// How far a tick goes toward its color, from 0.0 to 1.0
+    pub bounce_strength: f32,
+    /// Thickness of a top or bottom wall at the height of its tick, as a
+    /// multiple of its normal thickness
+    pub bounce_thickening: f32,
+}
+
+impl Default for WallFlashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,          // Off for plain, still walls
+            goal_duration: 0.5,     // Over before the next serve
+            goal_thickening: 2.5,   // Plain to see from across the board
+            bounce_duration: 0.15,  // A blink, easy to miss in a long rally
+            bounce_strength: 0.3,   // A hint of color rather than a flash
+            bounce_thickening: 1.3, // Barely there
+        }
+    }
+}
+
+/// Color a scoring wall flashes in when `scorer` wins the point, the same
+/// green and orange as the end screen's win and loss
+fn scorer_color(scorer: Player) -> Color {
+    match scorer {
+        Player::P1 => VICTORY_GREEN,
+        Player::P2 => RUST_ORANGE,
+    }
+}
+
+/// Component for a wall that is flashing
+#[derive(Component, Debug)]
+struct WallFlash {
+    /// Color the wall flashes toward
+    color: Color,
+    /// How far the wall goes toward `color`, from 0.0 to 1.0
+    strength: f32,
+    /// Thickness at the height of the flash, as a multiple of the normal
+    thickening: f32,
+    /// Time through the flash
+    timer: Timer,
+}
+
+impl WallFlash {
+    /// A scoring wall's flash for a point won by `scorer`
+    fn goal(config: &WallFlashConfig, scorer: Player) -> Self {
+        Self {
+            color: scorer_color(scorer),
+            strength: 1.0,
+            thickening: config.goal_thickening,
+            timer: Timer::from_seconds(config.goal_duration, TimerMode::Once),
+        }
+    }
+
+    /// A top or bottom wall's tick on a bounce
+    fn bounce(config: &WallFlashConfig) -> Self {
+        Self {
+            color: BOUNCE_COLOR,
+            strength: config.bounce_strength,
+            thickening: config.bounce_thickening,
+            timer: Timer::from_seconds(config.bounce_duration, TimerMode::Once),
+        }
+    }
+
+    /// How far into the flash the wall is, rising from 0.0 to 1.0 halfway
+    /// through and falling back to 0.0 at the end
+    fn intensity(&self) -> f32 {
+        (self.timer.fraction() * PI).sin()
+    }
+}
+
+/// Size of a wall's sprite with its thickness multiplied by `thickening`
+fn wall_size(board: &BoardConfig, wall: &Wall, thickening: f32) -> Vec2 {
+    let thickness = board.wall_thickness * thickening;
+    match wall {
+        Wall::Top | Wall::Bottom => Vec2::new(board.width, thickness),
+        Wall::Left | Wall::Right => Vec2::new(thickness, board.height),
+    }
+}
+
+/// Plugin that flashes the walls when the ball reaches them.
+pub struct WallFlashPlugin;
+
+impl Plugin for WallFlashPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WallFlashConfig>().add_systems(
+            Update,
+            (
+                // In the frame a point is scored, so the match's last point
+                // is read before the game leaves the Playing state
+                start_wall_flashes
+                    .after(handle_scoring)
+                    .run_if(in_state(GameState::Playing)),
+                // Runs in every state, so a flash started by a match's last
+                // point still finishes on the end screen
+                animate_wall_flashes,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Starts a flash on the wall behind the player who conceded each point,
+/// and a tick on the top or bottom wall for each bounce off it.
+fn start_wall_flashes(
+    mut commands: Commands,
+    config: Res<WallFlashConfig>,
+    side: Res<PlayerSide>,
+    mut points: EventReader<PointScored>,
+    mut collision_events: EventReader<CollisionEvent>,
+    ball_query: Query<(), With<Ball>>,
+    wall_query: Query<(Entity, &Wall)>,
+) {
+    if !config.enabled {
+        points.clear();
+        collision_events.clear();
+        return;
+    }
+
+    for point in points.read() {
+        let conceded = wall_query
+            .iter()
+            .find(|(_, wall)| side.defender(wall) == Some(point.scorer.opponent()));
+        if let Some((entity, _)) = conceded {
+            commands
+                .entity(entity)
+                .insert(WallFlash::goal(&config, point.scorer));
+        }
+    }
+
+    for collision_event in collision_events.read() {
+        let CollisionEvent::Started(e1, e2, _) = collision_event else {
+            continue;
+        };
+        if !ball_query.contains(*e1) && !ball_query.contains(*e2) {
+            continue;
+        }
+        let bounced = wall_query.iter().find(|(entity, wall)| {
+            (entity == e1 || entity == e2) && matches!(wall, Wall::Top | Wall::Bottom)
+        });
+        if let Some((entity, _)) = bounced {
+            commands.entity(entity).insert(WallFlash::bounce(&config));
+        }
+    }
+}
+
+/// Moves each flashing wall's color and thickness along its flash, and puts
+/// the wall back to its theme color and normal size once the flash ends.
+fn animate_wall_flashes(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<BoardConfig>,
+    theme: Res<Theme>,
+    mut query: Query<(Entity, &Wall, &mut WallFlash, &mut Sprite)>,
+) {
+    let color = theme.colors().wall;
+    for (entity, wall, mut flash, mut sprite) in query.iter_mut() {
+        if flash.timer.tick(time.delta()).finished() {
+            sprite.color = color;
+            sprite.custom_size = Some(wall_size(&board, wall, 1.0));
+            commands.entity(entity).remove::<WallFlash>();
+            continue;
+        }
+
+        let intensity = flash.intensity();
+        sprite.color = color.mix(&flash.color, flash.strength * intensity);
+        let thickening = 1.0 + (flash.thickening - 1.0) * intensity;
+        sprite.custom_size = Some(wall_size(&board, wall, thickening));
+    }
+}
@@ -1,12 +1,47 @@
-use bevy::prelude::WindowPlugin;
-use bevy::window::Window;
+use crate::mode::GameMode;
+use crate::player::{Player, PlayerSide};
+use crate::score::Score;
+use crate::GameState;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResolution};
 
-/// Creates and returns a window plugin configured for browser-based deployment.
+/// Title shown on the game's window
+const GAME_TITLE: &str = "Rusty Pong";
+
+/// Settings for the game's window
+#[derive(Debug, Clone)]
+pub(crate) struct WindowConfig {
+    /// Starting window title
+    pub title: String,
+    /// Starting window width in logical pixels. Ignored on the web
+    pub width: f32,
+    /// Starting window height in logical pixels. Ignored on the web
+    pub height: f32,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: GAME_TITLE.into(),
+            // A common 16:9 size; the camera keeps the whole board in view
+            width: 1280.0,
+            height: 720.0,
+        }
+    }
+}
+
+/// Creates the window plugin with the default [`WindowConfig`].
+pub(crate) fn default_window_plugin() -> WindowPlugin {
+    window_plugin(WindowConfig::default())
+}
+
+/// Creates and returns a window plugin that works both natively and in the browser.
 ///
-/// This function provides a `WindowPlugin` with settings optimized for web deployment,
-/// ensuring the game canvas behaves appropriately within the browser environment.
+/// Native builds open a window with the configured title and size, while on
+/// the web the game canvas fits its page and the size is left to the browser.
 ///
 /// # Window Configuration
+/// - Native windows start at the configured resolution
 /// - The game canvas will automatically resize to fill its parent element
 /// - Browser keyboard shortcuts remain functional (F5 for refresh, etc.)
 /// - Other window settings use their default values
@@ -20,9 +55,19 @@ use bevy::window::Window;
 ///
 /// # Returns
 /// A `WindowPlugin` instance with browser-specific configurations.
-pub(crate) fn default_window_plugin() -> WindowPlugin {
+pub(crate) fn window_plugin(config: WindowConfig) -> WindowPlugin {
+    // The browser decides the canvas size, see `fit_canvas_to_parent`
+    let resolution = if cfg!(target_arch = "wasm32") {
+        WindowResolution::default()
+    } else {
+        WindowResolution::new(config.width, config.height)
+    };
+
     WindowPlugin {
         primary_window: Some(Window {
+            // Starting title, kept up to date by `WindowTitlePlugin` on native builds
+            title: config.title,
+            resolution,
             // Set the canvas ID to match the parent element
             canvas: Some("#pong-game-canvas".into()),
             // Enable canvas-to-parent fitting
@@ -41,8 +86,6 @@ pub(crate) fn default_window_plugin() -> WindowPlugin {
 
             // Use defaults for all other window settings
             // This includes:
-            // - Title
-            // - Resolution
             // - Position
             // - Decorations
             // - etc.
@@ -52,3 +95,61 @@ pub(crate) fn default_window_plugin() -> WindowPlugin {
         ..Default::default()
     }
 }
+
+/// Builds the window title for the current game state and score.
+///
+/// Scores are listed in the same left-to-right order as the paddles.
+fn window_title(state: GameState, mode: GameMode, side: PlayerSide, score: &Score) -> String {
+    match state {
+        GameState::Playing if mode == GameMode::Survival => format!("{} — Survival", GAME_TITLE),
+        GameState::Playing => {
+            let (left, right) = if side.is_left(Player::P1) {
+                (score.p1, score.p2)
+            } else {
+                (score.p2, score.p1)
+            };
+            format!("{} — {} : {}", GAME_TITLE, left, right)
+        }
+        GameState::Paused => format!("{} — Paused", GAME_TITLE),
+        GameState::Replay => format!("{} — Replay", GAME_TITLE),
+        GameState::Online => format!("{} — Online", GAME_TITLE),
+        _ => GAME_TITLE.to_string(),
+    }
+}
+
+/// Updates the primary window's title to show the score and game state.
+///
+/// Setting a title can be expensive on some platforms, so the window is
+/// only touched when the title text actually changes.
+fn update_window_title(
+    state: Res<State<GameState>>,
+    mode: Res<GameMode>,
+    side: Res<PlayerSide>,
+    score: Res<Score>,
+    mut last_title: Local<String>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let title = window_title(*state.get(), *mode, *side, &score);
+    if title == *last_title {
+        return;
+    }
+
+    if let Ok(mut window) = window_query.get_single_mut() {
+        window.title = title.clone();
+        *last_title = title;
+    }
+}
+
+/// Plugin that keeps the window title in sync with the game.
+///
+/// Browser canvases have no title of their own, so on wasm builds the
+/// title is left alone.
+pub(crate) struct WindowTitlePlugin;
+
+impl Plugin for WindowTitlePlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(target_arch = "wasm32") {
+            app.add_systems(Update, update_window_title);
+        }
+    }
+}
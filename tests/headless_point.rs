@@ -0,0 +1,675 @@
+//! Plays points without a window or renderer.
+//!
+//! The app is built from the game's own board, ball and scoring plugins,
+//! with real physics stepped at a fixed rate, so a ball sent at a scoring
+//! wall goes through collision detection, `handle_scoring` and the point
+//! award just like in a match.
+
+use bevy::asset::AssetPlugin;
+use bevy::ecs::event::EventCursor;
+use bevy::ecs::system::RunSystemOnce;
+use bevy::hierarchy::HierarchyPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::time::TimeUpdateStrategy;
+use bevy::transform::TransformPlugin;
+use bevy_rapier2d::prelude::*;
+use rusty_pong::attract::AttractDemo;
+use rusty_pong::ball::{create_ball, Ball, BallAssets, BallConfig, BallPlugin};
+use rusty_pong::board::{BoardConfig, BoardPlugin, Wall, PIXELS_PER_METER};
+use rusty_pong::ghost::GhostRecording;
+use rusty_pong::mode::GameMode;
+use rusty_pong::multiball::{MultiBallConfig, MultiBallPlugin};
+use rusty_pong::options::SettingsScreen;
+use rusty_pong::pause::{PausePlugin, ResumeGrace};
+use rusty_pong::player::{
+    AiConfig, AiDebugInfo, MovementState, PaddleConfig, PaddleHit, Player, PlayerPlugin,
+    PlayerRoles, PlayerSide,
+};
+use rusty_pong::replay::InstantReplay;
+use rusty_pong::score::{Score, ScorePlugin, ScoreRules};
+use rusty_pong::serve::{AiServeConfig, AiServePlan, ServeConfig};
+use rusty_pong::survival::SurvivalStats;
+use rusty_pong::theme::ThemePlugin;
+use rusty_pong::touch::TouchTarget;
+use rusty_pong::trajectory::{ball_height_limit, predict_intersection};
+use rusty_pong::GameState;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Length of one frame, matching the physics step
+const FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// Frames to run after serving: long enough for the ball to cross half the
+/// board at its slowest, but shorter than that plus the serve delay
+const FRAMES: usize = 90;
+
+/// Frames a ball sent into a corner gets to come back out: over twice the
+/// dozen or so it takes to reach the corner and leave it at top speed
+const CORNER_FRAMES: usize = 30;
+
+/// Frames a ball bouncing straight up and down gets: over three trips
+/// across the board's height at the test's speed
+const BOUNCE_FRAMES: usize = 180;
+
+/// Points played when checking that balls reuse their shared assets
+const ASSET_POINTS: u32 = 50;
+
+/// Frames balls are fired at a paddle for when looking for tunneling, close
+/// to a minute of play
+const STRESS_FRAMES: usize = 3000;
+
+/// Builds a headless app that can play points, sitting in the Playing state
+fn headless_app() -> App {
+    let mut app = headless_app_before_play();
+    start_playing(&mut app);
+    app
+}
+
+/// Builds the headless app without starting play, so more plugins can be
+/// added first
+fn headless_app_before_play() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        AssetPlugin::default(),
+        TransformPlugin,
+        HierarchyPlugin,
+        RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PIXELS_PER_METER).in_fixed_schedule(),
+        ThemePlugin,
+        BoardPlugin,
+        BallPlugin,
+        ScorePlugin,
+        MultiBallPlugin,
+        PausePlugin,
+    ))
+    // Normally registered by the rendering plugins
+    .init_asset::<Mesh>()
+    .init_asset::<ColorMaterial>()
+    // Normally set up by plugins that need input, UI or rendering
+    .init_state::<GameState>()
+    .init_resource::<ButtonInput<KeyCode>>()
+    .init_resource::<GameMode>()
+    .init_resource::<PaddleConfig>()
+    .init_resource::<PlayerRoles>()
+    .init_resource::<PlayerSide>()
+    .init_resource::<ServeConfig>()
+    .init_resource::<AiServeConfig>()
+    .init_resource::<AiServePlan>()
+    .init_resource::<SurvivalStats>()
+    .init_resource::<InstantReplay>()
+    .init_resource::<AttractDemo>()
+    .init_resource::<SettingsScreen>()
+    // Advance the clock by exactly one frame per update
+    .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+    // Hold the match's first serve, so the test's ball is the only one
+    .insert_resource(ScoreRules {
+        ready_duration: 3600.0,
+        ..default()
+    });
+    app
+}
+
+/// Moves the app from the splash screen into the Playing state, as a new
+/// match
+fn start_playing(app: &mut App) {
+    // The startup systems run first, on the splash screen, as in the game
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+    app.update();
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Playing
+    );
+}
+
+/// Serves a ball from the right-hand side, heading straight for the left wall
+fn serve_toward_left_wall(app: &mut App) {
+    app.world_mut()
+        .run_system_once(
+            |mut commands: Commands, assets: Res<BallAssets>, config: Res<BallConfig>| {
+                create_ball(&mut commands, &assets, &config, false, Vec2::ZERO);
+            },
+        )
+        .expect("ball should spawn");
+}
+
+/// Number of balls in play
+fn ball_count(app: &mut App) -> usize {
+    app.world_mut()
+        .query_filtered::<Entity, With<Ball>>()
+        .iter(app.world())
+        .count()
+}
+
+#[test]
+fn ball_reaching_left_wall_scores_for_p2() {
+    let mut app = headless_app();
+    assert_eq!(app.world().resource::<Score>().p2, 0);
+
+    serve_toward_left_wall(&mut app);
+
+    for _ in 0..FRAMES {
+        app.update();
+    }
+
+    // P1 defends the left wall by default, so the point goes to P2
+    let score = app.world().resource::<Score>();
+    assert_eq!(score.p1, 0);
+    assert_eq!(score.p2, 1);
+    assert!(score.should_serve);
+}
+
+#[test]
+fn playing_many_points_adds_no_meshes_or_materials() {
+    let mut app = headless_app();
+    // Only the test serves, and the match never ends
+    {
+        let mut rules = app.world_mut().resource_mut::<ScoreRules>();
+        rules.target = 1000;
+        rules.serve_delay = 3600.0;
+    }
+    let meshes = app.world().resource::<Assets<Mesh>>().len();
+    let materials = app.world().resource::<Assets<ColorMaterial>>().len();
+
+    for point in 1..=ASSET_POINTS {
+        serve_toward_left_wall(&mut app);
+        let mut frames = 0;
+        while app.world().resource::<Score>().p2 < point {
+            assert!(frames < FRAMES, "point {point} was never scored");
+            app.update();
+            frames += 1;
+        }
+    }
+    // Until the last scored ball is gone
+    app.update();
+
+    assert_eq!(ball_count(&mut app), 0);
+    assert_eq!(app.world().resource::<Assets<Mesh>>().len(), meshes);
+    assert_eq!(
+        app.world().resource::<Assets<ColorMaterial>>().len(),
+        materials
+    );
+}
+
+#[test]
+fn repeated_wall_contacts_in_one_frame_score_once() {
+    let mut app = headless_app();
+    // Heading away from the wall, so only the injected contacts count
+    let ball = spawn_moving_ball(&mut app, Vec2::ZERO, Vec2::X * 8.0);
+    app.update();
+    let wall = app
+        .world_mut()
+        .query::<(Entity, &Wall)>()
+        .iter(app.world())
+        .find(|(_, wall)| matches!(wall, Wall::Left))
+        .map(|(entity, _)| entity)
+        .expect("the board should have a left wall");
+
+    // As when a ball clips the wall's end and reports two contacts
+    for _ in 0..2 {
+        app.world_mut()
+            .send_event(CollisionEvent::Started(
+                ball,
+                wall,
+                CollisionEventFlags::empty(),
+            ))
+            .expect("collision events should be registered");
+    }
+    app.update();
+
+    let score = app.world().resource::<Score>();
+    assert_eq!(score.p1, 0);
+    assert_eq!(score.p2, 1);
+}
+
+#[test]
+fn winning_point_ends_match_cleanly() {
+    let mut app = headless_app();
+    // One point from victory, so the scoring frame also decides the match
+    // and both the point and the win ask for the ball to go
+    app.world_mut().resource_mut::<Score>().p2 = 10;
+    // An immediate serve would follow the point in the same frame if the
+    // win didn't cancel it
+    app.world_mut().resource_mut::<ScoreRules>().serve_delay = 0.0;
+
+    serve_toward_left_wall(&mut app);
+    let mut frames = 0;
+    while app.world().resource::<Score>().p2 < 11 {
+        assert!(frames < FRAMES, "the winning point was never scored");
+        app.update();
+        frames += 1;
+    }
+
+    // The in-game score shows the winning point before the match ends
+    let texts: Vec<String> = app
+        .world_mut()
+        .query::<&Text>()
+        .iter(app.world())
+        .map(|text| text.0.clone())
+        .collect();
+    assert!(texts.iter().any(|text| text == "11"), "{texts:?}");
+
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let score = app.world().resource::<Score>();
+    assert!(!score.should_serve);
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::GameOver
+    );
+    assert_eq!(ball_count(&mut app), 0);
+}
+
+#[test]
+fn ball_sent_into_each_corner_comes_back_out() {
+    let mut app = headless_app();
+    let board = *app.world().resource::<BoardConfig>();
+    let speed = app.world().resource::<BallConfig>().max_velocity;
+    let inner = Vec2::new(board.inner_half_width(), board.inner_half_height());
+
+    for sign in [
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(-1.0, -1.0),
+    ] {
+        // Straight into the corner, across both walls at once
+        let corner = inner * sign;
+        let direction = sign.normalize();
+        let start = corner - direction * 3.0;
+        let ball = app
+            .world_mut()
+            .run_system_once(
+                move |mut commands: Commands, assets: Res<BallAssets>, config: Res<BallConfig>| {
+                    let ball =
+                        create_ball(&mut commands, &assets, &config, sign.x > 0.0, Vec2::ZERO);
+                    commands.entity(ball).insert((
+                        Transform::from_translation(start.extend(0.0)),
+                        Velocity::linear(direction * speed),
+                    ));
+                    ball
+                },
+            )
+            .expect("ball should spawn");
+
+        let mut entered = false;
+        let mut exited = false;
+        for _ in 0..CORNER_FRAMES {
+            app.update();
+            let position = app
+                .world()
+                .get::<Transform>(ball)
+                .expect("the corner should turn the ball back, not score it")
+                .translation
+                .truncate();
+            assert!(
+                position.x.abs() <= inner.x && position.y.abs() <= inner.y,
+                "ball escaped the board at {position}"
+            );
+
+            if position.distance(corner) < 1.0 {
+                entered = true;
+            } else if entered {
+                exited = true;
+                break;
+            }
+        }
+        assert!(exited, "ball stuck in the corner at {corner}");
+
+        app.world_mut().despawn(ball);
+        app.update();
+    }
+}
+
+#[test]
+fn ball_near_either_end_of_a_goal_line_still_scores() {
+    for sign in [1.0, -1.0] {
+        let mut app = headless_app();
+        let board = *app.world().resource::<BoardConfig>();
+        // Close to the corner, but not in it
+        let y = sign * (board.inner_half_height() - 0.3);
+        spawn_moving_ball(&mut app, Vec2::new(0.0, y), Vec2::NEG_X * 12.0);
+
+        for _ in 0..FRAMES {
+            app.update();
+        }
+        assert_eq!(
+            app.world().resource::<Score>().p2,
+            1,
+            "the ball at y = {y} was turned back"
+        );
+    }
+}
+
+#[test]
+fn ball_bouncing_between_top_and_bottom_walls_keeps_its_speed() {
+    let mut app = headless_app();
+    // Between the speed limits, so the clamp can't hide a change
+    let speed = 12.0;
+    let ball = app
+        .world_mut()
+        .run_system_once(
+            move |mut commands: Commands, assets: Res<BallAssets>, config: Res<BallConfig>| {
+                let ball = create_ball(&mut commands, &assets, &config, true, Vec2::ZERO);
+                commands
+                    .entity(ball)
+                    .insert(Velocity::linear(Vec2::Y * speed));
+                ball
+            },
+        )
+        .expect("ball should spawn");
+
+    let mut bounces = 0;
+    let mut rising = true;
+    for _ in 0..BOUNCE_FRAMES {
+        app.update();
+        let velocity = app
+            .world()
+            .get::<Velocity>(ball)
+            .expect("ball should stay in play")
+            .linvel;
+        assert!(
+            (velocity.length() - speed).abs() < 0.01,
+            "speed changed to {}",
+            velocity.length()
+        );
+        if (velocity.y > 0.0) != rising {
+            rising = velocity.y > 0.0;
+            bounces += 1;
+        }
+    }
+    assert!(bounces >= 3, "only {bounces} bounces");
+}
+
+/// Puts a ball at `position` moving at `velocity`
+fn spawn_moving_ball(app: &mut App, position: Vec2, velocity: Vec2) -> Entity {
+    app.world_mut()
+        .run_system_once(
+            move |mut commands: Commands, assets: Res<BallAssets>, config: Res<BallConfig>| {
+                let ball = create_ball(&mut commands, &assets, &config, true, Vec2::ZERO);
+                commands.entity(ball).insert((
+                    Transform::from_translation(position.extend(0.0)),
+                    Velocity::linear(velocity),
+                ));
+                ball
+            },
+        )
+        .expect("ball should spawn")
+}
+
+/// Moves the game to `state` and lets the transition run
+fn set_state(app: &mut App, state: GameState) {
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(state);
+    app.update();
+}
+
+#[test]
+fn ball_stays_still_while_paused() {
+    let mut app = headless_app();
+    let ball = spawn_moving_ball(&mut app, Vec2::new(-5.0, 0.0), Vec2::X * 8.0);
+    app.update();
+
+    set_state(&mut app, GameState::Paused);
+    let start = app
+        .world()
+        .get::<Transform>(ball)
+        .expect("pausing should keep the ball")
+        .translation;
+
+    for _ in 0..60 {
+        app.update();
+    }
+    assert_eq!(
+        app.world().get::<Transform>(ball).unwrap().translation,
+        start
+    );
+}
+
+#[test]
+fn ball_paused_mid_rally_carries_on_at_its_velocity() {
+    let mut app = headless_app();
+    // Between the speed limits, and far enough from the walls to cross
+    // the board's middle untouched
+    let ball = spawn_moving_ball(&mut app, Vec2::new(-5.0, 0.0), Vec2::new(8.0, 1.0));
+    for _ in 0..5 {
+        app.update();
+    }
+    let before = app.world().get::<Velocity>(ball).unwrap().linvel;
+    assert!(before.length() > 0.0);
+
+    set_state(&mut app, GameState::Paused);
+    for _ in 0..30 {
+        app.update();
+    }
+    set_state(&mut app, GameState::Playing);
+    let held = app.world().get::<Transform>(ball).unwrap().translation;
+
+    // Held still through the grace period, moving off in the step it ends
+    loop {
+        app.update();
+        if app.world().resource::<ResumeGrace>().0.is_none() {
+            break;
+        }
+        assert_eq!(
+            app.world().get::<Transform>(ball).unwrap().translation,
+            held
+        );
+    }
+
+    // Then the same ball moves off just as it was going
+    for _ in 0..5 {
+        app.update();
+    }
+    let after = app
+        .world()
+        .get::<Velocity>(ball)
+        .expect("the paused ball should still be in play")
+        .linvel;
+    assert!(
+        after.distance(before) < 1e-4,
+        "resumed at {after} instead of {before}"
+    );
+    assert_ne!(
+        app.world().get::<Transform>(ball).unwrap().translation,
+        held
+    );
+}
+
+#[test]
+fn multi_ball_never_exceeds_max_balls() {
+    let mut app = headless_app();
+    *app.world_mut().resource_mut::<GameMode>() = GameMode::MultiBall;
+    // A new ball every frame, so only the cap holds them back
+    app.insert_resource(MultiBallConfig {
+        max_balls: 3,
+        spawn_interval: FRAME.as_secs_f32(),
+    });
+
+    serve_toward_left_wall(&mut app);
+    let mut most = 0;
+    for _ in 0..FRAMES {
+        app.update();
+        let balls = ball_count(&mut app);
+        assert!(balls <= 3, "{balls} balls in play");
+        most = most.max(balls);
+    }
+    assert_eq!(most, 3);
+}
+
+/// Each paddle's player, position and half height
+fn paddles(app: &mut App) -> Vec<(Player, Vec2, f32)> {
+    let height = app.world().resource::<PaddleConfig>().height;
+    let mut paddles: Vec<_> = app
+        .world_mut()
+        .query::<(&Player, &Transform)>()
+        .iter(app.world())
+        .map(|(player, transform)| {
+            (
+                *player,
+                transform.translation.truncate(),
+                height * transform.scale.y / 2.0,
+            )
+        })
+        .collect();
+    paddles.sort_by_key(|(player, ..)| *player == Player::P2);
+    paddles
+}
+
+/// Each ball in play and its position
+fn ball_positions(app: &mut App) -> HashMap<Entity, Vec2> {
+    app.world_mut()
+        .query_filtered::<(Entity, &Transform), With<Ball>>()
+        .iter(app.world())
+        .map(|(entity, transform)| (entity, transform.translation.truncate()))
+        .collect()
+}
+
+#[test]
+fn balls_at_top_speed_never_tunnel_through_an_oscillating_paddle() {
+    let mut app = headless_app_before_play();
+    app.add_plugins(PlayerPlugin)
+        .init_resource::<TouchTarget>()
+        .init_resource::<GhostRecording>();
+    // Points keep coming, so the match mustn't end
+    app.world_mut().resource_mut::<ScoreRules>().target = 10_000;
+    start_playing(&mut app);
+
+    let speed = app.world().resource::<BallConfig>().max_velocity;
+    let mut hits = EventCursor::<PaddleHit>::default();
+    let mut hit_count = 0;
+    let mut last_paddles = paddles(&mut app);
+    let mut last_balls = ball_positions(&mut app);
+
+    for frame in 0..STRESS_FRAMES {
+        // The human's paddle turns every few frames and keeps swinging
+        {
+            let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            input.reset_all();
+            input.press(if (frame / 8) % 2 == 0 {
+                KeyCode::KeyW
+            } else {
+                KeyCode::KeyS
+            });
+            if frame % 12 == 0 {
+                input.press(KeyCode::KeyE);
+            }
+        }
+
+        // A new ball straight at the human's paddle whenever none is in
+        // play, spread over its height
+        if last_balls.is_empty() {
+            let (_, paddle, half_height) = last_paddles[0];
+            let offset = ((frame * 37 % 100) as f32 / 50.0 - 1.0) * half_height;
+            let start = Vec2::new(0.0, paddle.y + offset);
+            spawn_moving_ball(&mut app, start, Vec2::NEG_X * speed);
+        }
+
+        app.update();
+        hit_count += hits
+            .read(app.world().resource::<Events<PaddleHit>>())
+            .count();
+
+        // No ball may end a step behind a paddle it crossed within the
+        // paddle's height
+        let paddles = paddles(&mut app);
+        let balls = ball_positions(&mut app);
+        for (entity, end) in &balls {
+            let Some(start) = last_balls.get(entity) else {
+                continue;
+            };
+            for ((player, paddle_start, half_height), (_, paddle_end, _)) in
+                last_paddles.iter().zip(&paddles)
+            {
+                // P1 defends the left wall
+                let facing = if *player == Player::P1 { 1.0 } else { -1.0 };
+                let before = *start - *paddle_start;
+                let after = *end - *paddle_end;
+                if before.x * facing >= 0.0 && after.x * facing < 0.0 {
+                    let t = before.x / (before.x - after.x);
+                    let crossing_y = before.y + (after.y - before.y) * t;
+                    assert!(
+                        crossing_y.abs() > *half_height,
+                        "ball tunneled through {player:?}'s paddle on frame {frame}"
+                    );
+                }
+            }
+        }
+        last_paddles = paddles;
+        last_balls = balls;
+    }
+    assert!(hit_count > 20, "only {hit_count} paddle hits");
+}
+
+/// Frames the AI gets to notice a ball heading its way: over two of its
+/// decisions at the default rate
+const AI_FRAMES: usize = 40;
+
+#[test]
+fn ai_predicts_a_ball_sent_its_way_and_moves_to_meet_it() {
+    let mut app = headless_app_before_play();
+    app.add_plugins(PlayerPlugin)
+        .init_resource::<TouchTarget>()
+        .init_resource::<GhostRecording>();
+    // An AI that reads the ball perfectly and never misses on purpose
+    app.insert_resource(AiConfig {
+        error_chance: 0.0,
+        miss_chance: 0.0,
+        ..default()
+    });
+    start_playing(&mut app);
+
+    // P2 is the AI, resting at the middle of its goal line
+    let paddle_x = {
+        let world = app.world();
+        world.resource::<PlayerSide>().paddle_x(
+            Player::P2,
+            world.resource::<PaddleConfig>(),
+            world.resource::<BoardConfig>(),
+        )
+    };
+    let y_limit = ball_height_limit(
+        app.world().resource::<BoardConfig>(),
+        app.world().resource::<BallConfig>().size,
+    );
+
+    // A ball from the center that crosses the AI's line well above it
+    let velocity = Vec2::new(paddle_x.signum() * 8.0, 3.0 * 8.0 / paddle_x.abs());
+    spawn_moving_ball(&mut app, Vec2::ZERO, velocity);
+    let (expected, _) = predict_intersection(Vec2::ZERO, velocity, paddle_x, y_limit)
+        .expect("ball should be heading for the AI");
+
+    let mut info = None;
+    for _ in 0..AI_FRAMES {
+        app.update();
+        info = app
+            .world_mut()
+            .query::<(&Player, &AiDebugInfo)>()
+            .iter(app.world())
+            .find(|(player, _)| **player == Player::P2)
+            .map(|(_, info)| *info)
+            .filter(|info| info.last_prediction.is_some());
+        if info.is_some() {
+            break;
+        }
+    }
+    let info = info.expect("AI should have predicted the ball");
+
+    let prediction = info.last_prediction.unwrap();
+    assert!(
+        (prediction - expected).abs() < 0.05,
+        "predicted {prediction}, expected {expected}"
+    );
+    assert!(
+        matches!(info.movement_state, MovementState::MovingUp(_)),
+        "AI should move up to meet the ball, but is {:?}",
+        info.movement_state
+    );
+}
@@ -0,0 +1,144 @@
+//! Checks the serve rotation rules on a bare [`Score`], without an app.
+
+use rusty_pong::player::Player;
+use rusty_pong::score::{Score, ScoreRules, Tiebreak};
+
+/// Rules with P1 serving first, so the rotation is predictable
+fn rules(tiebreak: Tiebreak) -> ScoreRules {
+    rules_to(11, tiebreak)
+}
+
+/// Like [`rules`], but played to `target` points
+fn rules_to(target: u32, tiebreak: Tiebreak) -> ScoreRules {
+    ScoreRules {
+        target,
+        tiebreak,
+        first_server: Some(true),
+        ..Default::default()
+    }
+}
+
+/// A score for a match that has just started under `rules`
+fn new_match(rules: &ScoreRules) -> Score {
+    let mut score = Score::default();
+    score.pick_first_server(rules);
+    score
+}
+
+/// Trades points from 0-0 until both players have `points`
+fn trade_to(score: &mut Score, points: u32, rules: &ScoreRules) {
+    for _ in 0..points {
+        score.add_point(true, rules);
+        score.add_point(false, rules);
+    }
+}
+
+#[test]
+fn server_changes_every_two_points() {
+    let rules = rules(Tiebreak::WinByTwo);
+    let mut score = new_match(&rules);
+    assert_eq!(score.server(), Player::P1);
+
+    score.add_point(true, &rules);
+    assert_eq!(score.server(), Player::P1);
+    assert_eq!(score.serve_count(), 1);
+
+    score.add_point(false, &rules);
+    assert_eq!(score.server(), Player::P2);
+    assert_eq!(score.serve_count(), 0);
+
+    score.add_point(false, &rules);
+    score.add_point(true, &rules);
+    assert_eq!(score.server(), Player::P1);
+}
+
+#[test]
+fn server_changes_every_point_at_deuce() {
+    let rules = rules(Tiebreak::WinByTwo);
+    let mut score = new_match(&rules);
+
+    // Trade points up to 10-10, where deuce starts
+    trade_to(&mut score, 10, &rules);
+    let server = score.server();
+
+    score.add_point(true, &rules);
+    assert_eq!(score.server(), server.opponent());
+    score.add_point(false, &rules);
+    assert_eq!(score.server(), server);
+}
+
+#[test]
+fn server_changes_every_point_from_twenty_all() {
+    let rules = rules_to(21, Tiebreak::WinByTwo);
+    let mut score = new_match(&rules);
+
+    // At 19-19 the server still serves twice
+    trade_to(&mut score, 19, &rules);
+    let server = score.server();
+    score.add_point(true, &rules);
+    assert_eq!(score.server(), server);
+    score.add_point(false, &rules);
+    assert_eq!(score.server(), server.opponent());
+
+    // From 20-20 it changes after every point
+    let server = score.server();
+    score.add_point(true, &rules);
+    assert_eq!(score.server(), server.opponent());
+    score.add_point(false, &rules);
+    assert_eq!(score.server(), server);
+    score.add_point(false, &rules);
+    assert_eq!(score.server(), server.opponent());
+}
+
+#[test]
+fn twenty_one_point_game_needs_a_two_point_lead() {
+    let rules = rules_to(21, Tiebreak::WinByTwo);
+    let mut score = new_match(&rules);
+
+    trade_to(&mut score, 20, &rules);
+    assert!(!score.check_victory(&rules));
+
+    score.add_point(true, &rules);
+    assert_eq!((score.p1, score.p2), (21, 20));
+    assert!(!score.check_victory(&rules));
+
+    score.add_point(false, &rules);
+    score.add_point(false, &rules);
+    score.add_point(false, &rules);
+    assert_eq!((score.p1, score.p2), (21, 23));
+    assert!(score.check_victory(&rules));
+}
+
+#[test]
+fn twenty_one_point_game_is_won_at_twenty_one_without_win_by_two() {
+    let rules = rules_to(21, Tiebreak::SuddenDeath);
+    let mut score = new_match(&rules);
+
+    // 20 points is not enough, even with a big lead
+    for _ in 0..20 {
+        score.add_point(true, &rules);
+    }
+    assert!(!score.check_victory(&rules));
+
+    // From 20-20 the next point wins
+    let mut score = new_match(&rules);
+    trade_to(&mut score, 20, &rules);
+    assert!(score.is_match_point(&rules));
+    score.add_point(false, &rules);
+    assert_eq!((score.p1, score.p2), (20, 21));
+    assert!(score.check_victory(&rules));
+}
+
+#[test]
+fn reset_clears_points_and_pending_serve() {
+    let rules = rules(Tiebreak::SuddenDeath);
+    let mut score = new_match(&rules);
+    score.add_point(true, &rules);
+    score.should_serve = true;
+
+    score.reset(&rules);
+    assert_eq!((score.p1, score.p2), (0, 0));
+    assert_eq!(score.serve_count(), 0);
+    assert!(!score.should_serve);
+    assert_eq!(score.serve_timer().elapsed_secs(), 0.0);
+}